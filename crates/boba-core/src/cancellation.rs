@@ -0,0 +1,202 @@
+//! Cooperative cancellation for commands started with
+//! [`Command::future_with`](crate::Command::future_with).
+//!
+//! The runtime owns one [`CancellationToken`] per program run and cancels it
+//! when the program exits. Each task started with
+//! [`Command::subscription_task`](crate::Command::subscription_task) runs
+//! under a [child](CancellationToken::child_token) of it that
+//! [`Command::cancel`](crate::Command::cancel) cancels as well. Futures that
+//! hold a clone can watch it and stop cleanly instead of being dropped
+//! mid-operation.
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+tokio::task_local! {
+    /// The token of the program whose task is currently running.
+    static CURRENT: CancellationToken;
+}
+
+/// A cloneable flag that is set once and can be awaited.
+///
+/// All clones share the same state: cancelling any of them cancels every
+/// clone.
+///
+/// # Example
+///
+/// ```
+/// use boba_core::cancellation::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let observer = token.clone();
+/// assert!(!observer.is_cancelled());
+/// token.cancel();
+/// assert!(observer.is_cancelled());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    state: Arc<watch::Sender<bool>>,
+    /// Cancelling the parent cancels this token too.
+    parent: Option<Arc<CancellationToken>>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not yet cancelled.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(false)),
+            parent: None,
+        }
+    }
+
+    /// Create a token that is cancelled along with this one, but can also
+    /// be cancelled on its own without affecting this one.
+    ///
+    /// ```
+    /// use boba_core::cancellation::CancellationToken;
+    ///
+    /// let parent = CancellationToken::new();
+    /// let child = parent.child_token();
+    /// child.cancel();
+    /// assert!(!parent.is_cancelled());
+    ///
+    /// let child = parent.child_token();
+    /// parent.cancel();
+    /// assert!(child.is_cancelled());
+    /// ```
+    pub fn child_token(&self) -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(false)),
+            parent: Some(Arc::new(self.clone())),
+        }
+    }
+
+    /// Cancel the token, waking every task waiting on
+    /// [`cancelled`](Self::cancelled). Cancelling twice is a no-op.
+    pub fn cancel(&self) {
+        self.state.send_replace(true);
+    }
+
+    /// Return whether the token, or any token it was created from with
+    /// [`child_token`](Self::child_token), has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.lineage().any(|token| *token.state.borrow())
+    }
+
+    /// Wait until the token is cancelled. Resolves immediately if it
+    /// already is.
+    pub async fn cancelled(&self) {
+        let waits = self.lineage().map(|token| {
+            Box::pin(async move {
+                let mut rx = token.state.subscribe();
+                // The sender lives in `token`, so the channel cannot close
+                // here.
+                let _ = rx.wait_for(|cancelled| *cancelled).await;
+            })
+        });
+        futures::future::select_all(waits).await;
+    }
+
+    /// This token followed by its parents.
+    fn lineage(&self) -> impl Iterator<Item = &CancellationToken> {
+        std::iter::successors(Some(self), |token| token.parent.as_deref())
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run `fut` with `token` as the current task's cancellation token.
+pub(crate) async fn scope<F: Future>(token: CancellationToken, fut: F) -> F::Output {
+    CURRENT.scope(token, fut).await
+}
+
+/// The current task's cancellation token, or a fresh one that is never
+/// cancelled when the task was not started by the runtime.
+pub(crate) fn current() -> CancellationToken {
+    CURRENT.try_with(Clone::clone).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{Command, CommandInner};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancelled_resolves_after_cancel() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should finish")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn child_wakes_when_parent_is_cancelled() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let waiter = tokio::spawn({
+            let child = child.clone();
+            async move { child.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        parent.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("child should be cancelled with its parent")
+            .unwrap();
+        assert!(child.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn future_with_observes_scoped_token() {
+        let cmd = Command::future_with(|token| async move {
+            let mut ticks = 0u32;
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => return ticks,
+                    _ = tokio::time::sleep(Duration::from_millis(1)) => ticks += 1,
+                }
+            }
+        });
+        let CommandInner::Future(fut) = cmd.map(|ticks| ticks + 1000).inner else {
+            panic!("expected a future command");
+        };
+
+        let shutdown = CancellationToken::new();
+        let task = tokio::spawn(scope(shutdown.clone(), fut));
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(!task.is_finished());
+
+        // Quitting cancels the program's token; the future exits on its own.
+        shutdown.cancel();
+        let result = tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("future should stop after cancellation")
+            .unwrap();
+        assert!(result >= 1000);
+    }
+
+    #[tokio::test]
+    async fn current_outside_runtime_is_never_cancelled() {
+        assert!(!current().is_cancelled());
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(scope(token, async { current().is_cancelled() }).await);
+    }
+}
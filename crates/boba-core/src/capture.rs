@@ -0,0 +1,135 @@
+//! Snapshots of the program's screen, taken with
+//! [`Command::capture_frame`](crate::Command::capture_frame).
+//!
+//! The model is rendered off screen at the terminal's current size, so a
+//! capture shows exactly what the next frame would, without reading back
+//! from the terminal. Useful for bug reports and documentation.
+
+use std::io;
+use std::path::Path;
+
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Size;
+use ratatui::Terminal;
+
+use crate::model::Model;
+use crate::post_render::PostRender;
+
+/// One rendered frame, as cells.
+#[derive(Debug, Clone)]
+pub struct FrameCapture {
+    buffer: Buffer,
+}
+
+impl FrameCapture {
+    /// The captured cells.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The frame as plain text, one line per row.
+    ///
+    /// Trailing spaces and trailing blank rows are dropped.
+    pub fn text(&self) -> String {
+        let area = self.buffer.area;
+        let mut lines: Vec<String> = (area.top()..area.bottom())
+            .map(|y| {
+                let row: String = (area.left()..area.right())
+                    .map(|x| self.buffer[(x, y)].symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect();
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
+    /// The frame as text with ANSI escape codes for its colors and
+    /// modifiers, for viewing with `cat` or `less -R`.
+    pub fn ansi(&self) -> String {
+        let mut out = Vec::new();
+        // Writing to a Vec cannot fail.
+        let _ = crate::runtime::write_final_frame(&mut out, &self.buffer);
+        String::from_utf8_lossy(&out).replace("\r\n", "\n")
+    }
+
+    /// Write [`text`](Self::text) to the file at `path`, replacing it.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.text() + "\n")
+    }
+}
+
+/// Render `model` off screen at `size`, running `post_render` like a real
+/// frame.
+pub(crate) fn capture<M: Model>(
+    model: &M,
+    post_render: Option<&dyn PostRender>,
+    size: Size,
+) -> FrameCapture {
+    let mut terminal = Terminal::new(TestBackend::new(size.width, size.height))
+        .expect("a test backend never fails");
+    let completed = terminal
+        .draw(|frame| {
+            model.view(frame);
+            if let Some(hook) = post_render {
+                let area = frame.area();
+                hook.after_view(frame.buffer_mut(), area);
+            }
+        })
+        .expect("a test backend never fails");
+    FrameCapture {
+        buffer: completed.buffer.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use ratatui::style::{Color, Style};
+    use ratatui::widgets::{Block, Paragraph};
+    use ratatui::Frame;
+
+    struct Greeting;
+
+    impl Model for Greeting {
+        type Message = ();
+        type Flags = ();
+
+        fn init(_flags: ()) -> (Self, Command<()>) {
+            (Greeting, Command::none())
+        }
+
+        fn update(&mut self, _msg: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self, frame: &mut Frame) {
+            let area = frame.area();
+            let block = Block::bordered().title("hi");
+            frame.render_widget(
+                Paragraph::new("hello")
+                    .style(Style::default().fg(Color::Red))
+                    .block(block),
+                ratatui::layout::Rect::new(0, 0, 9, 3).intersection(area),
+            );
+        }
+    }
+
+    #[test]
+    fn text_matches_rendered_frame() {
+        let capture = capture(&Greeting, None, Size::new(12, 5));
+        assert_eq!(capture.buffer().area.width, 12);
+        assert_eq!(capture.text(), "┌hi─────┐\n│hello  │\n└───────┘");
+        // Styled like the screen, with plain newlines between rows.
+        let ansi = capture.ansi();
+        assert!(
+            ansi.contains("\u{1b}[38;5;1m│hello  │\u{1b}[0m"),
+            "{ansi:?}"
+        );
+        assert_eq!(ansi.lines().count(), 3);
+    }
+}
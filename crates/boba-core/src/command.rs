@@ -2,6 +2,7 @@ use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use std::future::Future;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// A side effect returned from [`Model::update`](crate::Model::update) or [`Model::init`](crate::Model::init).
 ///
@@ -42,6 +43,18 @@ pub(crate) enum CommandInner<Msg: Send + 'static> {
         cmd: ExecCommand,
         on_exit: Box<dyn FnOnce(std::io::Result<std::process::ExitStatus>) -> Msg + Send>,
     },
+    /// Run a closure against the program's terminal between frames.
+    WithTerminal(Box<dyn FnOnce(&mut crate::runtime::ProgramTerminal) + Send>),
+    /// Run a long-lived task registered under `key`, forwarding every
+    /// message it sends.
+    Task {
+        key: String,
+        stream: BoxStream<'static, Msg>,
+    },
+    /// Stop the task registered under the key.
+    Cancel(String),
+    /// Render the model off screen and send the result.
+    CaptureFrame(Box<dyn FnOnce(crate::capture::FrameCapture) -> Msg + Send>),
 }
 
 /// Internal action variants handled synchronously by the runtime.
@@ -53,6 +66,33 @@ pub enum Action<Msg> {
     Message(Msg),
     /// Quit the program.
     Quit,
+    /// Schedule a redraw without a message.
+    Redraw,
+}
+
+/// Why a command's task failed to deliver its message.
+///
+/// Passed to [`Model::on_command_error`](crate::Model::on_command_error).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum CommandError {
+    /// The task panicked. Holds the panic message, if it was a string.
+    #[error("command panicked: {0}")]
+    Panicked(String),
+}
+
+impl CommandError {
+    /// Build a [`CommandError::Panicked`] from a caught panic payload.
+    pub(crate) fn from_panic(payload: Box<dyn std::any::Any + Send>) -> Self {
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            (*s).to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        CommandError::Panicked(message)
+    }
 }
 
 /// Terminal management commands executed by the runtime.
@@ -91,15 +131,36 @@ pub enum TerminalCommand {
     ScrollUp(u16),
     /// Scroll the terminal viewport down by the given number of lines.
     ScrollDown(u16),
-    /// Print a line above the TUI (for inline mode).
+    /// Print a line above the TUI (for inline mode). Flushed immediately,
+    /// in order with the frames around it.
     Println(String),
-    /// Print formatted text above the TUI (for inline mode).
+    /// Print formatted text above the TUI (for inline mode). Flushed
+    /// immediately, in order with the frames around it.
     Printf(String),
+    /// Print one or more lines into the main screen's scrollback, leaving
+    /// the alternate screen around the write if it is active.
+    PrintAbove(String),
     /// Suspend the process (send SIGTSTP on Unix).
     Suspend,
+    /// Stop delivering subscription messages (other than terminal events)
+    /// without tearing the subscriptions down.
+    PauseSubscriptions,
+    /// Resume subscriptions paused by [`TerminalCommand::PauseSubscriptions`].
+    ResumeSubscriptions,
+    /// Change the target frame rate, clamped to `1..=120`. Takes effect
+    /// from the next frame.
+    SetFps(u32),
+    /// Write out anything still buffered in front of the terminal.
+    ///
+    /// Frames and the print commands flush themselves, so this is only
+    /// needed when something else writes to the same stream directly, such
+    /// as `println!` from another thread, and must not overtake output the
+    /// program has already produced.
+    Flush,
 }
 
-/// Configuration for executing an external process via [`Command::exec`].
+/// Configuration for executing an external process via [`Command::exec`]
+/// or [`Command::exec_output`].
 ///
 /// Build an `ExecCommand` using the builder pattern: call [`ExecCommand::new`],
 /// then chain [`arg`](ExecCommand::arg), [`args`](ExecCommand::args), and
@@ -152,6 +213,33 @@ pub enum MouseMode {
     AllMotion,
 }
 
+/// Delay policy between attempts of [`Command::retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Wait the same duration before every retry.
+    Fixed(Duration),
+    /// Start at `initial` and double after each failure, capped at `max`.
+    Exponential {
+        /// Delay before the first retry.
+        initial: Duration,
+        /// Upper bound for any single delay.
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Delay to wait after the given number of failed attempts (1-based).
+    pub fn delay(&self, failures: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(d) => d,
+            Backoff::Exponential { initial, max } => {
+                let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+                initial.saturating_mul(factor).min(max)
+            }
+        }
+    }
+}
+
 /// Terminal cursor shape styles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CursorStyle {
@@ -189,6 +277,161 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Run an async future that can observe cancellation, map the result to
+    /// a message.
+    ///
+    /// `f` receives a [`CancellationToken`](crate::cancellation::CancellationToken)
+    /// that the runtime cancels when the program exits. Long-running work can
+    /// watch it and wind down cleanly instead of being dropped mid-operation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::future_with(|token| async move {
+    ///     tokio::select! {
+    ///         _ = token.cancelled() => Msg::Stopped,
+    ///         result = download(url) => Msg::Downloaded(result),
+    ///     }
+    /// })
+    /// ```
+    pub fn future_with<F, Fut>(f: F) -> Self
+    where
+        F: FnOnce(crate::cancellation::CancellationToken) -> Fut + Send + 'static,
+        Fut: Future<Output = Msg> + Send + 'static,
+    {
+        Command {
+            inner: CommandInner::Future(Box::pin(async move {
+                f(crate::cancellation::current()).await
+            })),
+        }
+    }
+
+    /// Run an async future for the model's current
+    /// [`Generation`](crate::generation::Generation), map the result to a
+    /// message.
+    ///
+    /// The generation is read when the command is created. If it has been
+    /// [bumped](crate::generation::Generation::bump) by the time the future
+    /// resolves, the result belongs to a context the user has left and is
+    /// dropped without a message.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Msg::Select(id) => {
+    ///     self.generation.bump();
+    ///     Command::future_for_generation(&self.generation, load_details(id), Msg::Details)
+    /// }
+    /// ```
+    pub fn future_for_generation<F, T>(
+        generation: &crate::generation::Generation,
+        future: F,
+        map: impl FnOnce(T) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        use futures::StreamExt;
+        let generation = generation.clone();
+        let started = generation.current();
+        let result = futures::stream::once(async move {
+            let value = future.await;
+            generation.is_current(started).then(|| map(value))
+        });
+        Command {
+            inner: CommandInner::Stream(Box::pin(result.filter_map(|msg| async { msg }))),
+        }
+    }
+
+    /// Run CPU-bound or blocking work on tokio's blocking thread pool, map
+    /// the result to a message.
+    ///
+    /// Use this instead of [`Command::perform`] for synchronous work such as
+    /// parsing a large file, which would otherwise stall the async worker
+    /// and with it the event loop. A panic in `f` is reported through
+    /// [`Model::on_command_error`](crate::Model::on_command_error).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::spawn_blocking(move || parse_log(&path), Msg::LogParsed)
+    /// ```
+    pub fn spawn_blocking<F, T>(f: F, on_done: impl FnOnce(T) -> Msg + Send + 'static) -> Self
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        Command {
+            inner: CommandInner::Future(Box::pin(async move {
+                match tokio::task::spawn_blocking(|| crate::runtime::as_command_task(f)).await {
+                    Ok(value) => on_done(value),
+                    Err(err) => match err.try_into_panic() {
+                        Ok(payload) => std::panic::resume_unwind(payload),
+                        // Only happens while the runtime shuts down.
+                        Err(_) => std::future::pending().await,
+                    },
+                }
+            })),
+        }
+    }
+
+    /// Start a long-lived background task from `update` that can send any
+    /// number of messages, registered under `key` so that
+    /// [`Command::cancel`] can stop it later.
+    ///
+    /// `f` receives a sender for the program's messages. The task ends when
+    /// its future returns, when it is cancelled, or when the program exits.
+    /// Starting a task under a key that is already running replaces the old
+    /// task.
+    ///
+    /// Prefer [`Model::subscriptions`](crate::Model::subscriptions) for
+    /// sources that follow the model's state; this is for work that
+    /// `update` starts and stops imperatively. Inside a
+    /// [`Command::sequence`] the next step waits until the task ends or is
+    /// cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Msg::StartTail => Command::subscription_task("tail", |tx| async move {
+    ///     let mut lines = follow(&path).await;
+    ///     while let Some(line) = lines.next().await {
+    ///         if tx.send(Msg::Line(line)).is_err() {
+    ///             break;
+    ///         }
+    ///     }
+    /// }),
+    /// Msg::StopTail => Command::cancel("tail"),
+    /// ```
+    pub fn subscription_task<F, Fut>(key: impl Into<String>, f: F) -> Self
+    where
+        F: FnOnce(tokio::sync::mpsc::UnboundedSender<Msg>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        use futures::{FutureExt, StreamExt};
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = async move { f(tx).await }
+            .into_stream()
+            .filter_map(|()| async { None });
+        // Ends once the task has returned and dropped its sender.
+        let messages = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+        Command {
+            inner: CommandInner::Task {
+                key: key.into(),
+                stream: Box::pin(futures::stream::select(messages, task)),
+            },
+        }
+    }
+
+    /// Stop the task started by [`Command::subscription_task`] under `key`.
+    /// Does nothing if no such task is running.
+    pub fn cancel(key: impl Into<String>) -> Self {
+        Command {
+            inner: CommandInner::Cancel(key.into()),
+        }
+    }
+
     /// Send a message immediately.
     pub fn message(msg: Msg) -> Self {
         Command {
@@ -203,6 +446,17 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Draw the next frame without calling `update`.
+    ///
+    /// For state that changed outside of `update`, e.g. in
+    /// [`Model::after_render`](crate::Model::after_render). From outside the
+    /// program, use [`ProgramHandle::request_redraw`](crate::ProgramHandle::request_redraw).
+    pub fn redraw() -> Self {
+        Command {
+            inner: CommandInner::Action(Action::Redraw),
+        }
+    }
+
     /// Run multiple commands concurrently.
     pub fn batch(cmds: impl IntoIterator<Item = Command<Msg>>) -> Self {
         let cmds: Vec<_> = cmds.into_iter().collect();
@@ -218,6 +472,40 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Run commands concurrently, but with at most `max_concurrent` of them
+    /// in flight at once.
+    ///
+    /// Useful for fanning out many requests against a rate-limited service.
+    /// Messages are delivered as each command finishes, in no particular
+    /// order; the next queued command starts as soon as a slot frees up. As
+    /// with [`Command::and_then`], quit, terminal and exec actions inside the
+    /// batch are ignored. A limit of zero is treated as one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let fetches = urls
+    ///     .into_iter()
+    ///     .map(|url| Command::perform(fetch(url), Msg::Fetched));
+    /// Command::batch_limited(fetches, 4)
+    /// ```
+    pub fn batch_limited(
+        cmds: impl IntoIterator<Item = Command<Msg>>,
+        max_concurrent: usize,
+    ) -> Self {
+        use futures::StreamExt;
+        let cmds: Vec<_> = cmds.into_iter().collect();
+        if cmds.is_empty() {
+            return Command::none();
+        }
+        let stream = futures::stream::iter(cmds)
+            .map(Command::into_stream)
+            .flatten_unordered(max_concurrent.max(1));
+        Command {
+            inner: CommandInner::Stream(Box::pin(stream)),
+        }
+    }
+
     /// Run commands sequentially — each command's messages are delivered
     /// before the next command starts.
     pub fn sequence(cmds: impl IntoIterator<Item = Command<Msg>>) -> Self {
@@ -234,6 +522,28 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Build a command only if `condition` holds; otherwise
+    /// [`Command::none`]. `f` is not called when the condition is false.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::when(self.dirty, || Command::message(Msg::Save))
+    /// ```
+    pub fn when(condition: bool, f: impl FnOnce() -> Command<Msg>) -> Self {
+        if condition {
+            f()
+        } else {
+            Command::none()
+        }
+    }
+
+    /// Build a command only if `condition` does not hold; the inverse of
+    /// [`Command::when`].
+    pub fn unless(condition: bool, f: impl FnOnce() -> Command<Msg>) -> Self {
+        Command::when(!condition, f)
+    }
+
     /// Terminal management command.
     pub fn terminal(cmd: TerminalCommand) -> Self {
         Command {
@@ -257,6 +567,7 @@ impl<Msg: Send + 'static> Command<Msg> {
             CommandInner::None => Command::none(),
             CommandInner::Action(Action::Message(msg)) => Command::message(f(msg)),
             CommandInner::Action(Action::Quit) => Command::quit(),
+            CommandInner::Action(Action::Redraw) => Command::redraw(),
             CommandInner::Future(fut) => {
                 let f = f.clone();
                 Command {
@@ -284,7 +595,31 @@ impl<Msg: Send + 'static> Command<Msg> {
                         .collect(),
                 ),
             },
+            CommandInner::Task { key, stream } => {
+                use futures::StreamExt;
+                let f = f.clone();
+                Command {
+                    inner: CommandInner::Task {
+                        key,
+                        stream: Box::pin(stream.map(move |msg| f(msg))),
+                    },
+                }
+            }
+            CommandInner::Cancel(key) => Command {
+                inner: CommandInner::Cancel(key),
+            },
             CommandInner::Terminal(tcmd) => Command::terminal(tcmd),
+            CommandInner::WithTerminal(f) => Command {
+                inner: CommandInner::WithTerminal(f),
+            },
+            CommandInner::CaptureFrame(on_captured) => {
+                let f = f.clone();
+                Command {
+                    inner: CommandInner::CaptureFrame(Box::new(move |capture| {
+                        f(on_captured(capture))
+                    })),
+                }
+            }
             CommandInner::Exec { cmd, on_exit } => {
                 let f = f.clone();
                 Command {
@@ -312,6 +647,39 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Run an external process headlessly, capturing its stdout and stderr.
+    ///
+    /// Unlike [`Command::exec`], the terminal stays under the program's
+    /// control: the child gets a null stdin and its output is collected
+    /// instead of drawn. Because it never touches the terminal it also runs
+    /// inside [`Command::sequence`], so steps like "build, then test, then
+    /// show results" execute in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::sequence([
+    ///     Command::exec_output(ExecCommand::new("cargo").arg("build"), Msg::Built),
+    ///     Command::exec_output(ExecCommand::new("cargo").arg("test"), Msg::Tested),
+    /// ])
+    /// ```
+    pub fn exec_output(
+        cmd: ExecCommand,
+        on_output: impl FnOnce(std::io::Result<std::process::Output>) -> Msg + Send + 'static,
+    ) -> Self {
+        let mut process = tokio::process::Command::new(&cmd.program);
+        process
+            .args(&cmd.args)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+        if let Some(dir) = &cmd.working_dir {
+            process.current_dir(dir);
+        }
+        Command {
+            inner: CommandInner::Future(Box::pin(async move { on_output(process.output().await) })),
+        }
+    }
+
     /// One-shot timer: fires once after `duration`, mapping the instant to a message.
     pub fn tick(
         duration: std::time::Duration,
@@ -325,6 +693,190 @@ impl<Msg: Send + 'static> Command<Msg> {
         }
     }
 
+    /// Bounded repeating timer: after `delay`, emit `count` messages spaced
+    /// `interval` apart, then finish.
+    ///
+    /// `map` receives the zero-based frame index. Handy for short one-shot
+    /// animations, where an [`Every`](crate::subscriptions::Every)
+    /// subscription would need a counter in the model to stop it. Frames are
+    /// scheduled from the start time, so slow updates don't make the
+    /// animation drift.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Intro: after 200ms, ten frames 50ms apart.
+    /// Command::repeat(
+    ///     Duration::from_millis(200),
+    ///     Duration::from_millis(50),
+    ///     10,
+    ///     Msg::IntroFrame,
+    /// )
+    /// ```
+    pub fn repeat(
+        delay: Duration,
+        interval: Duration,
+        count: usize,
+        map: impl Fn(usize) -> Msg + Send + 'static,
+    ) -> Self {
+        if count == 0 {
+            return Command::none();
+        }
+        let stream =
+            futures::stream::unfold((0, None::<tokio::time::Instant>), move |(frame, start)| {
+                let due = (frame < count).then(|| {
+                    let start = start.unwrap_or_else(tokio::time::Instant::now);
+                    (start, start + delay + interval * frame as u32)
+                });
+                async move {
+                    let (start, due) = due?;
+                    tokio::time::sleep_until(due).await;
+                    Some((frame, (frame + 1, Some(start))))
+                }
+            });
+        Command {
+            inner: CommandInner::Stream(Box::pin(futures::StreamExt::map(stream, map))),
+        }
+    }
+
+    /// Run a fallible future, retrying on `Err` with a delay between attempts.
+    ///
+    /// `make_future` is called once per attempt. After a failure the command
+    /// waits for `backoff` and tries again, up to `max_attempts` attempts in
+    /// total (at least one). The first `Ok`, or the last `Err`, is mapped
+    /// through `on_result` into a message. Like [`perform`](Command::perform),
+    /// this runs as a spawned task and never blocks the event loop.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::retry(
+    ///     || fetch_branches(),
+    ///     3,
+    ///     Backoff::Exponential { initial: Duration::from_millis(200), max: Duration::from_secs(2) },
+    ///     Msg::BranchesLoaded,
+    /// )
+    /// ```
+    pub fn retry<F, Fut, T, E>(
+        mut make_future: F,
+        max_attempts: u32,
+        backoff: Backoff,
+        on_result: impl FnOnce(Result<T, E>) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        let max_attempts = max_attempts.max(1);
+        Command {
+            inner: CommandInner::Future(Box::pin(async move {
+                let mut failures = 0;
+                loop {
+                    match make_future().await {
+                        Err(_) if failures + 1 < max_attempts => {
+                            failures += 1;
+                            tokio::time::sleep(backoff.delay(failures)).await;
+                        }
+                        result => return on_result(result),
+                    }
+                }
+            })),
+        }
+    }
+
+    /// Run `future`, then build the follow-up command from its output.
+    ///
+    /// Unlike [`Command::sequence`], the next step can depend on the value the
+    /// first step produced. The follow-up command's messages are delivered as
+    /// they arrive; like commands inside a sequence, any quit, terminal or exec
+    /// actions it contains are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// Command::and_then(login(creds), |token| {
+    ///     Command::perform(fetch_profile(token), Msg::ProfileLoaded)
+    /// })
+    /// ```
+    pub fn and_then<F, T>(future: F, next: impl FnOnce(T) -> Command<Msg> + Send + 'static) -> Self
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        use futures::StreamExt;
+        let stream = futures::stream::once(async move { next(future.await).into_stream() });
+        Command {
+            inner: CommandInner::Stream(Box::pin(stream.flatten())),
+        }
+    }
+
+    /// Flatten this command into the stream of messages it produces.
+    ///
+    /// Batches are merged concurrently and sequences run in order. Quit,
+    /// terminal and exec actions have no message form and are dropped.
+    fn into_stream(self) -> BoxStream<'static, Msg> {
+        use futures::stream::{self, StreamExt};
+        match self.inner {
+            CommandInner::Action(Action::Message(msg)) => Box::pin(stream::once(async { msg })),
+            CommandInner::Future(fut) => Box::pin(stream::once(fut)),
+            CommandInner::Stream(stream) | CommandInner::Task { stream, .. } => stream,
+            CommandInner::Batch(cmds) => Box::pin(stream::select_all(
+                cmds.into_iter().map(Command::into_stream),
+            )),
+            CommandInner::Sequence(cmds) => {
+                Box::pin(stream::iter(cmds).flat_map(Command::into_stream))
+            }
+            CommandInner::None
+            | CommandInner::Action(Action::Quit)
+            | CommandInner::Action(Action::Redraw)
+            | CommandInner::Terminal(_)
+            | CommandInner::WithTerminal(_)
+            | CommandInner::CaptureFrame(_)
+            | CommandInner::Cancel(_)
+            | CommandInner::Exec { .. } => Box::pin(stream::empty()),
+        }
+    }
+
+    /// Run `f` against the program's terminal between two frames.
+    ///
+    /// See [`Program::with_terminal`](crate::Program::with_terminal) for
+    /// what is safe to do there. Ignored inside [`Command::sequence`]. If the
+    /// terminal is released or can't be flushed, `f` doesn't run and the
+    /// reason goes to the debug log.
+    pub fn with_terminal(
+        f: impl FnOnce(&mut crate::runtime::ProgramTerminal) + Send + 'static,
+    ) -> Self {
+        Command {
+            inner: CommandInner::WithTerminal(Box::new(f)),
+        }
+    }
+
+    /// Render the model as the next frame would look and send the result.
+    ///
+    /// The frame is drawn off screen at the terminal's current size, so
+    /// nothing on screen changes. Ignored inside [`Command::sequence`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // On F12, dump the screen for a bug report.
+    /// Command::capture_frame(Msg::Captured)
+    ///
+    /// // In update:
+    /// Msg::Captured(frame) => {
+    ///     let _ = frame.save("screen.txt");
+    /// }
+    /// ```
+    pub fn capture_frame(
+        on_captured: impl FnOnce(crate::capture::FrameCapture) -> Msg + Send + 'static,
+    ) -> Self {
+        Command {
+            inner: CommandInner::CaptureFrame(Box::new(on_captured)),
+        }
+    }
+
     /// Request the current window size. The callback receives (columns, rows).
     pub fn window_size(map: impl FnOnce(u16, u16) -> Msg + Send + 'static) -> Self {
         Command {
@@ -345,6 +897,16 @@ impl<Msg: Send + 'static> Command<Msg> {
         Command::terminal(TerminalCommand::Printf(text.into()))
     }
 
+    /// Print text into the terminal scrollback above the TUI.
+    ///
+    /// Unlike [`println`](Command::println), this also works in alt-screen
+    /// mode: the runtime briefly leaves the alternate screen, prints each
+    /// line of `text` to the main screen, and re-enters, so log lines
+    /// accumulate in scrollback behind a persistent UI.
+    pub fn print_above(text: impl Into<String>) -> Self {
+        Command::terminal(TerminalCommand::PrintAbove(text.into()))
+    }
+
     // Convenience terminal command constructors
 
     /// Switch to the alternate screen buffer.
@@ -387,7 +949,8 @@ impl<Msg: Send + 'static> Command<Msg> {
         Command::terminal(TerminalCommand::SetTitle(title.into()))
     }
 
-    /// Clear the entire terminal screen.
+    /// Clear the entire terminal screen, or only the program's
+    /// [`region`](crate::ProgramOptions::region) if it has one.
     pub fn clear_screen() -> Self {
         Command::terminal(TerminalCommand::ClearScreen)
     }
@@ -407,6 +970,30 @@ impl<Msg: Send + 'static> Command<Msg> {
         Command::terminal(TerminalCommand::Suspend)
     }
 
+    /// Pause all subscriptions except terminal events, e.g. to stop
+    /// animation timers while a modal takes over. Subscriptions keep their
+    /// identity; ticks that fire while paused are dropped.
+    pub fn pause_subscriptions() -> Self {
+        Command::terminal(TerminalCommand::PauseSubscriptions)
+    }
+
+    /// Resume subscriptions paused by [`pause_subscriptions`](Command::pause_subscriptions).
+    pub fn resume_subscriptions() -> Self {
+        Command::terminal(TerminalCommand::ResumeSubscriptions)
+    }
+
+    /// Change the frame rate while running, e.g. raise it for the duration
+    /// of an animation and drop it again when idle to save power. Clamped
+    /// to `1..=120` like [`ProgramOptions::fps`](crate::ProgramOptions::fps).
+    pub fn set_fps(fps: u32) -> Self {
+        Command::terminal(TerminalCommand::SetFps(fps))
+    }
+
+    /// Flush buffered terminal output. See [`TerminalCommand::Flush`].
+    pub fn flush() -> Self {
+        Command::terminal(TerminalCommand::Flush)
+    }
+
     // --- Inspection methods (useful for testing) ---
 
     /// Returns `true` if this is a no-op command.
@@ -431,6 +1018,39 @@ impl<Msg: Send + 'static> Command<Msg> {
     }
 }
 
+/// Combinators for commands whose messages are results, such as
+/// `Command::perform(load(path), |r| r)` or a [`Command::retry`].
+///
+/// They convert each result before it reaches the model, so the final
+/// [`map`](Command::map) into the model's message only sees the types the
+/// model uses.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// Command::perform(tokio::fs::read_to_string(path), |r| r)
+///     .map_err(|e| e.to_string())
+///     .map_ok(|text| text.lines().count())
+///     .map(Msg::Counted)
+/// ```
+impl<T: Send + 'static, E: Send + 'static> Command<Result<T, E>> {
+    /// Convert the value of every `Ok` message.
+    pub fn map_ok<U: Send + 'static>(
+        self,
+        f: impl Fn(T) -> U + Send + Sync + 'static,
+    ) -> Command<Result<U, E>> {
+        self.map(move |result| result.map(&f))
+    }
+
+    /// Convert the error of every `Err` message.
+    pub fn map_err<F: Send + 'static>(
+        self,
+        f: impl Fn(E) -> F + Send + Sync + 'static,
+    ) -> Command<Result<T, F>> {
+        self.map(move |result| result.map_err(&f))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -450,12 +1070,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn when_builds_only_if_condition_holds() {
+        let mut built = 0;
+        let mut build = || {
+            built += 1;
+            Command::message(7)
+        };
+        assert_eq!(Command::when(true, &mut build).into_message(), Some(7));
+        assert!(Command::when(false, &mut build).is_none());
+        assert!(Command::unless(true, &mut build).is_none());
+        assert_eq!(Command::unless(false, &mut build).into_message(), Some(7));
+        assert_eq!(built, 2);
+    }
+
     #[test]
     fn command_quit_creates_quit() {
         let cmd: Command<()> = Command::quit();
         assert!(matches!(cmd.inner, CommandInner::Action(Action::Quit)));
     }
 
+    #[test]
+    fn command_redraw_survives_map() {
+        let cmd: Command<i32> = Command::redraw();
+        assert!(matches!(
+            cmd.map(|n| n + 1).inner,
+            CommandInner::Action(Action::Redraw)
+        ));
+    }
+
     #[test]
     fn command_batch_empty_returns_none() {
         let cmd: Command<()> = Command::batch(vec![]);
@@ -549,5 +1192,290 @@ mod tests {
             CommandInner::Terminal(TerminalCommand::SetTitle(s)) => assert_eq!(s, "test"),
             _ => panic!("Expected SetTitle"),
         }
+
+        let cmd: Command<()> = Command::print_above("log line");
+        match cmd.inner {
+            CommandInner::Terminal(TerminalCommand::PrintAbove(s)) => assert_eq!(s, "log line"),
+            _ => panic!("Expected PrintAbove"),
+        }
+
+        let cmd: Command<()> = Command::pause_subscriptions();
+        assert!(matches!(
+            cmd.inner,
+            CommandInner::Terminal(TerminalCommand::PauseSubscriptions)
+        ));
+
+        let cmd: Command<()> = Command::resume_subscriptions();
+        assert!(matches!(
+            cmd.inner,
+            CommandInner::Terminal(TerminalCommand::ResumeSubscriptions)
+        ));
+
+        let cmd: Command<()> = Command::set_fps(10);
+        assert!(matches!(
+            cmd.inner,
+            CommandInner::Terminal(TerminalCommand::SetFps(10))
+        ));
+
+        let cmd: Command<()> = Command::flush();
+        assert!(matches!(
+            cmd.inner,
+            CommandInner::Terminal(TerminalCommand::Flush)
+        ));
+    }
+
+    fn flaky(
+        attempts: std::sync::Arc<std::sync::atomic::AtomicU32>,
+        failures: u32,
+    ) -> impl FnMut() -> BoxFuture<'static, Result<u32, String>> + Send + 'static {
+        use std::sync::atomic::Ordering;
+        move || {
+            let attempts = attempts.clone();
+            Box::pin(async move {
+                let n = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                if n <= failures {
+                    Err(format!("attempt {n} failed"))
+                } else {
+                    Ok(n)
+                }
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Loaded {
+        Lines(usize),
+        Failed(String),
+    }
+
+    fn deliver(result: Result<usize, String>) -> Loaded {
+        match result {
+            Ok(n) => Loaded::Lines(n),
+            Err(e) => Loaded::Failed(e),
+        }
+    }
+
+    #[tokio::test]
+    async fn map_err_converts_error_before_delivery() {
+        let cmd = Command::perform(
+            async { Err::<&str, std::io::Error>(std::io::ErrorKind::NotFound.into()) },
+            |r| r,
+        )
+        .map_err(|e| format!("{:?}", e.kind()))
+        .map_ok(|text| text.lines().count())
+        .map(deliver);
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("Expected Future");
+        };
+        assert_eq!(fut.await, Loaded::Failed("NotFound".into()));
+
+        let cmd = Command::perform(async { Ok::<_, std::io::Error>("a\nb") }, |r| r)
+            .map_err(|e| e.to_string())
+            .map_ok(|text| text.lines().count())
+            .map(deliver);
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("Expected Future");
+        };
+        assert_eq!(fut.await, Loaded::Lines(2));
+    }
+
+    #[tokio::test]
+    async fn retry_succeeds_after_transient_failures() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        let attempts = std::sync::Arc::new(AtomicU32::new(0));
+        let cmd: Command<Result<u32, String>> = Command::retry(
+            flaky(attempts.clone(), 2),
+            5,
+            Backoff::Fixed(Duration::from_millis(1)),
+            |r| r,
+        );
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("Expected Future");
+        };
+        assert_eq!(fut.await, Ok(3));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_after_max_attempts() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        let attempts = std::sync::Arc::new(AtomicU32::new(0));
+        let cmd: Command<Result<u32, String>> = Command::retry(
+            flaky(attempts.clone(), 10),
+            3,
+            Backoff::Fixed(Duration::from_millis(1)),
+            |r| r,
+        );
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("Expected Future");
+        };
+        assert_eq!(fut.await, Err("attempt 3 failed".to_string()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn exponential_backoff_doubles_up_to_max() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(350),
+        };
+        assert_eq!(backoff.delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(200));
+        assert_eq!(backoff.delay(3), Duration::from_millis(350));
+        assert_eq!(backoff.delay(40), Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn stale_generation_drops_future_result() {
+        use crate::generation::Generation;
+        use futures::StreamExt;
+        use tokio::sync::oneshot;
+
+        let generation = Generation::new();
+        let (tx, rx) = oneshot::channel();
+        let stale: Command<u32> =
+            Command::future_for_generation(&generation, async { rx.await.unwrap() }, |v| v);
+        // The user moves on before the first request finishes.
+        generation.bump();
+        let fresh: Command<u32> = Command::future_for_generation(&generation, async { 2 }, |v| v);
+        tx.send(1).unwrap();
+
+        let CommandInner::Stream(stale) = stale.inner else {
+            panic!("Expected Stream");
+        };
+        assert_eq!(stale.collect::<Vec<_>>().await, Vec::<u32>::new());
+        let CommandInner::Stream(fresh) = fresh.inner else {
+            panic!("Expected Stream");
+        };
+        assert_eq!(fresh.collect::<Vec<_>>().await, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn and_then_builds_follow_up_from_value() {
+        use futures::StreamExt;
+        let cmd: Command<String> = Command::and_then(async { 21 }, |n| {
+            Command::perform(async move { n * 2 }, |v: i32| format!("got {v}"))
+        });
+        let CommandInner::Stream(stream) = cmd.inner else {
+            panic!("Expected Stream");
+        };
+        let msgs: Vec<String> = stream.collect().await;
+        assert_eq!(msgs, vec!["got 42".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn and_then_delivers_every_follow_up_message() {
+        use futures::StreamExt;
+        let cmd: Command<i32> = Command::and_then(async { 3 }, |n| {
+            Command::sequence([
+                Command::message(n),
+                Command::none(),
+                Command::quit(),
+                Command::perform(async move { n + 1 }, |v| v),
+            ])
+        });
+        let CommandInner::Stream(stream) = cmd.inner else {
+            panic!("Expected Stream");
+        };
+        let msgs: Vec<i32> = stream.collect().await;
+        assert_eq!(msgs, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn spawn_blocking_keeps_async_worker_free() {
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        let cmd: Command<String> = Command::spawn_blocking(
+            move || {
+                wait.recv().unwrap();
+                6 * 7
+            },
+            |n: i32| format!("parsed {n}"),
+        );
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("expected a future command");
+        };
+        let blocking = tokio::spawn(fut);
+
+        // The single-threaded runtime still delivers other messages while
+        // the closure is blocked.
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move { tx.send("tick") });
+        let msg = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await;
+        assert_eq!(msg.unwrap(), Some("tick"));
+        assert!(!blocking.is_finished());
+
+        release.send(()).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(1), blocking)
+            .await
+            .expect("blocking work should finish")
+            .unwrap();
+        assert_eq!(result, "parsed 42");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn repeat_emits_count_frames_after_delay() {
+        use futures::StreamExt;
+        let cmd: Command<usize> = Command::repeat(
+            Duration::from_millis(200),
+            Duration::from_millis(50),
+            10,
+            |frame| frame,
+        );
+        let CommandInner::Stream(stream) = cmd.inner else {
+            panic!("Expected Stream");
+        };
+
+        let start = tokio::time::Instant::now();
+        let frames: Vec<(usize, Duration)> =
+            stream.map(|frame| (frame, start.elapsed())).collect().await;
+
+        let expected: Vec<(usize, Duration)> = (0..10)
+            .map(|i| (i, Duration::from_millis(200 + 50 * i as u64)))
+            .collect();
+        assert_eq!(frames, expected);
+    }
+
+    #[test]
+    fn repeat_zero_times_is_none() {
+        let cmd: Command<usize> = Command::repeat(Duration::ZERO, Duration::ZERO, 0, |f| f);
+        assert!(cmd.is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_limited_caps_concurrency() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let cmds = (0..10).map(|i| {
+            let running = running.clone();
+            let peak = peak.clone();
+            Command::perform(
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    i
+                },
+                |i| i,
+            )
+        });
+        let cmd: Command<i32> = Command::batch_limited(cmds, 3);
+        let CommandInner::Stream(stream) = cmd.inner else {
+            panic!("Expected Stream");
+        };
+        let mut msgs: Vec<i32> = stream.collect().await;
+        msgs.sort();
+        assert_eq!(msgs, (0..10).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn batch_limited_empty_is_none() {
+        let cmd: Command<()> = Command::batch_limited(Vec::new(), 2);
+        assert!(cmd.is_none());
     }
 }
@@ -1,6 +1,11 @@
 use crate::command::Command;
+use crate::event::{TerminalEvent, TerminalMessage};
 use crate::subscription::Subscription;
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    Frame,
+};
 
 /// A reusable sub-model that renders into a given [`Rect`] area.
 ///
@@ -108,6 +113,28 @@ pub trait Component: Send + 'static {
         vec![]
     }
 
+    /// Report the preferred `(width, height)` constraints for this component.
+    ///
+    /// Layout helpers can use the hint to size children without the parent
+    /// hard-coding dimensions. `available` is the space the parent could
+    /// offer. The default implementation is permissive and returns
+    /// `(Min(0), Min(0))`.
+    fn size_hint(&self, available: Rect) -> (Constraint, Constraint) {
+        let _ = available;
+        (Constraint::Min(0), Constraint::Min(0))
+    }
+
+    /// Render the component's content as styled lines for a given width.
+    ///
+    /// This lets a parent embed the component's output in a scroll
+    /// container such as a viewport instead of drawing it into a fixed
+    /// region. Components that can't be expressed as plain lines return
+    /// `None`, which is the default.
+    fn render_lines(&self, width: u16) -> Option<Vec<Line<'static>>> {
+        let _ = width;
+        None
+    }
+
     /// Whether this component currently has focus.
     ///
     /// This is a hint for input routing.  A parent can query `focused()` to
@@ -116,4 +143,116 @@ pub trait Component: Send + 'static {
     fn focused(&self) -> bool {
         false
     }
+
+    /// Whether this component has transient state that Esc would dismiss,
+    /// such as an open dropdown, an active filter or a modal.
+    ///
+    /// A parent can ask this before routing Esc: forward it to the child
+    /// when `true`, otherwise treat it as its own (go back, quit, ...). The
+    /// default implementation returns `false`.
+    fn wants_escape(&self) -> bool {
+        false
+    }
+
+    /// Turn a raw terminal event into this component's message and run
+    /// [`update`](Component::update) with it. Events the component takes no
+    /// message for return [`Command::none`].
+    ///
+    /// Focus is left to the component: widgets ignore keyboard input while
+    /// unfocused.
+    ///
+    /// ```rust,ignore
+    /// Msg::Terminal(event) => self.input.handle_terminal_event(event).map(Msg::Input),
+    /// ```
+    fn handle_terminal_event(&mut self, event: TerminalEvent) -> Command<Self::Message>
+    where
+        Self: Sized,
+        Self::Message: TerminalMessage,
+    {
+        match Self::Message::from_terminal_event(event) {
+            Some(msg) => self.update(msg),
+            None => Command::none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Plain;
+
+    impl Component for Plain {
+        type Message = ();
+
+        fn update(&mut self, _msg: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self, _frame: &mut Frame, _area: Rect) {}
+    }
+
+    #[test]
+    fn default_size_hint_is_permissive() {
+        let hint = Plain.size_hint(Rect::new(0, 0, 80, 24));
+        assert_eq!(hint, (Constraint::Min(0), Constraint::Min(0)));
+    }
+
+    #[test]
+    fn default_render_lines_is_none() {
+        assert!(Plain.render_lines(80).is_none());
+    }
+
+    #[test]
+    fn default_does_not_want_escape() {
+        assert!(!Plain.wants_escape());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum EchoMsg {
+        Key(crossterm::event::KeyCode),
+        Paste(String),
+    }
+
+    impl TerminalMessage for EchoMsg {
+        fn key_press(key: crossterm::event::KeyEvent) -> Option<Self> {
+            Some(EchoMsg::Key(key.code))
+        }
+
+        fn paste(text: String) -> Option<Self> {
+            Some(EchoMsg::Paste(text))
+        }
+    }
+
+    /// Echoes every message back as a command.
+    struct Echo;
+
+    impl Component for Echo {
+        type Message = EchoMsg;
+
+        fn update(&mut self, msg: EchoMsg) -> Command<EchoMsg> {
+            Command::message(msg)
+        }
+
+        fn view(&self, _frame: &mut Frame, _area: Rect) {}
+    }
+
+    #[test]
+    fn terminal_events_are_routed_to_update() {
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let key = TerminalEvent::Key(KeyEvent::from(KeyCode::Char('x')));
+        assert_eq!(
+            Echo.handle_terminal_event(key).into_message(),
+            Some(EchoMsg::Key(KeyCode::Char('x')))
+        );
+        let paste = TerminalEvent::Paste("hi".into());
+        assert_eq!(
+            Echo.handle_terminal_event(paste).into_message(),
+            Some(EchoMsg::Paste("hi".into()))
+        );
+        assert!(Echo
+            .handle_terminal_event(TerminalEvent::Resize(80, 24))
+            .is_none());
+    }
 }
@@ -1,4 +1,5 @@
 use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::layout::{Position, Rect};
 
 /// Terminal events produced by the runtime's event loop.
 ///
@@ -28,6 +29,15 @@ pub enum TerminalEvent {
     /// A keyboard event.
     Key(KeyEvent),
     /// A mouse event.
+    ///
+    /// Only delivered when mouse capture is on, either through
+    /// [`ProgramOptions::mouse_mode`](crate::ProgramOptions::mouse_mode) or
+    /// [`Command::enable_mouse_capture`](crate::Command::enable_mouse_capture).
+    /// The event's `kind` distinguishes presses (`Down`), releases (`Up`),
+    /// drags (`Drag`), wheel scrolling (`ScrollUp`/`ScrollDown`) and, with
+    /// [`MouseMode::AllMotion`](crate::MouseMode::AllMotion), hover (`Moved`).
+    /// Coordinates are absolute terminal cells; use [`hit_test`] to translate
+    /// them into a widget's rendered area.
     Mouse(MouseEvent),
     /// Terminal resized to (columns, rows).
     Resize(u16, u16),
@@ -39,6 +49,157 @@ pub enum TerminalEvent {
     Paste(String),
 }
 
+/// A widget message type that terminal events can be turned into.
+///
+/// Most widgets take input through the same few message variants, such as
+/// `KeyPress(KeyEvent)` and `Paste(String)`. Implementing this trait for a
+/// widget's message type lets a host forward raw terminal events with
+/// [`Component::handle_terminal_event`](crate::Component::handle_terminal_event)
+/// instead of writing the same plumbing for every widget. Each method
+/// returns `None` for input the widget doesn't take, which is the default.
+/// [`impl_terminal_message!`](crate::impl_terminal_message) writes the
+/// usual implementation.
+///
+/// # Example
+///
+/// ```rust
+/// use boba_core::event::TerminalMessage;
+/// use boba_core::TerminalEvent;
+/// use crossterm::event::{KeyCode, KeyEvent};
+///
+/// enum Message {
+///     KeyPress(KeyEvent),
+///     Paste(String),
+/// }
+///
+/// impl TerminalMessage for Message {
+///     fn key_press(key: KeyEvent) -> Option<Self> {
+///         Some(Message::KeyPress(key))
+///     }
+///
+///     fn paste(text: String) -> Option<Self> {
+///         Some(Message::Paste(text))
+///     }
+/// }
+///
+/// let key = TerminalEvent::Key(KeyEvent::from(KeyCode::Enter));
+/// assert!(matches!(Message::from_terminal_event(key), Some(Message::KeyPress(_))));
+/// assert!(Message::from_terminal_event(TerminalEvent::FocusLost).is_none());
+/// ```
+pub trait TerminalMessage: Sized {
+    /// The message for a key press.
+    fn key_press(key: KeyEvent) -> Option<Self> {
+        let _ = key;
+        None
+    }
+
+    /// The message for pasted text.
+    fn paste(text: String) -> Option<Self> {
+        let _ = text;
+        None
+    }
+
+    /// The message for a mouse event.
+    fn mouse(mouse: MouseEvent) -> Option<Self> {
+        let _ = mouse;
+        None
+    }
+
+    /// Dispatch `event` to the method for its kind. Resize and focus
+    /// events have no widget message.
+    fn from_terminal_event(event: TerminalEvent) -> Option<Self> {
+        match event {
+            TerminalEvent::Key(key) => Self::key_press(key),
+            TerminalEvent::Paste(text) => Self::paste(text),
+            TerminalEvent::Mouse(mouse) => Self::mouse(mouse),
+            TerminalEvent::Resize(..) | TerminalEvent::FocusGained | TerminalEvent::FocusLost => {
+                None
+            }
+        }
+    }
+}
+
+/// Implement [`TerminalMessage`] for a message type by naming the variant
+/// each kind of input goes to.
+///
+/// Any of `key_press`, `paste` and `mouse` can be listed; the others take
+/// no input.
+///
+/// ```rust
+/// use boba_core::impl_terminal_message;
+/// use boba_core::event::TerminalMessage;
+/// use boba_core::TerminalEvent;
+/// use crossterm::event::{KeyCode, KeyEvent};
+///
+/// enum Message {
+///     KeyPress(KeyEvent),
+///     Paste(String),
+/// }
+///
+/// impl_terminal_message!(Message {
+///     key_press: KeyPress,
+///     paste: Paste,
+/// });
+///
+/// let key = TerminalEvent::Key(KeyEvent::from(KeyCode::Enter));
+/// assert!(matches!(Message::from_terminal_event(key), Some(Message::KeyPress(_))));
+/// ```
+#[macro_export]
+macro_rules! impl_terminal_message {
+    ($ty:ty { $($method:ident: $variant:ident),* $(,)? }) => {
+        impl $crate::event::TerminalMessage for $ty {
+            $($crate::impl_terminal_message!(@method $method $variant);)*
+        }
+    };
+    (@method key_press $variant:ident) => {
+        fn key_press(key: ::crossterm::event::KeyEvent) -> Option<Self> {
+            Some(Self::$variant(key))
+        }
+    };
+    (@method paste $variant:ident) => {
+        fn paste(text: String) -> Option<Self> {
+            Some(Self::$variant(text))
+        }
+    };
+    (@method mouse $variant:ident) => {
+        fn mouse(mouse: ::crossterm::event::MouseEvent) -> Option<Self> {
+            Some(Self::$variant(mouse))
+        }
+    };
+}
+
+/// The terminal cell a mouse event occurred at.
+pub fn mouse_position(mouse: &MouseEvent) -> Position {
+    Position::new(mouse.column, mouse.row)
+}
+
+/// Translate a mouse event into coordinates relative to `area`.
+///
+/// Returns `None` when the event lies outside `area`. Widgets can remember
+/// the rect they were last rendered into and hit-test forwarded events
+/// against it:
+///
+/// ```
+/// use boba_core::event::hit_test;
+/// use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+/// use ratatui::layout::{Position, Rect};
+///
+/// let click = MouseEvent {
+///     kind: MouseEventKind::Down(MouseButton::Left),
+///     column: 12,
+///     row: 4,
+///     modifiers: KeyModifiers::NONE,
+/// };
+/// let area = Rect::new(10, 3, 20, 5);
+/// assert_eq!(hit_test(&click, area), Some(Position::new(2, 1)));
+/// assert_eq!(hit_test(&click, Rect::new(0, 0, 5, 5)), None);
+/// ```
+pub fn hit_test(mouse: &MouseEvent, area: Rect) -> Option<Position> {
+    let pos = mouse_position(mouse);
+    area.contains(pos)
+        .then(|| Position::new(pos.x - area.x, pos.y - area.y))
+}
+
 impl From<crossterm::event::Event> for TerminalEvent {
     fn from(event: crossterm::event::Event) -> Self {
         match event {
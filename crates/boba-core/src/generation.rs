@@ -0,0 +1,54 @@
+//! Generation counters for dropping the results of stale commands.
+//!
+//! A model that starts async work for the current context (the selected
+//! item, the current search query, the open directory) keeps a
+//! [`Generation`] and bumps it whenever that context changes. Commands
+//! started with [`Command::future_for_generation`](crate::Command::future_for_generation)
+//! remember the generation they were started in and only deliver their
+//! message if it is still current when the future resolves.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A shared counter identifying the model's current context.
+///
+/// Clones share the same counter, so a command holding a clone sees every
+/// later [`bump`](Self::bump).
+///
+/// # Example
+///
+/// ```
+/// use boba_core::Generation;
+///
+/// let generation = Generation::new();
+/// let started = generation.current();
+/// generation.bump();
+/// assert!(!generation.is_current(started));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Generation {
+    value: Arc<AtomicU64>,
+}
+
+impl Generation {
+    /// Create a counter at generation zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current generation.
+    pub fn current(&self) -> u64 {
+        self.value.load(Ordering::SeqCst)
+    }
+
+    /// Advance to a new generation, making every pending
+    /// generation-tied command stale. Returns the new generation.
+    pub fn bump(&self) -> u64 {
+        self.value.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Whether `generation` is still the current one.
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.current() == generation
+    }
+}
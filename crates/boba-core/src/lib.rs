@@ -66,34 +66,45 @@
 //!
 //! [Elm Architecture]: https://guide.elm-lang.org/architecture/
 
+pub mod cancellation;
+pub mod capture;
 pub mod command;
 pub mod component;
 pub mod event;
+pub mod generation;
 pub mod input_history;
 pub mod input_layer;
 pub mod key_sequence;
 pub mod model;
 pub mod post_render;
 pub mod quit;
+pub mod render_cache;
 pub mod runtime;
 pub mod subscription;
 pub mod subscriptions;
 pub mod testing;
 
-pub use command::{Command, CursorStyle, ExecCommand, MouseMode, TerminalCommand};
+pub use cancellation::CancellationToken;
+pub use capture::FrameCapture;
+pub use command::{
+    Backoff, Command, CommandError, CursorStyle, ExecCommand, MouseMode, TerminalCommand,
+};
 pub use component::Component;
-pub use event::TerminalEvent;
+pub use event::{TerminalEvent, TerminalMessage};
+pub use generation::Generation;
 pub use input_history::InputHistory;
 pub use input_layer::{InputLayer, LayeredModel};
 pub use key_sequence::KeySequenceTracker;
-pub use model::Model;
+pub use model::{InitError, Model};
 pub use post_render::PostRender;
-pub use quit::QuitConfirmation;
+pub use quit::{QuitConfirmation, QuitDecision};
+pub use render_cache::{Cached, RenderCache};
 pub use runtime::{
-    log_to_file, OutputTarget, Program, ProgramError, ProgramHandle, ProgramOptions,
+    log_to_file, FrameCallback, FrameMetrics, MessageQueue, OutputTarget, Overflow, Program,
+    ProgramError, ProgramHandle, ProgramOptions, ProgramTerminal, TerminalOutput,
 };
 pub use subscription::{subscribe, Subscription, SubscriptionId, SubscriptionSource};
-pub use subscriptions::{terminal_events, After, Every};
+pub use subscriptions::{reader_lines, stdin_lines, terminal_events, After, Every};
 
 /// Run a boba application with default options.
 pub async fn run<M: Model>(flags: M::Flags) -> Result<M, ProgramError> {
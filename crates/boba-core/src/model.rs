@@ -1,7 +1,11 @@
-use crate::command::Command;
+use crate::command::{Command, CommandError};
+use crate::quit::QuitDecision;
 use crate::subscription::Subscription;
 use ratatui::Frame;
 
+/// The error a [`Model::try_init`] can fail with.
+pub type InitError = Box<dyn std::error::Error + Send + Sync>;
+
 /// The top-level application trait, following the [Elm Architecture].
 ///
 /// Every boba application implements `Model`. The runtime drives a continuous
@@ -83,8 +87,37 @@ pub trait Model: Sized + Send + 'static {
     /// initial model value and a [`Command`] for any work that should begin
     /// immediately (e.g. loading data from disk).  Use [`Command::none()`] if
     /// no startup side effects are needed.
+    ///
+    /// A model whose setup can fail also overrides
+    /// [`try_init`](Model::try_init), which is what
+    /// [`Program`](crate::Program) calls; `init` can then be written in
+    /// terms of it.
     fn init(flags: Self::Flags) -> (Self, Command<Self::Message>);
 
+    /// Fallible version of [`init`](Model::init), for setup that can fail
+    /// (e.g. reading a required file).
+    ///
+    /// The runtime calls this before touching the terminal, so an error is
+    /// returned from [`Program::new`](crate::Program::new) as
+    /// [`ProgramError::Init`](crate::ProgramError::Init) with the terminal
+    /// untouched. The default wraps `init` and never fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// fn init(path: PathBuf) -> (Self, Command<Msg>) {
+    ///     Self::try_init(path).expect("failed to read the config")
+    /// }
+    ///
+    /// fn try_init(path: PathBuf) -> Result<(Self, Command<Msg>), InitError> {
+    ///     let config = std::fs::read_to_string(&path)?;
+    ///     Ok((Editor::new(config), Command::none()))
+    /// }
+    /// ```
+    fn try_init(flags: Self::Flags) -> Result<(Self, Command<Self::Message>), InitError> {
+        Ok(Self::init(flags))
+    }
+
     /// Process a message, mutate state, and return a command for side effects.
     ///
     /// This is the heart of the application logic.  Pattern-match on the
@@ -100,6 +133,11 @@ pub trait Model: Sized + Send + 'static {
     /// This method should be a pure function of `&self` -- it reads the model
     /// state and draws widgets into the frame.  The runtime calls `view` after
     /// every update and on the initial render.
+    ///
+    /// To place the terminal's own cursor (for example at a text input's
+    /// caret, so IMEs and screen readers can follow it), call
+    /// [`Frame::set_cursor_position`]. The cursor is shown at that position
+    /// for the frame and hidden again on frames that don't request it.
     fn view(&self, frame: &mut Frame);
 
     /// Declare active subscriptions.  Called after every update.
@@ -113,4 +151,58 @@ pub trait Model: Sized + Send + 'static {
     fn subscriptions(&self) -> Vec<Subscription<Self::Message>> {
         vec![]
     }
+
+    /// Called right after a frame has been drawn to the terminal.
+    ///
+    /// Frame-synchronised animations can advance here instead of on a
+    /// separate timer. The hook runs once per drawn frame and is not called
+    /// on frame ticks where nothing needed redrawing. The returned command
+    /// is executed like any other; sending a message from it schedules the
+    /// next frame.
+    ///
+    /// The default implementation does nothing.
+    fn after_render(&mut self) -> Command<Self::Message> {
+        Command::none()
+    }
+
+    /// Called once, right after the first frame has been drawn.
+    ///
+    /// Unlike [`init`](Model::init), which runs before the terminal is set
+    /// up, this runs when the first frame is already on screen, so it is the
+    /// place to start animations or anything that depends on the real
+    /// terminal size. It runs after the first frame's
+    /// [`after_render`](Model::after_render).
+    ///
+    /// The default implementation does nothing.
+    fn ready(&mut self) -> Command<Self::Message> {
+        Command::none()
+    }
+
+    /// Called when a command's task fails instead of producing its message,
+    /// for example because a [`Command::perform`] future panicked.
+    ///
+    /// Without this the message would silently never arrive; use it to
+    /// leave a loading state and show that the operation failed. The view
+    /// is redrawn afterwards.
+    ///
+    /// The default implementation does nothing.
+    fn on_command_error(&mut self, _error: CommandError) -> Command<Self::Message> {
+        Command::none()
+    }
+
+    /// Called when the program is about to exit because of a
+    /// [`Command::quit`] or a Ctrl+C signal.
+    ///
+    /// Return [`QuitDecision::Cancel`] to keep running -- for example when
+    /// there are unsaved changes and the model wants to show a confirmation
+    /// first. The view is redrawn afterwards, so state changed here (such as
+    /// opening a modal) appears immediately. Quitting for real later is just
+    /// another `Command::quit()` that this hook lets through.
+    ///
+    /// [`ProgramHandle::kill`](crate::ProgramHandle::kill) does not consult
+    /// this hook. The default implementation always returns
+    /// [`QuitDecision::Quit`].
+    fn on_quit_requested(&mut self) -> QuitDecision {
+        QuitDecision::Quit
+    }
 }
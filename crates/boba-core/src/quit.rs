@@ -2,6 +2,17 @@
 
 use std::time::{Duration, Instant};
 
+/// The model's answer to a quit request, returned from
+/// [`Model::on_quit_requested`](crate::Model::on_quit_requested).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuitDecision {
+    /// Let the program exit.
+    #[default]
+    Quit,
+    /// Keep running, e.g. to ask the user to confirm discarding changes.
+    Cancel,
+}
+
 /// Tracks double-press quit confirmation (e.g., "press Ctrl+C again to quit").
 ///
 /// In raw terminal mode, Ctrl+C arrives as a key event rather than SIGINT.
@@ -0,0 +1,262 @@
+//! Opt-in caching of rendered regions.
+//!
+//! ratatui only writes changed cells to the terminal, but the model's
+//! `view` still rebuilds the whole frame every time. For a large part of
+//! the screen that rarely changes, a [`RenderCache`] keeps the cells it drew
+//! last time and copies them back instead of calling the drawing code
+//! again, until it is invalidated. [`Cached`] wraps a [`Component`] in a
+//! cache that is invalidated by every message the component handles.
+
+use std::cell::{Cell, RefCell};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use crate::command::Command;
+use crate::component::Component;
+use crate::subscription::Subscription;
+
+/// The cells of one screen region, redrawn only when marked dirty.
+///
+/// Keep one cache per region next to the state it renders, call
+/// [`invalidate`](Self::invalidate) when that state changes, and render
+/// through [`render`](Self::render). The region is also redrawn when its
+/// area changes.
+///
+/// A cache hit restores cells only; a cursor position set by the drawing
+/// code is not replayed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn view(&self, frame: &mut Frame) {
+///     let [map, status] = Layout::vertical([Min(0), Length(1)]).areas(frame.area());
+///     // Only redrawn after `self.map_cache.invalidate()`.
+///     self.map_cache.render(frame, map, |frame, area| self.map.view(frame, area));
+///     self.status.view(frame, status);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RenderCache {
+    cells: RefCell<Option<Buffer>>,
+    dirty: Cell<bool>,
+}
+
+impl RenderCache {
+    /// Create an empty cache. The first render always draws.
+    pub fn new() -> Self {
+        Self {
+            cells: RefCell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Redraw the region on the next render.
+    pub fn invalidate(&self) {
+        self.dirty.set(true);
+    }
+
+    /// Whether the next render will call the drawing code.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// Render `area` with `draw` if the cache is dirty or was filled for a
+    /// different area, and remember the result; otherwise copy the
+    /// remembered cells into the frame.
+    pub fn render(&self, frame: &mut Frame, area: Rect, draw: impl FnOnce(&mut Frame, Rect)) {
+        let area = area.intersection(frame.area());
+        let mut cells = self.cells.borrow_mut();
+        if let Some(cached) = cells.as_ref() {
+            if !self.dirty.get() && cached.area == area {
+                frame.buffer_mut().merge(cached);
+                return;
+            }
+        }
+
+        draw(frame, area);
+        let buf = frame.buffer_mut();
+        let mut cached = Buffer::empty(area);
+        for position in area.positions() {
+            cached[position] = buf[position].clone();
+        }
+        *cells = Some(cached);
+        self.dirty.set(false);
+    }
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`Component`] whose rendering is cached until it handles a message.
+///
+/// Every call to [`update`](Component::update) invalidates the cache, so
+/// the wrapped component redraws only after something could have changed.
+/// Call [`invalidate`](Self::invalidate) for changes made through
+/// [`inner_mut`](Self::inner_mut) or outside the component.
+pub struct Cached<C> {
+    inner: C,
+    cache: RenderCache,
+}
+
+impl<C: Component> Cached<C> {
+    /// Wrap `inner` in a render cache.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            cache: RenderCache::new(),
+        }
+    }
+
+    /// The wrapped component.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The wrapped component, for changes that should be followed by
+    /// [`invalidate`](Self::invalidate).
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Redraw on the next render.
+    pub fn invalidate(&self) {
+        self.cache.invalidate();
+    }
+}
+
+impl<C: Component> Component for Cached<C> {
+    type Message = C::Message;
+
+    fn update(&mut self, msg: C::Message) -> Command<C::Message> {
+        self.cache.invalidate();
+        self.inner.update(msg)
+    }
+
+    fn view(&self, frame: &mut Frame, area: Rect) {
+        self.cache
+            .render(frame, area, |frame, area| self.inner.view(frame, area));
+    }
+
+    fn subscriptions(&self) -> Vec<Subscription<C::Message>> {
+        self.inner.subscriptions()
+    }
+
+    fn size_hint(&self, available: Rect) -> (Constraint, Constraint) {
+        self.inner.size_hint(available)
+    }
+
+    fn render_lines(&self, width: u16) -> Option<Vec<Line<'static>>> {
+        self.inner.render_lines(width)
+    }
+
+    fn focused(&self) -> bool {
+        self.inner.focused()
+    }
+
+    fn wants_escape(&self) -> bool {
+        self.inner.wants_escape()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Paragraph;
+    use ratatui::Terminal;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn row(terminal: &Terminal<TestBackend>, y: u16) -> String {
+        let buf = terminal.backend().buffer();
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    #[test]
+    fn clean_region_is_not_redrawn() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 2)).unwrap();
+        let cache = RenderCache::new();
+        let renders = AtomicUsize::new(0);
+        let mut frame_no = 0;
+        let mut draw = |terminal: &mut Terminal<TestBackend>, area: Rect| {
+            frame_no += 1;
+            let label = format!("frame {frame_no}");
+            terminal
+                .draw(|frame| {
+                    cache.render(frame, area, |frame, area| {
+                        renders.fetch_add(1, Ordering::SeqCst);
+                        frame.render_widget(Paragraph::new(label), area);
+                    });
+                })
+                .unwrap();
+        };
+
+        let top = Rect::new(0, 0, 10, 1);
+        draw(&mut terminal, top);
+        draw(&mut terminal, top);
+        draw(&mut terminal, top);
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+        // The cached cells are still on screen.
+        assert_eq!(row(&terminal, 0), "frame 1   ");
+
+        cache.invalidate();
+        draw(&mut terminal, top);
+        assert_eq!(renders.load(Ordering::SeqCst), 2);
+        assert_eq!(row(&terminal, 0), "frame 4   ");
+
+        // A different area redraws even when clean.
+        draw(&mut terminal, Rect::new(0, 1, 10, 1));
+        assert_eq!(renders.load(Ordering::SeqCst), 3);
+        assert_eq!(row(&terminal, 1), "frame 5   ");
+    }
+
+    struct Counter {
+        value: u32,
+        views: Arc<AtomicUsize>,
+    }
+
+    impl Component for Counter {
+        type Message = u32;
+
+        fn update(&mut self, msg: u32) -> Command<u32> {
+            self.value = msg;
+            Command::none()
+        }
+
+        fn view(&self, frame: &mut Frame, area: Rect) {
+            self.views.fetch_add(1, Ordering::SeqCst);
+            frame.render_widget(Paragraph::new(self.value.to_string()), area);
+        }
+    }
+
+    #[test]
+    fn cached_component_redraws_after_update() {
+        let views = Arc::new(AtomicUsize::new(0));
+        let mut counter = Cached::new(Counter {
+            value: 1,
+            views: views.clone(),
+        });
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let mut draw = |counter: &Cached<Counter>| {
+            terminal
+                .draw(|frame| counter.view(frame, frame.area()))
+                .unwrap();
+        };
+
+        draw(&counter);
+        draw(&counter);
+        assert_eq!(views.load(Ordering::SeqCst), 1);
+
+        counter.update(2);
+        draw(&counter);
+        draw(&counter);
+        assert_eq!(views.load(Ordering::SeqCst), 2);
+        assert_eq!(counter.inner().value, 2);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::command::{Action, Command, CommandInner, MouseMode, TerminalCommand};
+use crate::cancellation::{self, CancellationToken};
+use crate::command::{Action, Command, CommandError, CommandInner, MouseMode, TerminalCommand};
+use crate::event::TerminalEvent;
 use crate::model::Model;
+use crate::quit::QuitDecision;
 use crate::subscription::SubscriptionManager;
 use crossterm::{
     cursor::{self, SetCursorStyle as CrosstermSetCursorStyle},
@@ -12,12 +15,18 @@ use crossterm::{
         disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
     },
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::{self, stderr, stdout, Stderr, Stdout, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    Terminal, TerminalOptions, Viewport,
+};
+use std::collections::HashMap;
+use std::io::{self, stderr, stdout, BufWriter, Stderr, Stdout, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify};
 use tokio::time::MissedTickBehavior;
 
 /// Output target for the terminal UI.
@@ -38,6 +47,9 @@ pub enum OutputTarget {
 enum Output {
     Stdout(Stdout),
     Stderr(Stderr),
+    /// An in-memory screen for tests.
+    #[cfg(test)]
+    Memory(Arc<std::sync::Mutex<Vec<u8>>>),
 }
 
 impl Write for Output {
@@ -45,6 +57,11 @@ impl Write for Output {
         match self {
             Output::Stdout(w) => w.write(buf),
             Output::Stderr(w) => w.write(buf),
+            #[cfg(test)]
+            Output::Memory(screen) => {
+                screen.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
         }
     }
 
@@ -52,6 +69,8 @@ impl Write for Output {
         match self {
             Output::Stdout(w) => w.flush(),
             Output::Stderr(w) => w.flush(),
+            #[cfg(test)]
+            Output::Memory(_) => Ok(()),
         }
     }
 }
@@ -65,12 +84,61 @@ impl Output {
     }
 }
 
+/// Capacity of the buffer in front of the terminal. Large enough that a
+/// typical full-screen frame goes out in a single write when ratatui
+/// flushes at the end of each draw.
+const OUTPUT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The writer a [`ProgramTerminal`] draws through: buffered stdout or
+/// stderr, per [`ProgramOptions::output`].
+///
+/// Each frame is flushed once it is drawn, and [`TerminalCommand::Println`],
+/// [`TerminalCommand::Printf`] and [`TerminalCommand::PrintAbove`] write
+/// through the same buffer and flush, so they stay in order with frames.
+/// Anything that bypasses the buffer, like `println!`, should be preceded by
+/// a [`TerminalCommand::Flush`].
+pub struct TerminalOutput(BufWriter<Output>);
+
+impl Write for TerminalOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// The terminal a [`Program`] renders to, as handed out by
+/// [`Program::with_terminal`].
+pub type ProgramTerminal = Terminal<CrosstermBackend<TerminalOutput>>;
+
+/// Buffer `writer` so a frame's many small escape-sequence writes reach the
+/// underlying stream in as few syscalls as possible.
+fn buffered<W: Write>(writer: W) -> BufWriter<W> {
+    BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, writer)
+}
+
+/// Write `text` after anything already buffered in `writer`, then flush.
+fn write_output<W: Write>(writer: &mut W, text: &str) -> io::Result<()> {
+    writer.write_all(text.as_bytes())?;
+    writer.flush()
+}
+
 /// Errors that can occur while initializing or running a [`Program`].
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ProgramError {
     /// An I/O error from terminal setup, rendering, or teardown.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    /// The output stream is not a terminal (e.g. it is piped or redirected
+    /// in CI) and [`ProgramOptions::require_tty`] is set.
+    #[error("{0:?} is not a terminal")]
+    NotATerminal(OutputTarget),
+    /// [`Model::try_init`] failed. The terminal was not set up.
+    #[error("initialization failed: {0}")]
+    Init(#[source] crate::model::InitError),
 }
 
 /// Configuration options for a [`Program`].
@@ -85,7 +153,7 @@ pub enum ProgramError {
 ///
 /// let opts = ProgramOptions {
 ///     fps: 30,
-///     mouse_mode: Some(MouseMode::Normal),
+///     mouse_mode: Some(MouseMode::CellMotion),
 ///     title: Some("My App".into()),
 ///     output: OutputTarget::Stderr,
 ///     ..ProgramOptions::default()
@@ -97,6 +165,10 @@ pub struct ProgramOptions {
     /// Start in alternate screen (default: true).
     pub alt_screen: bool,
     /// Enable mouse capture mode.
+    ///
+    /// Captured events reach the model as
+    /// [`TerminalEvent::Mouse`](crate::TerminalEvent::Mouse) through the
+    /// [`terminal_events`](crate::terminal_events) subscription.
     pub mouse_mode: Option<MouseMode>,
     /// Enable bracketed paste (default: true).
     pub bracketed_paste: bool,
@@ -104,7 +176,14 @@ pub struct ProgramOptions {
     pub focus_reporting: bool,
     /// Set terminal title.
     pub title: Option<String>,
-    /// Whether to catch panics and restore terminal (default: true).
+    /// Whether to install a panic hook that restores the terminal before
+    /// the panic message is printed (default: true).
+    ///
+    /// The hook is installed once per process and chains to whatever hook
+    /// was set before it. It only touches the terminal while a program is
+    /// running. Set this to `false` when a host application manages panic
+    /// hooks itself: the global hook is then left alone, and the terminal
+    /// is still restored when a panic unwinds out of the program.
     pub catch_panics: bool,
     /// Whether to handle signals gracefully (default: true).
     pub handle_signals: bool,
@@ -112,6 +191,202 @@ pub struct ProgramOptions {
     pub log_file: Option<std::path::PathBuf>,
     /// Output target: stdout (default) or stderr.
     pub output: OutputTarget,
+    /// Refuse to start unless `output` is a terminal (default: true).
+    ///
+    /// Construction then fails with [`ProgramError::NotATerminal`] instead
+    /// of entering raw mode on a pipe. Check for that error to fall back to
+    /// plain, non-interactive output, or clear this to draw anyway.
+    pub require_tty: bool,
+    /// Keep the last rendered frame visible after exit (default: false).
+    ///
+    /// Only meaningful when `alt_screen` is true: the final frame is copied
+    /// to the main screen after leaving the alternate buffer, so it stays in
+    /// the shell's scrollback instead of disappearing.
+    pub leave_final_frame: bool,
+    /// Redraw whenever the terminal gains or loses focus (default: true).
+    ///
+    /// Focus changes seen by [`terminal_events`](crate::terminal_events)
+    /// schedule a redraw even if the model ignores the event or its update
+    /// returns [`Command::none()`]. Requires `focus_reporting`.
+    pub redraw_on_focus_change: bool,
+    /// Leave the terminal cursor visible during startup (default: false).
+    ///
+    /// Once drawing starts, the cursor follows each frame: it is shown where
+    /// [`Model::view`] places it with
+    /// [`Frame::set_cursor_position`](ratatui::Frame::set_cursor_position)
+    /// and hidden otherwise. The cursor is always made visible again when
+    /// the program exits.
+    pub show_cursor: bool,
+    /// Called after every frame tick with timing information (default: none).
+    ///
+    /// Useful for profiling slow views. See [`FrameMetrics`] and
+    /// [`with_on_frame`](Self::with_on_frame).
+    pub on_frame: Option<FrameCallback>,
+    /// How many messages may wait to be processed (default: unbounded).
+    ///
+    /// See [`MessageQueue`] for which producers are held back when the
+    /// queue is full.
+    pub message_queue: MessageQueue,
+    /// Draw only into this area of a terminal that the host application
+    /// already manages (default: `None`, the program owns the screen).
+    ///
+    /// For embedding a program as one panel of a larger crossterm UI. The
+    /// area is cleared at startup and `frame.area()` in [`Model::view`] is
+    /// this area; cells outside it are never written. The program leaves
+    /// the terminal's state to the host: raw mode, the alternate screen,
+    /// mouse capture, bracketed paste, focus reporting, the title and the
+    /// panic hook are neither set up nor restored, so `alt_screen`,
+    /// `mouse_mode`, `bracketed_paste`, `focus_reporting`, `title`,
+    /// `catch_panics` and `leave_final_frame` are ignored. So are terminal
+    /// commands that would touch the rest of the screen or the terminal's
+    /// modes, such as printing, scrolling and suspending;
+    /// [`Command::clear_screen`] clears only the area. The area does not
+    /// follow terminal resizes.
+    pub region: Option<Rect>,
+}
+
+/// Bound on the program's message queue; see
+/// [`ProgramOptions::message_queue`].
+///
+/// With [`Bounded`](Self::Bounded), messages from
+/// [`ProgramHandle::send`] and from command futures, streams and
+/// [`Command::subscription_task`] tasks are only queued while fewer than
+/// `capacity` messages are waiting. Terminal events, subscriptions and
+/// [`Command::message`] are always queued but count toward the capacity,
+/// so a full queue holds back the other producers first. The bound is
+/// approximate: producers admitted at the same moment can overshoot it by
+/// one message each. Under [`Overflow::Block`], the synchronous
+/// [`ProgramHandle::send`] cannot wait and queues anyway, and
+/// [`Program::sender`] is never bounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageQueue {
+    /// Queue every message (default).
+    #[default]
+    Unbounded,
+    /// Hold at most `capacity` waiting messages from bounded producers.
+    Bounded {
+        /// Number of waiting messages at which producers are held back.
+        /// A capacity of 0 is treated as 1, so blocked producers can always
+        /// make progress.
+        capacity: usize,
+        /// What a producer does when the queue is full.
+        overflow: Overflow,
+    },
+}
+
+/// What a producer does when a [`MessageQueue::Bounded`] queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Discard the message (default). Suits progress updates and other
+    /// messages a later one supersedes.
+    #[default]
+    Drop,
+    /// Wait until the model has caught up. A command's future or stream
+    /// pauses, and [`ProgramHandle::send_async`] waits; the synchronous
+    /// [`ProgramHandle::send`] cannot wait and queues the message anyway.
+    Block,
+}
+
+/// Admission control for a [`MessageQueue::Bounded`] queue, shared by the
+/// inbox and every bounded producer.
+pub(crate) struct QueueGate {
+    capacity: usize,
+    overflow: Overflow,
+    /// Messages waiting, as last seen by the inbox plus those admitted
+    /// since.
+    queued: AtomicUsize,
+    /// Woken whenever the inbox takes a message.
+    room: Notify,
+}
+
+impl QueueGate {
+    fn new(queue: MessageQueue) -> Option<Arc<Self>> {
+        match queue {
+            MessageQueue::Unbounded => None,
+            MessageQueue::Bounded { capacity, overflow } => Some(Arc::new(Self {
+                capacity: capacity.max(1),
+                overflow,
+                queued: AtomicUsize::new(0),
+                room: Notify::new(),
+            })),
+        }
+    }
+
+    /// Reserve a place if the queue has room.
+    fn try_admit(&self) -> bool {
+        self.queued
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < self.capacity).then_some(n + 1)
+            })
+            .is_ok()
+    }
+
+    /// Reserve a place, waiting for room under [`Overflow::Block`].
+    /// Returns `false` if the message should be dropped.
+    async fn admit(&self) -> bool {
+        match self.overflow {
+            Overflow::Drop => self.try_admit(),
+            Overflow::Block => loop {
+                let room = self.room.notified();
+                if self.try_admit() {
+                    return true;
+                }
+                room.await;
+            },
+        }
+    }
+
+    /// Record how many messages are waiting after the inbox took one.
+    fn set_queued(&self, queued: usize) {
+        self.queued.store(queued, Ordering::SeqCst);
+        self.room.notify_waiters();
+    }
+}
+
+/// Reserve a place in the queue behind `gate`, if it is bounded.
+async fn admit(gate: Option<&QueueGate>) -> bool {
+    match gate {
+        Some(gate) => gate.admit().await,
+        None => true,
+    }
+}
+
+/// Callback invoked by the runtime after each frame; see
+/// [`ProgramOptions::on_frame`].
+pub type FrameCallback = Box<dyn Fn(FrameMetrics) + Send + Sync>;
+
+/// Timing information for a single frame, passed to
+/// [`ProgramOptions::on_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameMetrics {
+    /// Time spent drawing the frame, including [`Model::view`] and the
+    /// terminal flush. Zero when no redraw occurred.
+    pub render_time: Duration,
+    /// Messages the program received since the previous frame, including
+    /// ones a [`Program::with_filter`] filter dropped and command errors
+    /// passed to [`Model::on_command_error`].
+    pub messages: usize,
+    /// Whether the frame was actually redrawn. Frames are skipped when
+    /// nothing changed since the last draw.
+    pub redrawn: bool,
+}
+
+impl ProgramOptions {
+    /// Set the [`on_frame`](Self::on_frame) metrics callback.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let opts = ProgramOptions::default().with_on_frame(|m| {
+    ///     if m.render_time > Duration::from_millis(16) {
+    ///         eprintln!("slow frame: {:?}", m.render_time);
+    ///     }
+    /// });
+    /// ```
+    pub fn with_on_frame(mut self, f: impl Fn(FrameMetrics) + Send + Sync + 'static) -> Self {
+        self.on_frame = Some(Box::new(f));
+        self
+    }
 }
 
 impl Default for ProgramOptions {
@@ -127,6 +402,13 @@ impl Default for ProgramOptions {
             handle_signals: true,
             log_file: None,
             output: OutputTarget::default(),
+            require_tty: true,
+            leave_final_frame: false,
+            redraw_on_focus_change: true,
+            show_cursor: false,
+            on_frame: None,
+            message_queue: MessageQueue::Unbounded,
+            region: None,
         }
     }
 }
@@ -145,19 +427,53 @@ impl Default for ProgramOptions {
 #[derive(Clone)]
 pub struct ProgramHandle<Msg: Send + 'static> {
     msg_tx: mpsc::UnboundedSender<Msg>,
+    gate: Option<Arc<QueueGate>>,
     killed: Arc<AtomicBool>,
+    redraw: Arc<Notify>,
 }
 
 impl<Msg: Send + 'static> ProgramHandle<Msg> {
     /// Send a message to the running program.
     ///
-    /// The message is enqueued on an unbounded channel and will be processed
-    /// on the next iteration of the event loop.  Returns silently if the
-    /// program has already exited.
+    /// The message is enqueued and will be processed on the next iteration
+    /// of the event loop.  Returns silently if the program has already
+    /// exited. With a [`MessageQueue::Bounded`] queue that is full, the
+    /// message is dropped under [`Overflow::Drop`]; use
+    /// [`send_async`](Self::send_async) to wait for room instead.
+    ///
+    /// Messages sent through handles keep their order among themselves, but
+    /// are queued separately from terminal events, subscriptions and
+    /// commands. The loop alternates between the two queues, so a flood of
+    /// external messages delays a key press by at most one message.
     pub fn send(&self, msg: Msg) {
+        if let Some(gate) = &self.gate {
+            if !gate.try_admit() && gate.overflow == Overflow::Drop {
+                return;
+            }
+        }
         let _ = self.msg_tx.send(msg);
     }
 
+    /// Send a message to the running program, waiting for room in a
+    /// [`MessageQueue::Bounded`] queue under [`Overflow::Block`].
+    ///
+    /// Otherwise the same as [`send`](Self::send).
+    pub async fn send_async(&self, msg: Msg) {
+        if admit(self.gate.as_deref()).await {
+            let _ = self.msg_tx.send(msg);
+        }
+    }
+
+    /// Draw the next frame without sending a message.
+    ///
+    /// Use this after changing state the view reads from outside the
+    /// program (shared with an `Arc<Mutex<_>>`, say), instead of adding a
+    /// message variant just to trigger a redraw. `update` is not called.
+    /// Requests made before the frame is drawn are coalesced.
+    pub fn request_redraw(&self) {
+        self.redraw.notify_one();
+    }
+
     /// Force-kill the program immediately.
     ///
     /// Sets an atomic flag that the event loop checks on every iteration.
@@ -189,19 +505,40 @@ impl<Msg: Send + 'static> ProgramHandle<Msg> {
 /// ```
 pub struct Program<M: Model> {
     model: M,
-    terminal: Terminal<CrosstermBackend<Output>>,
+    terminal: ProgramTerminal,
     msg_tx: mpsc::UnboundedSender<M::Message>,
-    msg_rx: mpsc::UnboundedReceiver<M::Message>,
+    /// Sender behind [`Program::sender`] and [`ProgramHandle`].
+    external_tx: mpsc::UnboundedSender<M::Message>,
+    inbox: Inbox<M::Message>,
+    /// Set when [`ProgramOptions::message_queue`] is bounded.
+    gate: Option<Arc<QueueGate>>,
+    /// Failures of spawned command tasks, for [`Model::on_command_error`].
+    error_tx: mpsc::UnboundedSender<CommandError>,
+    error_rx: mpsc::UnboundedReceiver<CommandError>,
     subscription_manager: SubscriptionManager<M::Message>,
     options: ProgramOptions,
     needs_redraw: bool,
     should_quit: bool,
     killed: Arc<AtomicBool>,
+    /// Woken by [`ProgramHandle::request_redraw`].
+    redraw: Arc<Notify>,
     #[allow(clippy::type_complexity)]
     filter: Option<Box<dyn Fn(M::Message) -> Option<M::Message> + Send>>,
     terminal_released: bool,
     log_file: Option<std::fs::File>,
     post_render: Option<Box<dyn crate::post_render::PostRender>>,
+    /// Copy of the last rendered frame, kept only when `leave_final_frame` is set.
+    final_frame: Option<Buffer>,
+    /// Whether the alternate screen is currently active.
+    in_alt_screen: bool,
+    /// Messages processed since the last frame, for [`FrameMetrics`].
+    messages_since_frame: usize,
+    /// Cancelled on exit; handed to [`Command::future_with`] futures.
+    shutdown: CancellationToken,
+    /// Tasks started with [`Command::subscription_task`].
+    tasks: Tasks,
+    /// Restores the terminal if a panic unwinds through the program.
+    _unwind_guard: UnwindGuard,
 }
 
 impl<M: Model> Program<M> {
@@ -214,9 +551,26 @@ impl<M: Model> Program<M> {
 
     /// Create a new program with custom options.
     ///
-    /// Returns an error if terminal initialization fails.
+    /// Returns an error if terminal initialization fails,
+    /// [`ProgramError::NotATerminal`] if the output isn't a terminal and
+    /// [`ProgramOptions::require_tty`] is set, or [`ProgramError::Init`] if
+    /// [`Model::try_init`] fails.
     pub fn with_options(flags: M::Flags, options: ProgramOptions) -> Result<Self, ProgramError> {
+        Self::build(flags, options, is_terminal, init_terminal)
+    }
+
+    /// Create a program whose terminal is opened by `open_terminal` once
+    /// the model is initialized, after checking the output with `is_tty`.
+    fn build(
+        flags: M::Flags,
+        options: ProgramOptions,
+        is_tty: impl Fn(OutputTarget) -> bool,
+        open_terminal: impl FnOnce(&ProgramOptions) -> Result<ProgramTerminal, ProgramError>,
+    ) -> Result<Self, ProgramError> {
+        ensure_terminal(&options, is_tty)?;
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
+        let (external_tx, external_rx) = mpsc::unbounded_channel();
+        let (error_tx, error_rx) = mpsc::unbounded_channel();
 
         // Open log file if specified
         let log_file = if let Some(ref path) = options.log_file {
@@ -230,26 +584,39 @@ impl<M: Model> Program<M> {
             None
         };
 
-        let (model, init_cmd) = M::init(flags);
+        let (model, init_cmd) = M::try_init(flags).map_err(ProgramError::Init)?;
 
-        let terminal = init_terminal(&options)?;
+        let terminal = open_terminal(&options)?;
+        let gate = QueueGate::new(options.message_queue);
         let subscription_manager = SubscriptionManager::new(msg_tx.clone());
         let killed = Arc::new(AtomicBool::new(false));
+        let in_alt_screen = options.alt_screen && options.region.is_none();
 
         let mut program = Self {
             model,
             terminal,
             msg_tx,
-            msg_rx,
+            external_tx,
+            inbox: Inbox::new(msg_rx, external_rx).with_gate(gate.clone()),
+            gate,
+            error_tx,
+            error_rx,
             subscription_manager,
             options,
             needs_redraw: true,
             should_quit: false,
             killed,
+            redraw: Arc::new(Notify::new()),
             filter: None,
             terminal_released: false,
             log_file,
             post_render: None,
+            final_frame: None,
+            in_alt_screen,
+            messages_since_frame: 0,
+            shutdown: CancellationToken::new(),
+            tasks: Tasks::default(),
+            _unwind_guard: UnwindGuard,
         };
 
         program.debug_log("program initialized");
@@ -274,34 +641,74 @@ impl<M: Model> Program<M> {
         self
     }
 
+    /// Set a preprocessor for raw terminal events. Every keyboard, mouse,
+    /// resize, focus and paste event passes through it before the
+    /// [`terminal_events`](crate::subscriptions::terminal_events) mapping
+    /// turns it into a message, so no widget ever sees an event it consumes.
+    /// Return `Some(event)` to pass (possibly transformed), `None` to drop.
+    ///
+    /// Unlike [`with_filter`](Self::with_filter), this sees events before
+    /// the model maps them, which suits a global keymap or leader key. To
+    /// act on a consumed chord, send a message through a [`ProgramHandle`].
+    ///
+    /// ```rust,ignore
+    /// let handle = program.handle();
+    /// let program = program.with_event_filter(move |event| match event {
+    ///     TerminalEvent::Key(key) if key.code == KeyCode::F(1) => {
+    ///         handle.send(Msg::ToggleHelp);
+    ///         None
+    ///     }
+    ///     event => Some(event),
+    /// });
+    /// ```
+    pub fn with_event_filter(
+        mut self,
+        filter: impl FnMut(TerminalEvent) -> Option<TerminalEvent> + Send + 'static,
+    ) -> Self {
+        self.subscription_manager.set_event_filter(Box::new(filter));
+        self
+    }
+
     /// Set a post-render hook that runs after view() but before the buffer is flushed.
     pub fn with_post_render(mut self, hook: impl crate::post_render::PostRender + 'static) -> Self {
         self.post_render = Some(Box::new(hook));
         self
     }
 
-    /// Get a sender for external message injection.
+    /// Get a sender for external message injection. See
+    /// [`ProgramHandle::send`] for how these messages are ordered. Unlike
+    /// the handle, the sender ignores a bounded
+    /// [`message_queue`](ProgramOptions::message_queue).
     pub fn sender(&self) -> mpsc::UnboundedSender<M::Message> {
-        self.msg_tx.clone()
+        self.external_tx.clone()
     }
 
     /// Get a handle for external control (send messages, force-kill).
     pub fn handle(&self) -> ProgramHandle<M::Message> {
         ProgramHandle {
-            msg_tx: self.msg_tx.clone(),
+            msg_tx: self.external_tx.clone(),
+            gate: self.gate.clone(),
             killed: self.killed.clone(),
+            redraw: self.redraw.clone(),
         }
     }
 
     /// Run the program. Blocks until quit.
     pub async fn run(mut self) -> Result<M, ProgramError> {
-        self.event_loop().await?;
+        let result = self.event_loop().await;
+        self.shutdown.cancel();
+        result?;
 
         // Cleanup
         self.debug_log("shutting down");
         self.subscription_manager.shutdown();
         if !self.terminal_released {
+            Backend::flush(self.terminal.backend_mut())?;
             restore_terminal(&self.options)?;
+            if let Some(buf) = self.final_frame.take() {
+                let mut writer = Output::new(self.options.output);
+                write_final_frame(&mut writer, &buf)?;
+            }
         }
 
         Ok(self.model)
@@ -310,6 +717,7 @@ impl<M: Model> Program<M> {
     /// Temporarily release terminal control without quitting.
     pub fn release_terminal(&mut self) -> Result<(), ProgramError> {
         if !self.terminal_released {
+            Backend::flush(self.terminal.backend_mut())?;
             restore_terminal(&self.options)?;
             self.terminal_released = true;
         }
@@ -321,18 +729,84 @@ impl<M: Model> Program<M> {
         if self.terminal_released {
             self.terminal = init_terminal(&self.options)?;
             self.terminal_released = false;
+            self.in_alt_screen = self.options.alt_screen && self.options.region.is_none();
             self.needs_redraw = true;
         }
         Ok(())
     }
 
+    /// Run `f` with direct access to the terminal, between frames.
+    ///
+    /// This is an escape hatch for output `Model::view` can't express, such
+    /// as image protocol escapes. The draw loop can't run concurrently: `f`
+    /// runs only while the program isn't drawing, with pending output
+    /// flushed before and after. From inside a running program, use
+    /// [`Command::with_terminal`].
+    ///
+    /// Ratatui doesn't know what `f` wrote, so the next frame only repaints
+    /// cells that changed in the model's view. That keeps an image in place
+    /// but leaves stray output too; call `terminal.clear()` in `f` to force
+    /// a full repaint instead. Leaving raw mode or the alternate screen from
+    /// here will confuse the runtime; use the matching commands for that.
+    ///
+    /// Returns an error if the terminal is released (see
+    /// [`release_terminal`](Self::release_terminal)).
+    pub fn with_terminal<R>(
+        &mut self,
+        f: impl FnOnce(&mut ProgramTerminal) -> R,
+    ) -> Result<R, ProgramError> {
+        if self.terminal_released {
+            return Err(io::Error::other("terminal is released").into());
+        }
+        Ok(between_frames(
+            &mut self.terminal,
+            &mut self.needs_redraw,
+            f,
+        )?)
+    }
+
+    /// Pause every subscription except terminal events without tearing
+    /// them down.
+    ///
+    /// Messages produced while paused (timer ticks, for example) are
+    /// dropped. Subscriptions keep their identity, so reconciliation does
+    /// not restart them and they pick up again after
+    /// [`resume_subscriptions`](Self::resume_subscriptions).
+    pub fn pause_subscriptions(&mut self) {
+        self.subscription_manager.pause_all();
+    }
+
+    /// Resume subscriptions paused by
+    /// [`pause_subscriptions`](Self::pause_subscriptions).
+    pub fn resume_subscriptions(&mut self) {
+        self.subscription_manager.resume_all();
+    }
+
     async fn event_loop(&mut self) -> Result<(), ProgramError> {
-        // Initial render
-        self.render()?;
+        // Initial render, then let the model know it is on screen
+        let final_frame = if keeps_final_frame(&self.options) {
+            Some(&mut self.final_frame)
+        } else {
+            None
+        };
+        let start = Instant::now();
+        let cmd = draw_initial(
+            &mut self.terminal,
+            &mut self.model,
+            self.post_render.as_deref(),
+            &mut self.needs_redraw,
+            final_frame,
+        )?;
+        report_frame(
+            self.options.on_frame.as_ref(),
+            start.elapsed(),
+            true,
+            &mut self.messages_since_frame,
+        );
+        self.execute_command(cmd);
 
-        let fps = self.options.fps.clamp(1, 120);
-        let mut frame_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / fps as f64));
-        frame_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut fps = self.options.fps;
+        let mut frame_interval = frame_timer(fps);
 
         let handle_signals = self.options.handle_signals;
 
@@ -347,23 +821,45 @@ impl<M: Model> Program<M> {
 
                 _ = tokio::signal::ctrl_c(), if handle_signals => {
                     self.debug_log("received ctrl+c signal");
-                    self.should_quit = true;
-                    return Ok(());
+                    if confirm_quit(&mut self.model, &mut self.needs_redraw) {
+                        self.should_quit = true;
+                        return Ok(());
+                    }
+                }
+
+                _ = self.redraw.notified() => {
+                    self.needs_redraw = true;
+                }
+
+                // Ahead of messages so a steady stream of them can't hold
+                // frames back; the timer is ready at most `fps` times a
+                // second.
+                _ = frame_interval.tick() => {
+                    check_focus_redraw(
+                        &self.options,
+                        self.subscription_manager.terminal(),
+                        &mut self.needs_redraw,
+                    );
+                    if !self.terminal_released {
+                        self.render()?;
+                    }
                 }
 
-                Some(msg) = self.msg_rx.recv() => {
+                Some(msg) = self.inbox.recv() => {
+                    self.messages_since_frame += 1;
                     self.process_message(msg);
 
                     // Micro-batch: drain additional messages within 100μs, up to 100 messages
                     let deadline = Instant::now() + Duration::from_micros(100);
                     let mut batch_count = 0u32;
                     while Instant::now() < deadline && batch_count < 100 {
-                        match self.msg_rx.try_recv() {
-                            Ok(msg) => {
+                        match self.inbox.try_recv() {
+                            Some(msg) => {
+                                self.messages_since_frame += 1;
                                 self.process_message(msg);
                                 batch_count += 1;
                             }
-                            Err(_) => break,
+                            None => break,
                         }
                     }
 
@@ -372,13 +868,22 @@ impl<M: Model> Program<M> {
                     }
                 }
 
-                _ = frame_interval.tick() => {
-                    if self.needs_redraw && !self.terminal_released {
-                        self.render()?;
-                        self.needs_redraw = false;
-                    }
+                Some(error) = self.error_rx.recv() => {
+                    self.messages_since_frame += 1;
+                    self.debug_log(&format!("command failed: {error}"));
+                    let cmd = self.model.on_command_error(error);
+                    self.execute_command(cmd);
+                    let subs = self.model.subscriptions();
+                    self.subscription_manager.reconcile(subs);
+                    self.needs_redraw = true;
                 }
             }
+
+            // Re-arm the frame timer after a `Command::set_fps`.
+            if self.options.fps != fps {
+                fps = self.options.fps;
+                frame_interval = frame_timer(fps);
+            }
         }
     }
 
@@ -410,26 +915,39 @@ impl<M: Model> Program<M> {
                 let _ = self.msg_tx.send(msg);
             }
             CommandInner::Action(Action::Quit) => {
-                self.should_quit = true;
+                if confirm_quit(&mut self.model, &mut self.needs_redraw) {
+                    self.should_quit = true;
+                }
+            }
+            CommandInner::Action(Action::Redraw) => {
+                self.needs_redraw = true;
             }
             CommandInner::Future(fut) => {
                 let tx = self.msg_tx.clone();
-                tokio::spawn(async move {
+                let gate = self.gate.clone();
+                self.spawn(async move {
                     let msg = fut.await;
-                    let _ = tx.send(msg);
+                    if admit(gate.as_deref()).await {
+                        let _ = tx.send(msg);
+                    }
                 });
             }
             CommandInner::Stream(stream) => {
-                use futures::StreamExt;
                 let tx = self.msg_tx.clone();
-                tokio::spawn(async move {
-                    futures::pin_mut!(stream);
-                    while let Some(msg) = stream.next().await {
-                        if tx.send(msg).is_err() {
-                            break;
-                        }
-                    }
+                let gate = self.gate.clone();
+                self.spawn(async move { forward_stream(stream, &tx, gate.as_deref()).await });
+            }
+            CommandInner::Task { key, stream } => {
+                let tx = self.msg_tx.clone();
+                let gate = self.gate.clone();
+                let token = self.shutdown.child_token();
+                let handle = self.spawn_with(token.clone(), async move {
+                    forward_stream(stream, &tx, gate.as_deref()).await
                 });
+                self.tasks.start(key, handle.abort_handle(), token);
+            }
+            CommandInner::Cancel(key) => {
+                self.tasks.cancel(&key);
             }
             CommandInner::Batch(cmds) => {
                 for cmd in cmds {
@@ -438,15 +956,30 @@ impl<M: Model> Program<M> {
             }
             CommandInner::Sequence(cmds) => {
                 let tx = self.msg_tx.clone();
-                tokio::spawn(async move {
+                let gate = self.gate.clone();
+                let tasks = self.tasks.clone();
+                self.spawn(async move {
                     for cmd in cmds {
-                        execute_command_sequential(cmd, &tx).await;
+                        execute_command_sequential(cmd, &tx, gate.as_ref(), &tasks).await;
                     }
                 });
             }
             CommandInner::Terminal(tcmd) => {
                 self.execute_terminal_command(tcmd);
             }
+            CommandInner::WithTerminal(f) => {
+                if let Err(error) = self.with_terminal(f) {
+                    self.debug_log(&format!("with_terminal skipped: {error}"));
+                }
+            }
+            CommandInner::CaptureFrame(on_captured) => {
+                let size = match self.options.region {
+                    Some(region) => region.as_size(),
+                    None => self.terminal.size().unwrap_or_default(),
+                };
+                let frame = crate::capture::capture(&self.model, self.post_render.as_deref(), size);
+                let _ = self.msg_tx.send(on_captured(frame));
+            }
             CommandInner::Exec {
                 cmd: exec_cmd,
                 on_exit,
@@ -472,14 +1005,67 @@ impl<M: Model> Program<M> {
         }
     }
 
+    /// Spawn a command task under the program's cancellation token,
+    /// reporting a panic to [`Model::on_command_error`].
+    fn spawn(
+        &self,
+        task: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> tokio::task::JoinHandle<()> {
+        self.spawn_with(self.shutdown.clone(), task)
+    }
+
+    /// Like [`spawn`](Self::spawn), with `token` as the task's
+    /// cancellation token.
+    fn spawn_with(
+        &self,
+        token: CancellationToken,
+        task: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> tokio::task::JoinHandle<()> {
+        spawn_command_task(token, self.error_tx.clone(), task)
+    }
+
     fn execute_terminal_command(&mut self, cmd: TerminalCommand) {
-        let mut writer = Output::new(self.options.output);
+        if let Some(region) = self.options.region {
+            match cmd {
+                // The screen and the terminal's modes belong to the host.
+                TerminalCommand::EnterAltScreen
+                | TerminalCommand::ExitAltScreen
+                | TerminalCommand::EnableMouseCapture(_)
+                | TerminalCommand::DisableMouse
+                | TerminalCommand::EnableBracketedPaste
+                | TerminalCommand::DisableBracketedPaste
+                | TerminalCommand::EnableFocusReporting
+                | TerminalCommand::DisableFocusReporting
+                | TerminalCommand::SetTitle(_)
+                | TerminalCommand::ScrollUp(_)
+                | TerminalCommand::ScrollDown(_)
+                | TerminalCommand::Println(_)
+                | TerminalCommand::Printf(_)
+                | TerminalCommand::PrintAbove(_)
+                | TerminalCommand::Suspend => {
+                    self.debug_log(&format!("ignored in a region: {cmd:?}"));
+                    return;
+                }
+                TerminalCommand::ClearScreen => {
+                    clear_region(&mut self.terminal, region).ok();
+                    self.needs_redraw = true;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        // Write through the frame buffer, flushing it first so what is
+        // still in it isn't overtaken.
+        Backend::flush(self.terminal.backend_mut()).ok();
+        let writer = self.terminal.backend_mut();
         match cmd {
             TerminalCommand::EnterAltScreen => {
                 execute!(writer, EnterAlternateScreen).ok();
+                self.in_alt_screen = true;
             }
             TerminalCommand::ExitAltScreen => {
                 execute!(writer, LeaveAlternateScreen).ok();
+                self.in_alt_screen = false;
             }
             TerminalCommand::EnableMouseCapture(_mode) => {
                 execute!(writer, EnableMouseCapture).ok();
@@ -548,14 +1134,28 @@ impl<M: Model> Program<M> {
             TerminalCommand::Println(text) => {
                 // Use \r\n to produce correct output in raw mode (raw mode does
                 // not translate \n to \r\n).
-                execute!(writer, crossterm::style::Print(format!("{text}\r\n"))).ok();
+                write_output(self.terminal.backend_mut(), &format!("{text}\r\n")).ok();
             }
             TerminalCommand::Printf(text) => {
-                execute!(writer, crossterm::style::Print(text)).ok();
+                write_output(self.terminal.backend_mut(), &text).ok();
+            }
+            TerminalCommand::Flush => {
+                write_output(self.terminal.backend_mut(), "").ok();
+            }
+            TerminalCommand::PrintAbove(text) => {
+                write_print_above(self.terminal.backend_mut(), &text, self.in_alt_screen).ok();
+                if self.in_alt_screen {
+                    // The alternate screen may come back blank; force a full repaint.
+                    self.terminal.clear().ok();
+                    self.needs_redraw = true;
+                }
             }
             TerminalCommand::Suspend => {
                 self.suspend();
             }
+            TerminalCommand::PauseSubscriptions => self.pause_subscriptions(),
+            TerminalCommand::ResumeSubscriptions => self.resume_subscriptions(),
+            TerminalCommand::SetFps(fps) => self.options.fps = fps.clamp(1, 120),
         }
     }
 
@@ -567,6 +1167,7 @@ impl<M: Model> Program<M> {
     }
 
     fn suspend(&mut self) {
+        Backend::flush(self.terminal.backend_mut()).ok();
         restore_terminal(&self.options).ok();
 
         #[cfg(unix)]
@@ -589,158 +1190,723 @@ impl<M: Model> Program<M> {
         }
     }
 
+    /// Draw the model if a redraw is pending and run its `after_render` hook.
     fn render(&mut self) -> Result<(), ProgramError> {
-        let model = &self.model;
-        let post_render = &self.post_render;
-        self.terminal.draw(|frame| {
-            model.view(frame);
-            if let Some(hook) = post_render {
-                let area = frame.area();
-                hook.after_view(frame.buffer_mut(), area);
-            }
-        })?;
+        let final_frame = if keeps_final_frame(&self.options) {
+            Some(&mut self.final_frame)
+        } else {
+            None
+        };
+        let start = Instant::now();
+        let cmd = draw_pending(
+            &mut self.terminal,
+            &mut self.model,
+            self.post_render.as_deref(),
+            &mut self.needs_redraw,
+            final_frame,
+        )?;
+        report_frame(
+            self.options.on_frame.as_ref(),
+            start.elapsed(),
+            cmd.is_some(),
+            &mut self.messages_since_frame,
+        );
+        if let Some(cmd) = cmd {
+            self.execute_command(cmd);
+        }
         Ok(())
     }
 }
 
-/// Execute a command sequentially (for `Command::sequence`).
-fn execute_command_sequential<Msg: Send + 'static>(
-    cmd: Command<Msg>,
-    tx: &mpsc::UnboundedSender<Msg>,
-) -> futures::future::BoxFuture<'_, ()> {
-    Box::pin(async move {
-        match cmd.inner {
-            CommandInner::None => {}
-            CommandInner::Action(Action::Message(msg)) => {
-                let _ = tx.send(msg);
-            }
-            CommandInner::Action(Action::Quit) => {
-                // Can't easily signal quit from here; send would need a special channel
-            }
-            CommandInner::Future(fut) => {
-                let msg = fut.await;
-                let _ = tx.send(msg);
-            }
-            CommandInner::Stream(stream) => {
-                use futures::StreamExt;
-                futures::pin_mut!(stream);
-                while let Some(msg) = stream.next().await {
-                    if tx.send(msg).is_err() {
-                        break;
-                    }
-                }
-            }
-            CommandInner::Batch(cmds) => {
-                // In a sequence, batch still runs concurrently within itself
-                let handles: Vec<_> = cmds
-                    .into_iter()
-                    .map(|cmd| {
-                        let tx = tx.clone();
-                        tokio::spawn(async move {
-                            execute_command_sequential(cmd, &tx).await;
-                        })
-                    })
-                    .collect();
-                for handle in handles {
-                    handle.await.ok();
-                }
-            }
-            CommandInner::Sequence(cmds) => {
-                for cmd in cmds {
-                    execute_command_sequential(cmd, tx).await;
-                }
-            }
-            CommandInner::Terminal(_) => {
-                // Terminal commands from a sequential context are not supported
-                // (they need mutable terminal access)
-            }
-            CommandInner::Exec { .. } => {
-                // Exec commands from a sequential context are not supported
-                // (they need mutable terminal access)
-            }
-        }
-    })
+/// The loop's two message queues: `internal` carries terminal events,
+/// subscriptions and commands, `external` carries messages injected through
+/// [`ProgramHandle`]s.
+///
+/// Each queue stays FIFO. When both have messages waiting they take turns,
+/// so neither can starve the other.
+struct Inbox<Msg> {
+    internal: mpsc::UnboundedReceiver<Msg>,
+    external: mpsc::UnboundedReceiver<Msg>,
+    /// Whether the external queue goes first on the next receive.
+    external_first: bool,
+    /// Told the queue length after every receive.
+    gate: Option<Arc<QueueGate>>,
 }
 
-fn init_terminal(
-    options: &ProgramOptions,
-) -> Result<Terminal<CrosstermBackend<Output>>, ProgramError> {
-    // Install panic hook that restores terminal (only once to avoid stacking)
-    if options.catch_panics {
-        use std::sync::Once;
-        static HOOK_INSTALLED: Once = Once::new();
-        let alt_screen = options.alt_screen;
-        let output_target = options.output;
-        HOOK_INSTALLED.call_once(|| {
-            let original_hook = std::panic::take_hook();
-            std::panic::set_hook(Box::new(move |info| {
-                let _ = restore_terminal_minimal(alt_screen, output_target);
-                original_hook(info);
-            }));
-        });
+impl<Msg> Inbox<Msg> {
+    fn new(internal: mpsc::UnboundedReceiver<Msg>, external: mpsc::UnboundedReceiver<Msg>) -> Self {
+        Self {
+            internal,
+            external,
+            external_first: false,
+            gate: None,
+        }
     }
 
-    enable_raw_mode()?;
-    let mut writer = Output::new(options.output);
+    /// Report the queue length to `gate` as messages are taken.
+    fn with_gate(mut self, gate: Option<Arc<QueueGate>>) -> Self {
+        self.gate = gate;
+        self
+    }
 
-    if options.alt_screen {
-        execute!(writer, EnterAlternateScreen)?;
+    /// Tell the gate, if any, how many messages are still waiting.
+    fn release(&self) {
+        if let Some(gate) = &self.gate {
+            gate.set_queued(self.internal.len() + self.external.len());
+        }
     }
-    if options.bracketed_paste {
-        execute!(writer, EnableBracketedPaste)?;
+
+    /// Take the next waiting message without blocking.
+    fn try_recv(&mut self) -> Option<Msg> {
+        let (first, second) = if self.external_first {
+            (&mut self.external, &mut self.internal)
+        } else {
+            (&mut self.internal, &mut self.external)
+        };
+        let (msg, from_first) = match first.try_recv() {
+            Ok(msg) => (msg, true),
+            Err(_) => (second.try_recv().ok()?, false),
+        };
+        // Whichever queue just delivered goes second next time.
+        self.external_first = self.external_first != from_first;
+        self.release();
+        Some(msg)
     }
-    if options.mouse_mode.is_some() {
-        execute!(writer, EnableMouseCapture)?;
+
+    /// Wait for the next message. Returns `None` once both queues are
+    /// closed and empty.
+    async fn recv(&mut self) -> Option<Msg> {
+        if let Some(msg) = self.try_recv() {
+            return Some(msg);
+        }
+        tokio::select! {
+            Some(msg) = self.internal.recv() => {
+                self.external_first = true;
+                self.release();
+                Some(msg)
+            }
+            Some(msg) = self.external.recv() => {
+                self.external_first = false;
+                self.release();
+                Some(msg)
+            }
+            else => None,
+        }
     }
-    if options.focus_reporting {
-        execute!(writer, EnableFocusChange)?;
+}
+
+/// Draw `model` if `needs_redraw` is set, then call
+/// [`Model::after_render`].
+///
+/// Returns the hook's command, or `None` when the draw was skipped. When
+/// `final_frame` is given, a copy of the drawn buffer is stored in it.
+fn draw_pending<M: Model, B: Backend>(
+    terminal: &mut Terminal<B>,
+    model: &mut M,
+    post_render: Option<&dyn crate::post_render::PostRender>,
+    needs_redraw: &mut bool,
+    final_frame: Option<&mut Option<Buffer>>,
+) -> Result<Option<Command<M::Message>>, B::Error> {
+    if !*needs_redraw {
+        return Ok(None);
     }
-    if let Some(ref title) = options.title {
-        execute!(writer, SetTitle(title))?;
+    let completed = terminal.draw(|frame| {
+        model.view(frame);
+        if let Some(hook) = post_render {
+            let area = frame.area();
+            hook.after_view(frame.buffer_mut(), area);
+        }
+    })?;
+    if let Some(slot) = final_frame {
+        *slot = Some(completed.buffer.clone());
     }
-    execute!(writer, cursor::Hide)?;
+    *needs_redraw = false;
+    Ok(Some(model.after_render()))
+}
 
-    let backend = CrosstermBackend::new(writer);
-    let terminal = Terminal::new(backend)?;
-    Ok(terminal)
+/// Run `f` against `terminal` outside of a draw, flushing buffered output
+/// around it and scheduling a redraw afterwards.
+fn between_frames<B: Backend, R>(
+    terminal: &mut Terminal<B>,
+    needs_redraw: &mut bool,
+    f: impl FnOnce(&mut Terminal<B>) -> R,
+) -> Result<R, B::Error> {
+    Backend::flush(terminal.backend_mut())?;
+    let result = f(terminal);
+    Backend::flush(terminal.backend_mut())?;
+    *needs_redraw = true;
+    Ok(result)
 }
 
-fn restore_terminal(options: &ProgramOptions) -> Result<(), ProgramError> {
-    restore_terminal_minimal(options.alt_screen, options.output)?;
-    Ok(())
+/// Frame timer for `fps` frames per second, clamped to `1..=120`. Missed
+/// ticks are skipped rather than bursting to catch up.
+fn frame_timer(fps: u32) -> tokio::time::Interval {
+    let fps = fps.clamp(1, 120);
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / fps as f64));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval
 }
 
-fn restore_terminal_minimal(
-    alt_screen: bool,
-    output_target: OutputTarget,
-) -> Result<(), std::io::Error> {
-    // Use best-effort cleanup: continue even if individual steps fail,
-    // so we restore as much terminal state as possible.
-    let r1 = disable_raw_mode();
-    let mut writer = Output::new(output_target);
-    execute!(writer, DisableBracketedPaste).ok();
-    execute!(writer, DisableMouseCapture).ok();
-    execute!(writer, DisableFocusChange).ok();
-    execute!(writer, cursor::Show).ok();
-    if alt_screen {
-        execute!(writer, LeaveAlternateScreen).ok();
+/// Pass the metrics for a finished frame to `on_frame`, if set, and reset
+/// the message counter.
+fn report_frame(
+    on_frame: Option<&FrameCallback>,
+    elapsed: Duration,
+    redrawn: bool,
+    messages: &mut usize,
+) {
+    if let Some(callback) = on_frame {
+        callback(FrameMetrics {
+            render_time: if redrawn { elapsed } else { Duration::ZERO },
+            messages: *messages,
+            redrawn,
+        });
     }
-    // Propagate the raw mode error if it was the only failure that matters
-    r1
+    *messages = 0;
 }
 
-/// Open a log file for debugging TUI applications.
-///
-/// Returns a file handle that can be used with `writeln!` or passed to
-/// a logging framework. The file is opened in append mode.
-///
-/// This is the equivalent of Bubble Tea's `tea.LogToFile()`.
-///
-/// # Example
-///
-/// ```no_run
-/// use boba_core::runtime::log_to_file;
-/// use std::io::Write;
+/// Draw the first frame unconditionally, then call [`Model::after_render`]
+/// and [`Model::ready`], returning their commands batched in that order.
+fn draw_initial<M: Model, B: Backend>(
+    terminal: &mut Terminal<B>,
+    model: &mut M,
+    post_render: Option<&dyn crate::post_render::PostRender>,
+    needs_redraw: &mut bool,
+    final_frame: Option<&mut Option<Buffer>>,
+) -> Result<Command<M::Message>, B::Error> {
+    *needs_redraw = true;
+    let after_render = draw_pending(terminal, model, post_render, needs_redraw, final_frame)?;
+    Ok(Command::batch(
+        after_render.into_iter().chain([model.ready()]),
+    ))
+}
+
+/// Print `text` line by line to the main screen.
+///
+/// When the alternate screen is active it is left for the duration of the
+/// write and re-entered afterwards, so the lines land in scrollback.
+fn write_print_above<W: Write>(writer: &mut W, text: &str, in_alt_screen: bool) -> io::Result<()> {
+    use crossterm::queue;
+    use crossterm::style::Print;
+
+    if in_alt_screen {
+        queue!(writer, LeaveAlternateScreen)?;
+    }
+    // Raw mode does not translate \n, so emit \r\n explicitly per line.
+    for line in text.lines() {
+        queue!(writer, Print(line), Print("\r\n"))?;
+    }
+    if in_alt_screen {
+        queue!(writer, EnterAlternateScreen)?;
+    }
+    writer.flush()
+}
+
+/// Schedule a redraw if the terminal's focus changed since the last check
+/// and the options ask for it.
+fn check_focus_redraw(
+    options: &ProgramOptions,
+    terminal: &crate::subscriptions::TerminalState,
+    needs_redraw: &mut bool,
+) {
+    if terminal.take_focus_change() && options.redraw_on_focus_change {
+        *needs_redraw = true;
+    }
+}
+
+/// Whether the last frame should be copied to the main screen on exit.
+fn keeps_final_frame(options: &ProgramOptions) -> bool {
+    options.alt_screen && options.leave_final_frame && options.region.is_none()
+}
+
+/// Print a rendered frame as plain styled lines, for the main screen.
+///
+/// Trailing blank rows are dropped so the shell prompt lands directly below
+/// the last line of content.
+pub(crate) fn write_final_frame<W: Write>(writer: &mut W, buf: &Buffer) -> io::Result<()> {
+    use crossterm::style::{Print, ResetColor, SetAttribute, SetStyle};
+    use crossterm::{queue, style::Attribute};
+
+    let area = buf.area;
+    let is_blank_row =
+        |y: u16| (area.left()..area.right()).all(|x| buf[(x, y)].symbol().trim().is_empty());
+    let mut bottom = area.bottom();
+    while bottom > area.top() && is_blank_row(bottom - 1) {
+        bottom -= 1;
+    }
+
+    for y in area.top()..bottom {
+        // Group cells into runs of identical style to keep the output small.
+        let mut runs: Vec<(ratatui::style::Style, String)> = Vec::new();
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            if cell.skip {
+                continue;
+            }
+            match runs.last_mut() {
+                Some((style, text)) if *style == cell.style() => text.push_str(cell.symbol()),
+                _ => runs.push((cell.style(), cell.symbol().to_string())),
+            }
+        }
+        for (style, text) in runs {
+            queue!(
+                writer,
+                SetStyle(content_style(style)),
+                Print(text),
+                SetAttribute(Attribute::Reset),
+                ResetColor
+            )?;
+        }
+        queue!(writer, Print("\r\n"))?;
+    }
+    writer.flush()
+}
+
+/// Convert a ratatui style into the equivalent crossterm content style.
+fn content_style(style: ratatui::style::Style) -> crossterm::style::ContentStyle {
+    use crossterm::style::{Attribute, ContentStyle};
+    use ratatui::backend::IntoCrossterm;
+    use ratatui::style::Modifier;
+
+    let mut content = ContentStyle {
+        foreground_color: style.fg.map(IntoCrossterm::into_crossterm),
+        background_color: style.bg.map(IntoCrossterm::into_crossterm),
+        ..ContentStyle::default()
+    };
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            content.attributes.set(attribute);
+        }
+    }
+    content
+}
+
+/// Ask the model whether a quit request should go ahead. A cancelled quit
+/// schedules a redraw so any confirmation UI shows up.
+fn confirm_quit<M: Model>(model: &mut M, needs_redraw: &mut bool) -> bool {
+    match model.on_quit_requested() {
+        QuitDecision::Quit => true,
+        QuitDecision::Cancel => {
+            *needs_redraw = true;
+            false
+        }
+    }
+}
+
+/// Spawn `task` under `token`, sending a [`CommandError`] to `errors` if it
+/// panics.
+fn spawn_command_task(
+    token: CancellationToken,
+    errors: mpsc::UnboundedSender<CommandError>,
+    task: impl std::future::Future<Output = ()> + Send + 'static,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(cancellation::scope(token, report_panics(task, errors)))
+}
+
+/// Run `task`, sending a [`CommandError`] to `errors` if it panics.
+async fn report_panics(
+    task: impl std::future::Future<Output = ()>,
+    errors: mpsc::UnboundedSender<CommandError>,
+) {
+    use futures::FutureExt;
+    if let Err(payload) = std::panic::AssertUnwindSafe(command_task(task))
+        .catch_unwind()
+        .await
+    {
+        let _ = errors.send(CommandError::from_panic(payload));
+    }
+}
+
+thread_local! {
+    /// Set while the thread runs part of a command task, whose panics are
+    /// caught and reported instead of ending the program.
+    static IN_COMMAND_TASK: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Run `f` as part of a command task, so the panic hook leaves the
+/// terminal to the still-running program if it panics.
+pub(crate) fn as_command_task<T>(f: impl FnOnce() -> T) -> T {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IN_COMMAND_TASK.set(self.0);
+        }
+    }
+    let _reset = Reset(IN_COMMAND_TASK.replace(true));
+    f()
+}
+
+/// Poll `task` as part of a command task; see [`as_command_task`].
+async fn command_task<F: std::future::Future>(task: F) -> F::Output {
+    let mut task = std::pin::pin!(task);
+    std::future::poll_fn(|cx| as_command_task(|| task.as_mut().poll(cx))).await
+}
+
+/// Send every message of `stream` to `tx` until either side ends, subject
+/// to `gate` when the queue is bounded.
+async fn forward_stream<Msg>(
+    stream: futures::stream::BoxStream<'static, Msg>,
+    tx: &mpsc::UnboundedSender<Msg>,
+    gate: Option<&QueueGate>,
+) {
+    use futures::StreamExt;
+    futures::pin_mut!(stream);
+    while let Some(msg) = stream.next().await {
+        if !admit(gate).await {
+            continue;
+        }
+        if tx.send(msg).is_err() {
+            break;
+        }
+    }
+}
+
+/// Running [`Command::subscription_task`] tasks by key, each with the
+/// cancellation token it runs under. Clones share the same tasks, so
+/// sequences can start and cancel them from their own task.
+#[derive(Clone, Default)]
+struct Tasks {
+    handles: Arc<std::sync::Mutex<HashMap<String, TaskHandle>>>,
+}
+
+type TaskHandle = (tokio::task::AbortHandle, CancellationToken);
+
+impl Tasks {
+    /// Track `handle`, running under `token`, as `key`, stopping the task
+    /// it replaces.
+    fn start(&self, key: String, handle: tokio::task::AbortHandle, token: CancellationToken) {
+        let mut handles = self.lock();
+        // Forget tasks that already finished so the map doesn't grow.
+        handles.retain(|_, (h, _)| !h.is_finished());
+        if let Some(old) = handles.insert(key, (handle, token)) {
+            stop_task(old);
+        }
+    }
+
+    /// Stop the task under `key`, if any.
+    fn cancel(&self, key: &str) {
+        if let Some(task) = self.lock().remove(key) {
+            stop_task(task);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, TaskHandle>> {
+        // The map stays consistent even if a holder panicked.
+        self.handles
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Cancel a task's token, then abort it. Aborting only drops the task's
+/// own future; work it handed the token to, such as a blocking thread or
+/// another spawned task, learns of the cancellation through the token.
+fn stop_task((handle, token): TaskHandle) {
+    token.cancel();
+    handle.abort();
+}
+
+/// Execute a command sequentially (for `Command::sequence`), subject to
+/// `gate` when the queue is bounded.
+fn execute_command_sequential<'a, Msg: Send + 'static>(
+    cmd: Command<Msg>,
+    tx: &'a mpsc::UnboundedSender<Msg>,
+    gate: Option<&'a Arc<QueueGate>>,
+    tasks: &'a Tasks,
+) -> futures::future::BoxFuture<'a, ()> {
+    Box::pin(async move {
+        match cmd.inner {
+            CommandInner::None => {}
+            CommandInner::Action(Action::Message(msg)) => {
+                let _ = tx.send(msg);
+            }
+            CommandInner::Action(Action::Quit) | CommandInner::Action(Action::Redraw) => {
+                // Can't easily signal quit or redraw from here; send would
+                // need a special channel
+            }
+            CommandInner::Future(fut) => {
+                let msg = fut.await;
+                if admit(gate.map(Arc::as_ref)).await {
+                    let _ = tx.send(msg);
+                }
+            }
+            CommandInner::Stream(stream) => {
+                forward_stream(stream, tx, gate.map(Arc::as_ref)).await;
+            }
+            CommandInner::Task { key, stream } => {
+                // Register the task so `Command::cancel` can stop it, then
+                // wait for it to end or be cancelled before moving on.
+                let token = cancellation::current().child_token();
+                let (tx, gate) = (tx.clone(), gate.cloned());
+                let handle = tokio::spawn(cancellation::scope(
+                    token.clone(),
+                    command_task(async move {
+                        forward_stream(stream, &tx, gate.as_deref()).await;
+                    }),
+                ));
+                tasks.start(key, handle.abort_handle(), token);
+                if let Err(err) = handle.await {
+                    if err.is_panic() {
+                        std::panic::resume_unwind(err.into_panic());
+                    }
+                }
+            }
+            CommandInner::Cancel(key) => {
+                tasks.cancel(&key);
+            }
+            CommandInner::Batch(cmds) => {
+                // In a sequence, batch still runs concurrently within itself
+                let handles: Vec<_> = cmds
+                    .into_iter()
+                    .map(|cmd| {
+                        let tx = tx.clone();
+                        let gate = gate.cloned();
+                        let tasks = tasks.clone();
+                        tokio::spawn(cancellation::scope(
+                            cancellation::current(),
+                            command_task(async move {
+                                execute_command_sequential(cmd, &tx, gate.as_ref(), &tasks).await;
+                            }),
+                        ))
+                    })
+                    .collect();
+                for handle in handles {
+                    // Re-raise a branch's panic so the sequence's task
+                    // reports it.
+                    if let Err(err) = handle.await {
+                        if err.is_panic() {
+                            std::panic::resume_unwind(err.into_panic());
+                        }
+                    }
+                }
+            }
+            CommandInner::Sequence(cmds) => {
+                for cmd in cmds {
+                    execute_command_sequential(cmd, tx, gate, tasks).await;
+                }
+            }
+            CommandInner::Terminal(_)
+            | CommandInner::WithTerminal(_)
+            | CommandInner::CaptureFrame(_) => {
+                // Terminal commands from a sequential context are not supported
+                // (they need mutable terminal access)
+            }
+            CommandInner::Exec { .. } => {
+                // Exec commands from a sequential context are not supported
+                // (they need mutable terminal access). `Command::exec_output`
+                // is a plain future and runs in the Future arm above.
+            }
+        }
+    })
+}
+
+/// Whether `target` is connected to a terminal.
+fn is_terminal(target: OutputTarget) -> bool {
+    use std::io::IsTerminal;
+    match target {
+        OutputTarget::Stdout => stdout().is_terminal(),
+        OutputTarget::Stderr => stderr().is_terminal(),
+    }
+}
+
+/// Fail with [`ProgramError::NotATerminal`] if the options require a TTY
+/// and `is_tty` says the output isn't one.
+fn ensure_terminal(
+    options: &ProgramOptions,
+    is_tty: impl Fn(OutputTarget) -> bool,
+) -> Result<(), ProgramError> {
+    if options.require_tty && !is_tty(options.output) {
+        return Err(ProgramError::NotATerminal(options.output));
+    }
+    Ok(())
+}
+
+/// How to restore the terminal of the running program (alternate screen,
+/// output), or `None` while no program holds the terminal. Read by the
+/// panic hook and [`UnwindGuard`].
+static ACTIVE_TERMINAL: std::sync::Mutex<Option<(bool, OutputTarget)>> =
+    std::sync::Mutex::new(None);
+
+/// Set or clear the running program's terminal settings.
+fn set_active_terminal(active: Option<(bool, OutputTarget)>) {
+    *ACTIVE_TERMINAL
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = active;
+}
+
+/// Restore the running program's terminal, if any. Safe to call more than
+/// once; only the first call after setup does anything.
+fn restore_active_terminal() {
+    let active = ACTIVE_TERMINAL
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .take();
+    if let Some((alt_screen, output)) = active {
+        let _ = restore_terminal_minimal(alt_screen, output);
+    }
+}
+
+/// Install the terminal-restoring panic hook once per process, chained in
+/// front of the hook that was set before. Does nothing if `enabled` is
+/// false.
+fn install_panic_hook(enabled: bool) {
+    use std::sync::Once;
+    static HOOK_INSTALLED: Once = Once::new();
+    if !enabled {
+        return;
+    }
+    HOOK_INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            run_panic_hook(info, restore_active_terminal, &previous);
+        }));
+    });
+}
+
+/// Body of the panic hook: restore the terminal with `restore` so the
+/// panic message lands on a usable screen, then hand `info` to the
+/// `previous` hook.
+///
+/// A panic inside a command task is reported through
+/// [`Model::on_command_error`] and the program keeps running, so neither
+/// runs: the terminal stays set up and nothing is printed over the UI.
+fn run_panic_hook<I: ?Sized>(info: &I, restore: impl FnOnce(), previous: impl Fn(&I)) {
+    if IN_COMMAND_TASK.get() {
+        return;
+    }
+    restore();
+    previous(info);
+}
+
+/// Restores the terminal when dropped during a panic, so it is left usable
+/// even without the panic hook.
+struct UnwindGuard;
+
+impl Drop for UnwindGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            restore_active_terminal();
+        }
+    }
+}
+
+fn init_terminal(options: &ProgramOptions) -> Result<ProgramTerminal, ProgramError> {
+    if let Some(region) = options.region {
+        let writer = TerminalOutput(buffered(Output::new(options.output)));
+        return Ok(open_region(CrosstermBackend::new(writer), region)?);
+    }
+    install_panic_hook(options.catch_panics);
+
+    enable_raw_mode()?;
+    let mut writer = TerminalOutput(buffered(Output::new(options.output)));
+    write_terminal_setup(&mut writer, options)?;
+    set_active_terminal(Some((options.alt_screen, options.output)));
+
+    let backend = CrosstermBackend::new(writer);
+    let terminal = Terminal::new(backend)?;
+    Ok(terminal)
+}
+
+/// Blank `region` on screen and in the terminal's buffers, so the next
+/// frame repaints all of it.
+fn clear_region<B: Backend>(terminal: &mut Terminal<B>, region: Rect) -> Result<(), B::Error> {
+    let blank = Cell::default();
+    terminal
+        .backend_mut()
+        .draw(region.positions().map(|p| (p.x, p.y, &blank)))?;
+    // Each swap resets the buffer it retires.
+    terminal.swap_buffers();
+    terminal.swap_buffers();
+    Backend::flush(terminal.backend_mut())
+}
+
+/// Open a terminal that draws only into `region`, blanking it first.
+///
+/// ratatui diffs the first frame against blank cells, so the region must
+/// really be blank for cells the frame leaves empty to be correct.
+fn open_region<B: Backend>(mut backend: B, region: Rect) -> Result<Terminal<B>, B::Error> {
+    let blank = Cell::default();
+    backend.draw(region.positions().map(|p| (p.x, p.y, &blank)))?;
+    Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: Viewport::Fixed(region),
+        },
+    )
+}
+
+/// Write the escape sequences that put the terminal into the state
+/// requested by `options`.
+fn write_terminal_setup<W: Write>(writer: &mut W, options: &ProgramOptions) -> io::Result<()> {
+    if options.alt_screen {
+        execute!(writer, EnterAlternateScreen)?;
+    }
+    if options.bracketed_paste {
+        execute!(writer, EnableBracketedPaste)?;
+    }
+    if options.mouse_mode.is_some() {
+        execute!(writer, EnableMouseCapture)?;
+    }
+    if options.focus_reporting {
+        execute!(writer, EnableFocusChange)?;
+    }
+    if let Some(ref title) = options.title {
+        execute!(writer, SetTitle(title))?;
+    }
+    if !options.show_cursor {
+        execute!(writer, cursor::Hide)?;
+    }
+    Ok(())
+}
+
+fn restore_terminal(options: &ProgramOptions) -> Result<(), ProgramError> {
+    if options.region.is_some() {
+        // The host set the terminal up and restores it.
+        return Ok(());
+    }
+    set_active_terminal(None);
+    restore_terminal_minimal(options.alt_screen, options.output)?;
+    Ok(())
+}
+
+fn restore_terminal_minimal(
+    alt_screen: bool,
+    output_target: OutputTarget,
+) -> Result<(), std::io::Error> {
+    // Use best-effort cleanup: continue even if individual steps fail,
+    // so we restore as much terminal state as possible.
+    let r1 = disable_raw_mode();
+    let mut writer = Output::new(output_target);
+    execute!(writer, DisableBracketedPaste).ok();
+    execute!(writer, DisableMouseCapture).ok();
+    execute!(writer, DisableFocusChange).ok();
+    execute!(writer, cursor::Show).ok();
+    if alt_screen {
+        execute!(writer, LeaveAlternateScreen).ok();
+    }
+    // Propagate the raw mode error if it was the only failure that matters
+    r1
+}
+
+/// Open a log file for debugging TUI applications.
+///
+/// Returns a file handle that can be used with `writeln!` or passed to
+/// a logging framework. The file is opened in append mode.
+///
+/// This is the equivalent of Bubble Tea's `tea.LogToFile()`.
+///
+/// # Example
+///
+/// ```no_run
+/// use boba_core::runtime::log_to_file;
+/// use std::io::Write;
 ///
 /// let mut f = log_to_file("debug.log").unwrap();
 /// writeln!(f, "debug message").unwrap();
@@ -751,3 +1917,1242 @@ pub fn log_to_file(path: impl AsRef<std::path::Path>) -> Result<std::fs::File, s
         .append(true)
         .open(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Paragraph;
+
+    fn render_to_buffer(text: &str, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(Paragraph::new(text), frame.area()))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn final_frame_kept_only_with_alt_screen() {
+        let mut options = ProgramOptions {
+            leave_final_frame: true,
+            ..ProgramOptions::default()
+        };
+        assert!(keeps_final_frame(&options));
+
+        options.alt_screen = false;
+        assert!(!keeps_final_frame(&options));
+
+        assert!(!keeps_final_frame(&ProgramOptions::default()));
+    }
+
+    #[test]
+    fn print_above_leaves_and_reenters_alt_screen() {
+        let mut out = Vec::new();
+        write_print_above(&mut out, "one\ntwo", true).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let leave = out.find("\x1b[?1049l").unwrap();
+        let one = out.find("one\r\n").unwrap();
+        let two = out.find("two\r\n").unwrap();
+        let enter = out.find("\x1b[?1049h").unwrap();
+        assert!(leave < one && one < two && two < enter);
+    }
+
+    #[test]
+    fn print_above_without_alt_screen_only_prints() {
+        let mut out = Vec::new();
+        write_print_above(&mut out, "line\r\n", false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "line\r\n");
+    }
+
+    #[test]
+    fn write_final_frame_prints_buffer_lines() {
+        let buf = render_to_buffer("first\nsecond", 10, 5);
+        let mut out = Vec::new();
+        write_final_frame(&mut out, &buf).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let first = out.find("first").unwrap();
+        let second = out.find("second").unwrap();
+        assert!(first < second);
+        // Trailing blank rows are trimmed: one line break per content row.
+        assert_eq!(out.matches("\r\n").count(), 2);
+    }
+
+    struct Counting {
+        renders: usize,
+        ready: usize,
+        updates: usize,
+    }
+
+    impl Model for Counting {
+        type Message = ();
+        type Flags = ();
+
+        fn init(_: ()) -> (Self, Command<()>) {
+            (
+                Counting {
+                    renders: 0,
+                    ready: 0,
+                    updates: 0,
+                },
+                Command::none(),
+            )
+        }
+
+        fn update(&mut self, _msg: ()) -> Command<()> {
+            self.updates += 1;
+            Command::none()
+        }
+
+        fn view(&self, frame: &mut ratatui::Frame) {
+            frame.render_widget(Paragraph::new("x"), frame.area());
+        }
+
+        fn after_render(&mut self) -> Command<()> {
+            self.renders += 1;
+            Command::none()
+        }
+
+        fn ready(&mut self) -> Command<()> {
+            assert_eq!(self.renders, 1, "ready must follow the first frame");
+            self.ready += 1;
+            Command::message(())
+        }
+    }
+
+    impl<M: Model> Program<M> {
+        /// A program drawing into a region of an in-memory screen instead
+        /// of the terminal, so tests can drive the real event loop. Returns
+        /// the program and everything it writes.
+        fn headless(
+            flags: M::Flags,
+            options: ProgramOptions,
+        ) -> (Self, Arc<std::sync::Mutex<Vec<u8>>>) {
+            let screen = Arc::<std::sync::Mutex<Vec<u8>>>::default();
+            let options = ProgramOptions {
+                region: options.region.or(Some(Rect::new(0, 0, 20, 4))),
+                handle_signals: false,
+                ..options
+            };
+            let program = Self::build(
+                flags,
+                options,
+                |_| true,
+                |options| {
+                    let writer = TerminalOutput(buffered(Output::Memory(Arc::clone(&screen))));
+                    Ok(open_region(
+                        CrosstermBackend::new(writer),
+                        options.region.unwrap(),
+                    )?)
+                },
+            )
+            .unwrap();
+            (program, screen)
+        }
+    }
+
+    /// Run `program` to completion, failing after a minute of (usually
+    /// virtual) time.
+    async fn run_to_end<M: Model>(program: Program<M>) -> M {
+        tokio::time::timeout(Duration::from_secs(60), program.run())
+            .await
+            .expect("program did not exit")
+            .unwrap()
+    }
+
+    /// Records the messages it receives and quits on 0.
+    struct Collect(Vec<u32>);
+
+    impl Model for Collect {
+        type Message = u32;
+        type Flags = Command<u32>;
+
+        fn init(cmd: Command<u32>) -> (Self, Command<u32>) {
+            (Collect(Vec::new()), cmd)
+        }
+
+        fn update(&mut self, msg: u32) -> Command<u32> {
+            self.0.push(msg);
+            if msg == 0 {
+                Command::quit()
+            } else {
+                Command::none()
+            }
+        }
+
+        fn view(&self, _frame: &mut ratatui::Frame) {}
+    }
+
+    /// Sets the frame rate from `update` to each nonzero message and quits
+    /// on 0.
+    struct Retune;
+
+    impl Model for Retune {
+        type Message = u32;
+        type Flags = Command<u32>;
+
+        fn init(cmd: Command<u32>) -> (Self, Command<u32>) {
+            (Retune, cmd)
+        }
+
+        fn update(&mut self, fps: u32) -> Command<u32> {
+            if fps == 0 {
+                Command::quit()
+            } else {
+                Command::set_fps(fps)
+            }
+        }
+
+        fn view(&self, _frame: &mut ratatui::Frame) {}
+    }
+
+    /// Frames a headless [`Retune`] reports over one second after `init`
+    /// returns `setup`.
+    async fn frames_in_one_second(setup: Command<u32>) -> usize {
+        let frames = Arc::new(AtomicUsize::new(0));
+        let options = ProgramOptions::default().with_on_frame({
+            let frames = Arc::clone(&frames);
+            move |_| {
+                frames.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        let quit = Command::perform(tokio::time::sleep(Duration::from_secs(1)), |()| 0);
+        let (program, _) = Program::<Retune>::headless(Command::batch([setup, quit]), options);
+        run_to_end(program).await;
+        frames.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn set_fps_command_changes_the_running_frame_rate() {
+        let default = frames_in_one_second(Command::none()).await;
+        assert!((59..=62).contains(&default), "{default} frames at 60 fps");
+        // Sent from `update`, once the frame timer is already running.
+        let slow = frames_in_one_second(Command::message(5)).await;
+        assert!((5..=8).contains(&slow), "{slow} frames at 5 fps");
+    }
+
+    #[tokio::test]
+    async fn flush_command_writes_out_buffered_output() {
+        let (mut program, screen) =
+            Program::<Collect>::headless(Command::none(), ProgramOptions::default());
+        program
+            .terminal
+            .backend_mut()
+            .write_all(b"pending")
+            .unwrap();
+        assert!(!screen.lock().unwrap().ends_with(b"pending"));
+
+        program.execute_terminal_command(TerminalCommand::Flush);
+        assert!(screen.lock().unwrap().ends_with(b"pending"));
+    }
+
+    #[tokio::test]
+    async fn failed_with_terminal_is_logged() {
+        let log =
+            std::env::temp_dir().join(format!("boba-with-terminal-{}.log", std::process::id()));
+        let options = ProgramOptions {
+            log_file: Some(log.clone()),
+            ..ProgramOptions::default()
+        };
+        let (mut program, _) = Program::<Collect>::headless(Command::none(), options);
+        program.terminal_released = true;
+
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        program.execute_command(Command::with_terminal(move |_| {
+            flag.store(true, Ordering::SeqCst)
+        }));
+        drop(program);
+
+        let logged = std::fs::read_to_string(&log).unwrap();
+        let _ = std::fs::remove_file(&log);
+        assert!(!ran.load(Ordering::SeqCst));
+        assert!(logged.contains("with_terminal skipped"), "{logged}");
+        assert!(logged.contains("terminal is released"), "{logged}");
+    }
+
+    /// Asks to quit on `true` and saves on `false`.
+    struct Guarded {
+        unsaved: bool,
+        confirming: bool,
+    }
+
+    impl Model for Guarded {
+        type Message = bool;
+        type Flags = bool;
+
+        fn init(unsaved: bool) -> (Self, Command<bool>) {
+            (
+                Guarded {
+                    unsaved,
+                    confirming: false,
+                },
+                Command::none(),
+            )
+        }
+
+        fn update(&mut self, quit: bool) -> Command<bool> {
+            if quit {
+                Command::quit()
+            } else {
+                self.unsaved = false;
+                Command::none()
+            }
+        }
+
+        fn view(&self, _frame: &mut ratatui::Frame) {}
+
+        fn on_quit_requested(&mut self) -> QuitDecision {
+            if self.unsaved {
+                self.confirming = true;
+                QuitDecision::Cancel
+            } else {
+                QuitDecision::Quit
+            }
+        }
+    }
+
+    #[test]
+    fn cancelled_quit_keeps_running_and_redraws() {
+        let (mut model, _) = Guarded::init(true);
+        let mut needs_redraw = false;
+        assert!(!confirm_quit(&mut model, &mut needs_redraw));
+        assert!(model.confirming);
+        assert!(needs_redraw);
+    }
+
+    #[tokio::test]
+    async fn vetoed_quit_keeps_the_program_running() {
+        let (program, _) = Program::<Guarded>::headless(true, ProgramOptions::default());
+        let tx = program.sender();
+        for msg in [true, false, true] {
+            tx.send(msg).unwrap();
+        }
+
+        // The first quit is cancelled, so the save after it still lands
+        // and the second quit goes through.
+        let model = run_to_end(program).await;
+        assert!(model.confirming);
+        assert!(!model.unsaved);
+    }
+
+    #[test]
+    fn accepted_quit_proceeds() {
+        let (mut model, _) = Guarded::init(false);
+        let mut needs_redraw = false;
+        assert!(confirm_quit(&mut model, &mut needs_redraw));
+        assert!(!needs_redraw);
+
+        // Default hook always quits.
+        let (mut counting, _) = Counting::init(());
+        assert!(confirm_quit(&mut counting, &mut needs_redraw));
+    }
+
+    /// Count the ticks `timer` fires in one virtual second, excluding the
+    /// immediate first tick. A millisecond of slack absorbs rounding in
+    /// periods like 1/60 s.
+    async fn ticks_per_second(mut timer: tokio::time::Interval) -> u32 {
+        timer.tick().await;
+        let end = tokio::time::Instant::now() + Duration::from_millis(1001);
+        let mut ticks = 0;
+        while timer.tick().await <= end {
+            ticks += 1;
+        }
+        ticks
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn set_fps_rearms_frame_timer() {
+        assert_eq!(ticks_per_second(frame_timer(60)).await, 60);
+        assert_eq!(ticks_per_second(frame_timer(10)).await, 10);
+        // Out-of-range rates are clamped like `ProgramOptions::fps`.
+        assert_eq!(ticks_per_second(frame_timer(0)).await, 1);
+        assert_eq!(ticks_per_second(frame_timer(1000)).await, 120);
+    }
+
+    /// Counts the writes that reach it.
+    #[derive(Clone, Default)]
+    struct CountingWriter {
+        writes: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes.fetch_add(1, Ordering::SeqCst);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn writes_for_one_frame<W: Write>(writer: W, counter: &CountingWriter) -> usize {
+        let backend = CrosstermBackend::new(writer);
+        let mut terminal = Terminal::with_options(
+            backend,
+            ratatui::TerminalOptions {
+                viewport: ratatui::Viewport::Fixed(ratatui::layout::Rect::new(0, 0, 40, 10)),
+            },
+        )
+        .unwrap();
+        counter.writes.store(0, Ordering::SeqCst);
+        terminal
+            .draw(|frame| {
+                for (i, row) in frame.area().rows().enumerate() {
+                    let style = ratatui::style::Style::default()
+                        .fg(ratatui::style::Color::Indexed(i as u8));
+                    frame.render_widget(Paragraph::new(format!("row {i}")).style(style), row);
+                }
+            })
+            .unwrap();
+        counter.writes.load(Ordering::SeqCst)
+    }
+
+    /// Shares everything written to it.
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_writes_out_buffered_output_in_order() {
+        let sink = SharedWriter::default();
+        let mut writer = buffered(sink.clone());
+        writer.write_all(b"frame").unwrap();
+        assert!(sink.0.lock().unwrap().is_empty());
+
+        // What TerminalCommand::Flush does.
+        write_output(&mut writer, "").unwrap();
+        assert_eq!(&*sink.0.lock().unwrap(), b"frame");
+
+        // Printed lines follow buffered frames rather than overtaking them.
+        writer.write_all(b"next frame").unwrap();
+        write_output(&mut writer, "log\r\n").unwrap();
+        assert_eq!(&*sink.0.lock().unwrap(), b"framenext framelog\r\n");
+    }
+
+    #[test]
+    fn buffered_output_coalesces_frame_writes() {
+        let direct = CountingWriter::default();
+        let unbuffered = writes_for_one_frame(direct.clone(), &direct);
+
+        let counted = CountingWriter::default();
+        let buffered_writes = writes_for_one_frame(buffered(counted.clone()), &counted);
+
+        assert!(unbuffered > 10, "expected many writes, got {unbuffered}");
+        assert_eq!(buffered_writes, 1);
+    }
+
+    struct Slow;
+
+    impl Model for Slow {
+        type Message = ();
+        type Flags = ();
+
+        fn init(_: ()) -> (Self, Command<()>) {
+            (Slow, Command::none())
+        }
+
+        fn update(&mut self, _msg: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self, frame: &mut ratatui::Frame) {
+            std::thread::sleep(Duration::from_millis(2));
+            frame.render_widget(Paragraph::new("slow"), frame.area());
+        }
+    }
+
+    #[test]
+    fn frame_metrics_report_render_time_and_message_count() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let options = ProgramOptions::default().with_on_frame({
+            let seen = Arc::clone(&seen);
+            move |m| seen.lock().unwrap().push(m)
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(10, 1)).unwrap();
+        let (mut model, _) = Slow::init(());
+        let mut needs_redraw = true;
+        let mut messages = 0;
+
+        // One frame after three updates, then an idle frame.
+        for pending in [3, 0] {
+            for _ in 0..pending {
+                let _ = model.update(());
+                messages += 1;
+                needs_redraw = true;
+            }
+            let start = Instant::now();
+            let cmd =
+                draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+            report_frame(
+                options.on_frame.as_ref(),
+                start.elapsed(),
+                cmd.is_some(),
+                &mut messages,
+            );
+        }
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert!(seen[0].redrawn);
+        assert_eq!(seen[0].messages, 3);
+        assert!(seen[0].render_time >= Duration::from_millis(2));
+        assert!(!seen[1].redrawn);
+        assert_eq!(seen[1].messages, 0);
+        assert_eq!(seen[1].render_time, Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn frame_metrics_count_every_received_message() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let options = ProgramOptions::default().with_on_frame({
+            let seen = Arc::clone(&seen);
+            move |m: FrameMetrics| seen.lock().unwrap().push(m.messages)
+        });
+        let init = Command::batch([
+            Command::message(1),
+            Command::message(2),
+            Command::message(3),
+            Command::perform(tokio::time::sleep(Duration::from_secs(1)), |()| 0),
+        ]);
+        let (program, _) = Program::<Collect>::headless(init, options);
+        let model = run_to_end(program.with_filter(|n| (n != 2).then_some(n))).await;
+        assert_eq!(model.0, [1, 3, 0]);
+
+        // The initial frame comes first; the quitting 0 is never reported.
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0], 0);
+        assert_eq!(seen.iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn after_render_runs_once_per_drawn_frame() {
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = true;
+
+        let cmd = draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert!(cmd.is_some());
+        assert_eq!(model.renders, 1);
+        assert!(!needs_redraw);
+
+        // Nothing changed: the frame is skipped and the hook is not called
+        let cmd = draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert!(cmd.is_none());
+        assert_eq!(model.renders, 1);
+
+        needs_redraw = true;
+        let mut final_frame = None;
+        draw_pending(
+            &mut terminal,
+            &mut model,
+            None,
+            &mut needs_redraw,
+            Some(&mut final_frame),
+        )
+        .unwrap();
+        assert_eq!(model.renders, 2);
+        assert!(final_frame.is_some());
+    }
+
+    #[test]
+    fn between_frames_gives_direct_terminal_access() {
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+
+        let size = between_frames(&mut terminal, &mut needs_redraw, |terminal| {
+            let cell = ratatui::buffer::Cell::new("Z");
+            terminal
+                .backend_mut()
+                .draw([(2, 0, &cell)].into_iter())
+                .unwrap();
+            terminal.size().unwrap()
+        })
+        .unwrap();
+        assert_eq!(size.width, 4);
+        assert_eq!(terminal.backend().buffer()[(2, 0)].symbol(), "Z");
+        assert!(needs_redraw);
+
+        // The next frame only repaints what the view changed.
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert_eq!(model.renders, 2);
+        assert_eq!(terminal.backend().buffer()[(2, 0)].symbol(), "Z");
+    }
+
+    #[test]
+    fn region_drawing_leaves_other_cells_alone() {
+        // The host's screen, filled with '#'.
+        let mut backend = TestBackend::new(8, 4);
+        let host = Cell::new("#");
+        let area = Rect::new(0, 0, 8, 4);
+        backend
+            .draw(area.positions().map(|p| (p.x, p.y, &host)))
+            .unwrap();
+
+        let mut terminal = open_region(backend, Rect::new(2, 1, 4, 2)).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..8).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["########", "##x   ##", "##    ##", "########"]);
+        assert!(!keeps_final_frame(&ProgramOptions {
+            leave_final_frame: true,
+            region: Some(Rect::new(2, 1, 4, 2)),
+            ..ProgramOptions::default()
+        }));
+    }
+
+    #[test]
+    fn clearing_a_region_leaves_other_cells_alone() {
+        let mut backend = TestBackend::new(8, 4);
+        let host = Cell::new("#");
+        backend
+            .draw(Rect::new(0, 0, 8, 4).positions().map(|p| (p.x, p.y, &host)))
+            .unwrap();
+        let region = Rect::new(2, 1, 4, 2);
+        let mut terminal = open_region(backend, region).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+
+        clear_region(&mut terminal, region).unwrap();
+        let rows = |terminal: &Terminal<TestBackend>| -> Vec<String> {
+            let buf = terminal.backend().buffer();
+            (0..4)
+                .map(|y| (0..8).map(|x| buf[(x, y)].symbol()).collect())
+                .collect()
+        };
+        assert_eq!(
+            rows(&terminal),
+            ["########", "##    ##", "##    ##", "########"]
+        );
+
+        // The next frame repaints the region even though the view is
+        // unchanged.
+        needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert_eq!(
+            rows(&terminal),
+            ["########", "##x   ##", "##    ##", "########"]
+        );
+    }
+
+    #[tokio::test]
+    async fn redraw_request_draws_one_frame_without_update() {
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+
+        let redraw = Arc::new(Notify::new());
+        let handle = ProgramHandle::<()> {
+            msg_tx: mpsc::unbounded_channel().0,
+            gate: None,
+            killed: Arc::new(AtomicBool::new(false)),
+            redraw: redraw.clone(),
+        };
+        handle.request_redraw();
+        handle.request_redraw();
+
+        // What the event loop does when woken.
+        tokio::time::timeout(Duration::from_secs(1), redraw.notified())
+            .await
+            .expect("redraw request should wake the loop");
+        needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert_eq!(model.renders, 2);
+        assert_eq!(model.updates, 0);
+
+        // Both requests were coalesced into that one frame.
+        let again = tokio::time::timeout(Duration::from_millis(10), redraw.notified()).await;
+        assert!(again.is_err());
+    }
+
+    #[test]
+    fn ready_fires_once_after_first_render() {
+        let mut terminal = Terminal::new(TestBackend::new(4, 1)).unwrap();
+        let (mut model, _) = Counting::init(());
+        let mut needs_redraw = false;
+
+        let cmd = draw_initial(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert_eq!(model.renders, 1);
+        assert_eq!(model.ready, 1);
+        assert_eq!(terminal.backend().buffer()[(0, 0)].symbol(), "x");
+        assert!(!cmd.is_none());
+
+        // Later frames only run after_render
+        needs_redraw = true;
+        draw_pending(&mut terminal, &mut model, None, &mut needs_redraw, None).unwrap();
+        assert_eq!(model.renders, 2);
+        assert_eq!(model.ready, 1);
+    }
+
+    #[test]
+    fn focus_change_schedules_redraw() {
+        use crate::event::TerminalEvent;
+        use crate::subscriptions::TerminalState;
+
+        let terminal = TerminalState::default();
+        let options = ProgramOptions::default();
+        let mut needs_redraw = false;
+        terminal.preprocess(TerminalEvent::FocusGained);
+        check_focus_redraw(&options, &terminal, &mut needs_redraw);
+        assert!(needs_redraw);
+
+        // The change is consumed by the check
+        needs_redraw = false;
+        check_focus_redraw(&options, &terminal, &mut needs_redraw);
+        assert!(!needs_redraw);
+
+        let options = ProgramOptions {
+            redraw_on_focus_change: false,
+            ..ProgramOptions::default()
+        };
+        terminal.preprocess(TerminalEvent::FocusLost);
+        check_focus_redraw(&options, &terminal, &mut needs_redraw);
+        assert!(!needs_redraw);
+    }
+
+    #[tokio::test]
+    async fn external_flood_does_not_starve_terminal_input() {
+        let (internal_tx, internal_rx) = mpsc::unbounded_channel();
+        let (external_tx, external_rx) = mpsc::unbounded_channel();
+        let mut inbox = Inbox::new(internal_rx, external_rx);
+
+        for i in 0..1000 {
+            external_tx.send(format!("ext{i}")).unwrap();
+        }
+        internal_tx.send("key a".to_string()).unwrap();
+        internal_tx.send("ctrl+c".to_string()).unwrap();
+
+        let first: Vec<String> = (0..4).map(|_| inbox.try_recv().unwrap()).collect();
+        assert_eq!(first, ["key a", "ext0", "ctrl+c", "ext1"]);
+
+        // The rest of the flood follows in order.
+        for i in 2..1000 {
+            assert_eq!(inbox.recv().await.unwrap(), format!("ext{i}"));
+        }
+        assert!(inbox.try_recv().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn zero_capacity_blocking_queue_still_delivers() {
+        let options = ProgramOptions {
+            message_queue: MessageQueue::Bounded {
+                capacity: 0,
+                overflow: Overflow::Block,
+            },
+            ..ProgramOptions::default()
+        };
+        let init = Command::sequence([
+            Command::perform(async { 1 }, |n: u32| n),
+            Command::perform(async { 2 }, |n: u32| n),
+            Command::perform(async { 0 }, |n: u32| n),
+        ]);
+        let (program, _screen) = Program::<Collect>::headless(init, options);
+        let model = run_to_end(program).await;
+        assert_eq!(model.0, [1, 2, 0]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn region_ignores_whole_screen_commands() {
+        let init = Command::batch([
+            Command::clear_screen(),
+            Command::scroll_up(2),
+            Command::println("over the host"),
+            Command::print_above("above the host"),
+            Command::set_title("title"),
+            Command::enter_alt_screen(),
+            Command::message(0),
+        ]);
+        let (program, screen) = Program::<Collect>::headless(init, ProgramOptions::default());
+        run_to_end(program).await;
+
+        let written = String::from_utf8_lossy(&screen.lock().unwrap()).into_owned();
+        for sequence in ["\x1b[2J", "\x1b[2S", "\x1b]0;", "\x1b[?1049h", "host"] {
+            assert!(!written.contains(sequence), "wrote {sequence:?}");
+        }
+    }
+
+    fn bounded_handle(
+        capacity: usize,
+        overflow: Overflow,
+    ) -> (ProgramHandle<u32>, Inbox<u32>, mpsc::UnboundedSender<u32>) {
+        let gate = QueueGate::new(MessageQueue::Bounded { capacity, overflow });
+        let (internal_tx, internal_rx) = mpsc::unbounded_channel();
+        let (external_tx, external_rx) = mpsc::unbounded_channel();
+        let handle = ProgramHandle {
+            msg_tx: external_tx,
+            gate: gate.clone(),
+            killed: Arc::new(AtomicBool::new(false)),
+            redraw: Arc::new(Notify::new()),
+        };
+        (
+            handle,
+            Inbox::new(internal_rx, external_rx).with_gate(gate),
+            internal_tx,
+        )
+    }
+
+    #[tokio::test]
+    async fn bounded_queue_drops_excess_sends() {
+        let (handle, mut inbox, internal_tx) = bounded_handle(3, Overflow::Drop);
+        for i in 0..100 {
+            handle.send(i);
+        }
+        assert_eq!(
+            (0..4).map(|_| inbox.try_recv()).collect::<Vec<_>>(),
+            [Some(0), Some(1), Some(2), None]
+        );
+
+        // Draining the queue makes room again, and input is never dropped.
+        handle.send(100);
+        internal_tx.send(7).unwrap();
+        assert_eq!(inbox.recv().await, Some(7));
+        assert_eq!(inbox.recv().await, Some(100));
+
+        // A full queue drops command stream messages too.
+        let stream = Box::pin(futures::stream::iter(200..210));
+        forward_stream(stream, &internal_tx, inbox.gate.as_deref()).await;
+        let received: Vec<u32> = std::iter::from_fn(|| inbox.try_recv()).collect();
+        assert_eq!(received, [200, 201, 202]);
+    }
+
+    #[tokio::test]
+    async fn bounded_queue_applies_inside_sequences() {
+        let (_handle, mut inbox, internal_tx) = bounded_handle(2, Overflow::Drop);
+        let cmd = Command::sequence([
+            Command::perform(async { 1 }, |n: u32| n),
+            Command::batch([
+                Command::perform(async { 2 }, |n: u32| n),
+                Command::perform(async { 3 }, |n: u32| n),
+            ]),
+            Command::perform(async { 4 }, |n: u32| n),
+        ]);
+        let gate = inbox.gate.clone();
+        execute_command_sequential(cmd, &internal_tx, gate.as_ref(), &Tasks::default()).await;
+
+        let received: Vec<u32> = std::iter::from_fn(|| inbox.try_recv()).collect();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0], 1);
+    }
+
+    #[tokio::test]
+    async fn bounded_queue_blocks_async_sends_until_room() {
+        let (handle, mut inbox, _internal_tx) = bounded_handle(1, Overflow::Block);
+        handle.send_async(1).await;
+        let waiting = tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.send_async(2).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiting.is_finished());
+
+        assert_eq!(inbox.recv().await, Some(1));
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("send should resume once the queue has room")
+            .unwrap();
+        assert_eq!(inbox.recv().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn inbox_waits_for_either_queue() {
+        let (internal_tx, internal_rx) = mpsc::unbounded_channel();
+        let (external_tx, external_rx) = mpsc::unbounded_channel();
+        let mut inbox = Inbox::new(internal_rx, external_rx);
+
+        tokio::spawn(async move {
+            tokio::task::yield_now().await;
+            external_tx.send(1).unwrap();
+        });
+        assert_eq!(inbox.recv().await, Some(1));
+
+        drop(internal_tx);
+        assert_eq!(inbox.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn panicking_future_reports_command_error() {
+        let cmd = Command::perform(async { panic!("boom") }, |n: u32| n);
+        let CommandInner::Future(fut) = cmd.inner else {
+            panic!("expected a future command");
+        };
+        let (msg_tx, mut msg_rx) = mpsc::unbounded_channel();
+        let (error_tx, mut error_rx) = mpsc::unbounded_channel();
+        let task = async move {
+            let _ = msg_tx.send(fut.await);
+        };
+        tokio::spawn(report_panics(task, error_tx)).await.unwrap();
+
+        assert_eq!(
+            error_rx.recv().await,
+            Some(CommandError::Panicked("boom".to_string()))
+        );
+        assert!(msg_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn subscription_task_pushes_messages_until_cancelled() {
+        let cmd = Command::subscription_task("ticker", |tx| async move {
+            for i in 0.. {
+                if tx.send(i).is_err() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+        let CommandInner::Task { key, stream } = cmd.map(|n: u32| n * 10).inner else {
+            panic!("expected a task command");
+        };
+        assert_eq!(key, "ticker");
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let tasks = Tasks::default();
+        tasks.start(
+            key,
+            tokio::spawn(async move { forward_stream(stream, &tx, None).await }).abort_handle(),
+            CancellationToken::new(),
+        );
+        for expected in [0, 10, 20] {
+            assert_eq!(rx.recv().await, Some(expected));
+        }
+
+        tasks.cancel("ticker");
+        // Drain what was sent before the abort; then the channel closes.
+        let drained = tokio::time::timeout(Duration::from_secs(1), async {
+            while rx.recv().await.is_some() {}
+        })
+        .await;
+        assert!(drained.is_ok(), "task kept running after cancel");
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_task_signals_its_token() {
+        let (token_tx, token_rx) = tokio::sync::oneshot::channel();
+        let cmd = Command::subscription_task("watch", |_tx| async move {
+            let _ = token_tx.send(cancellation::current());
+            std::future::pending::<()>().await;
+        });
+        let CommandInner::Task { key, stream } = cmd.inner else {
+            panic!("expected a task command");
+        };
+
+        let (tx, _rx) = mpsc::unbounded_channel::<()>();
+        let shutdown = CancellationToken::new();
+        let token = shutdown.child_token();
+        let tasks = Tasks::default();
+        tasks.start(
+            key,
+            tokio::spawn(cancellation::scope(token.clone(), async move {
+                forward_stream(stream, &tx, None).await
+            }))
+            .abort_handle(),
+            token,
+        );
+        let observed = token_rx.await.unwrap();
+        assert!(!observed.is_cancelled());
+
+        // What the runtime does for `Command::cancel("watch")`.
+        tasks.cancel("watch");
+        tokio::time::timeout(Duration::from_secs(1), observed.cancelled())
+            .await
+            .expect("cancel should signal the task's token");
+        assert!(!shutdown.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn subscription_task_stream_ends_with_the_task() {
+        use futures::StreamExt;
+
+        let cmd = Command::subscription_task("once", |tx| async move {
+            tx.send("a").unwrap();
+            tx.send("b").unwrap();
+        });
+        let CommandInner::Task { stream, .. } = cmd.inner else {
+            panic!("expected a task command");
+        };
+        assert_eq!(stream.collect::<Vec<_>>().await, ["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn panic_inside_sequence_batch_is_reported() {
+        let cmd = Command::sequence([
+            Command::message(1),
+            Command::batch([Command::perform(
+                async { panic!("{} failed", "fetch") },
+                |n: u32| n,
+            )]),
+            Command::message(2),
+        ]);
+        let (msg_tx, mut msg_rx) = mpsc::unbounded_channel();
+        let (error_tx, mut error_rx) = mpsc::unbounded_channel();
+        let task =
+            async move { execute_command_sequential(cmd, &msg_tx, None, &Tasks::default()).await };
+        tokio::spawn(report_panics(task, error_tx)).await.unwrap();
+
+        assert_eq!(
+            error_rx.recv().await,
+            Some(CommandError::Panicked("fetch failed".to_string()))
+        );
+        // The sequence stops at the failed step.
+        assert_eq!(msg_rx.recv().await, Some(1));
+        assert_eq!(msg_rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn task_inside_sequence_can_be_cancelled() {
+        let cmd = Command::sequence([
+            Command::subscription_task("tail", |tx| async move {
+                let _ = tx.send(1);
+                std::future::pending::<()>().await;
+            }),
+            Command::message(2),
+        ]);
+        let tasks = Tasks::default();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sequence = tokio::spawn({
+            let tasks = tasks.clone();
+            async move { execute_command_sequential(cmd, &tx, None, &tasks).await }
+        });
+
+        assert_eq!(rx.recv().await, Some(1));
+        tasks.cancel("tail");
+        sequence.await.unwrap();
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sequence_runs_captured_exec_in_order() {
+        use crate::command::ExecCommand;
+
+        let output = |label: &'static str| {
+            move |result: io::Result<std::process::Output>| {
+                let out = result.unwrap();
+                format!("{label}:{}", String::from_utf8_lossy(&out.stdout).trim())
+            }
+        };
+        let cmd = Command::sequence([
+            Command::exec_output(
+                ExecCommand::new("sh").args(["-c", "sleep 0.05; echo built"]),
+                output("build"),
+            ),
+            Command::exec_output(ExecCommand::new("echo").arg("passed"), output("test")),
+            Command::message("results".to_string()),
+        ]);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        execute_command_sequential(cmd, &tx, None, &Tasks::default()).await;
+        drop(tx);
+        let mut msgs = Vec::new();
+        while let Some(msg) = rx.recv().await {
+            msgs.push(msg);
+        }
+        assert_eq!(msgs, ["build:built", "test:passed", "results"]);
+    }
+
+    #[test]
+    fn show_cursor_skips_hide_during_setup() {
+        use crossterm::Command as _;
+
+        let mut hide = String::new();
+        cursor::Hide.write_ansi(&mut hide).unwrap();
+
+        let mut out = Vec::new();
+        write_terminal_setup(&mut out, &ProgramOptions::default()).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains(&hide));
+
+        let options = ProgramOptions {
+            show_cursor: true,
+            ..ProgramOptions::default()
+        };
+        let mut out = Vec::new();
+        write_terminal_setup(&mut out, &options).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains(&hide));
+    }
+
+    struct MissingConfig;
+
+    impl Model for MissingConfig {
+        type Message = ();
+        type Flags = &'static str;
+
+        fn init(path: &'static str) -> (Self, Command<()>) {
+            Self::try_init(path).expect("missing config")
+        }
+
+        fn try_init(path: &'static str) -> Result<(Self, Command<()>), crate::InitError> {
+            std::fs::read_to_string(path)?;
+            Ok((MissingConfig, Command::none()))
+        }
+
+        fn update(&mut self, _msg: ()) -> Command<()> {
+            Command::none()
+        }
+
+        fn view(&self, _frame: &mut ratatui::Frame) {}
+    }
+
+    /// Held by tests that read or set [`ACTIVE_TERMINAL`].
+    static ACTIVE_TERMINAL_TESTS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn failing_try_init_is_reported_before_terminal_setup() {
+        let _active = ACTIVE_TERMINAL_TESTS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let options = ProgramOptions {
+            require_tty: false,
+            ..ProgramOptions::default()
+        };
+        let result = Program::<MissingConfig>::with_options("/nonexistent/boba.toml", options);
+        let Err(ProgramError::Init(err)) = result else {
+            panic!("expected an init error");
+        };
+        assert!(err.downcast_ref::<std::io::Error>().is_some());
+        assert!(!crossterm::terminal::is_raw_mode_enabled().unwrap());
+        assert!(ACTIVE_TERMINAL.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn non_tty_output_is_rejected_up_front() {
+        let options = ProgramOptions {
+            output: OutputTarget::Stderr,
+            ..ProgramOptions::default()
+        };
+        assert!(matches!(
+            ensure_terminal(&options, |_| false),
+            Err(ProgramError::NotATerminal(OutputTarget::Stderr))
+        ));
+        assert!(ensure_terminal(&options, |_| true).is_ok());
+
+        let lenient = ProgramOptions {
+            require_tty: false,
+            ..ProgramOptions::default()
+        };
+        assert!(ensure_terminal(&lenient, |_| false).is_ok());
+
+        // Construction reports the error before touching the terminal.
+        let result = Program::<Counting>::build(
+            (),
+            ProgramOptions::default(),
+            |_| false,
+            |_| unreachable!("opened a terminal on a pipe"),
+        );
+        assert!(matches!(
+            result,
+            Err(ProgramError::NotATerminal(OutputTarget::Stdout))
+        ));
+    }
+
+    #[test]
+    fn panic_hook_restores_terminal_then_runs_previous_hook() {
+        let calls = std::cell::RefCell::new(Vec::new());
+        run_panic_hook(
+            "boom",
+            || calls.borrow_mut().push("restore".to_string()),
+            |info: &str| calls.borrow_mut().push(info.to_string()),
+        );
+        assert_eq!(calls.into_inner(), ["restore", "boom"]);
+    }
+
+    #[test]
+    fn panic_hook_preserves_and_chains_existing_hook() {
+        // The hook restores ACTIVE_TERMINAL, so keep other tests' terminals
+        // out of it. This is the only test that installs the hook.
+        let _active = ACTIVE_TERMINAL_TESTS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        // Count only this test's panics; others may panic concurrently and
+        // still get the default report.
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        const MARKER: &str = "panic hook test";
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if info.payload().downcast_ref::<&str>() == Some(&MARKER) {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+            } else {
+                default(info);
+            }
+        }));
+        let panic_once = || {
+            let _ = std::panic::catch_unwind(|| std::panic::panic_any(MARKER));
+        };
+
+        // Opted out: the host's hook stays installed.
+        install_panic_hook(false);
+        panic_once();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Opted in: boba's hook runs first, then the host's.
+        install_panic_hook(true);
+        panic_once();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+        drop(std::panic::take_hook());
+    }
+
+    #[test]
+    fn command_task_panic_leaves_terminal_active() {
+        let _active = ACTIVE_TERMINAL_TESTS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        install_panic_hook(true);
+        set_active_terminal(Some((false, OutputTarget::Stdout)));
+
+        let (error_tx, mut error_rx) = mpsc::unbounded_channel();
+        let errors = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async move {
+                let spawned =
+                    spawn_command_task(CancellationToken::new(), error_tx.clone(), async {
+                        panic!("spawned");
+                    });
+                let CommandInner::Future(blocking) =
+                    Command::spawn_blocking(|| panic!("blocking"), |()| ()).inner
+                else {
+                    panic!("expected a future command");
+                };
+                let blocking = spawn_command_task(CancellationToken::new(), error_tx, async move {
+                    blocking.await;
+                });
+                spawned.await.unwrap();
+                blocking.await.unwrap();
+                [error_rx.recv().await, error_rx.recv().await]
+            });
+
+        let still_active = ACTIVE_TERMINAL.lock().unwrap().is_some();
+        set_active_terminal(None);
+        assert!(still_active, "the panic hook tore down the terminal");
+        assert_eq!(
+            errors,
+            [
+                Some(CommandError::Panicked("spawned".to_string())),
+                Some(CommandError::Panicked("blocking".to_string())),
+            ]
+        );
+    }
+}
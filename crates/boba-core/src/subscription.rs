@@ -3,9 +3,12 @@ use futures::StreamExt;
 use std::any::TypeId;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
 use tokio::task::AbortHandle;
 
+use crate::subscriptions::{in_program, with_terminal_state, EventFilter, TerminalState};
+
 /// A long-lived event source managed by the runtime.
 ///
 /// Subscriptions are declared in [`Model::subscriptions`](crate::Model::subscriptions) and automatically
@@ -85,14 +88,14 @@ where
     Subscription {
         id,
         spawn: Box::new(move |tx| {
-            let handle = tokio::spawn(async move {
+            let handle = tokio::spawn(in_program(async move {
                 let mut stream = source.stream();
                 while let Some(msg) = stream.next().await {
                     if tx.send(msg).is_err() {
                         break;
                     }
                 }
-            });
+            }));
             handle.abort_handle()
         }),
     }
@@ -117,12 +120,50 @@ impl<Msg: Send + 'static> Subscription<Msg> {
         }
     }
 
+    /// Replace the subscription's identity, for sources whose own id can't
+    /// tell instances apart (e.g. an [`Every`](crate::subscriptions::Every)
+    /// keyed by a runtime string).
+    pub fn with_id(mut self, id: SubscriptionId) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Transform the message type (for component composition).
     pub fn map<NewMsg: Send + 'static>(
         self,
         f: impl Fn(Msg) -> NewMsg + Send + Sync + 'static,
     ) -> Subscription<NewMsg> {
-        let f = std::sync::Arc::new(f);
+        self.map_shared(Arc::new(f))
+    }
+
+    /// Transform the message type of a whole group of subscriptions with one
+    /// mapper.
+    ///
+    /// Each subscription keeps its own [`SubscriptionId`], so reconciliation
+    /// behaves exactly as if every subscription had been mapped individually.
+    /// This is the subscription counterpart of mapping a batched command.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+    ///     Subscription::map_all(self.child.subscriptions(), Msg::Child)
+    /// }
+    /// ```
+    pub fn map_all<NewMsg: Send + 'static>(
+        subs: impl IntoIterator<Item = Subscription<Msg>>,
+        f: impl Fn(Msg) -> NewMsg + Send + Sync + 'static,
+    ) -> Vec<Subscription<NewMsg>> {
+        let f = Arc::new(f);
+        subs.into_iter()
+            .map(|sub| sub.map_shared(Arc::clone(&f)))
+            .collect()
+    }
+
+    fn map_shared<NewMsg: Send + 'static, F>(self, f: Arc<F>) -> Subscription<NewMsg>
+    where
+        F: Fn(Msg) -> NewMsg + Send + Sync + 'static,
+    {
         Subscription {
             id: self.id,
             spawn: Box::new(move |new_tx: mpsc::UnboundedSender<NewMsg>| {
@@ -149,6 +190,9 @@ impl<Msg: Send + 'static> Subscription<Msg> {
 pub(crate) struct SubscriptionManager<Msg: Send + 'static> {
     active: HashMap<SubscriptionId, AbortHandle>,
     msg_tx: mpsc::UnboundedSender<Msg>,
+    paused: watch::Sender<bool>,
+    /// The program's terminal event filter and focus state.
+    terminal: Arc<TerminalState>,
 }
 
 impl<Msg: Send + 'static> SubscriptionManager<Msg> {
@@ -156,9 +200,54 @@ impl<Msg: Send + 'static> SubscriptionManager<Msg> {
         Self {
             active: HashMap::new(),
             msg_tx,
+            paused: watch::Sender::new(false),
+            terminal: Arc::default(),
         }
     }
 
+    /// Preprocess raw terminal events with `filter`, including those of a
+    /// terminal events subscription that is already running.
+    pub fn set_event_filter(&mut self, filter: EventFilter) {
+        self.terminal.set_filter(filter);
+    }
+
+    /// The program's terminal state, shared with the subscriptions it
+    /// starts.
+    pub fn terminal(&self) -> &TerminalState {
+        &self.terminal
+    }
+
+    /// Start a subscription, routing its output through the pause gate.
+    ///
+    /// Terminal events bypass the gate so the application can still
+    /// receive the input it needs to resume.
+    fn start(&self, sub: Subscription<Msg>) -> AbortHandle {
+        if sub.id == SubscriptionId::of::<crate::subscriptions::TerminalEvents>() {
+            let tx = self.msg_tx.clone();
+            return with_terminal_state(self.terminal.clone(), || (sub.spawn)(tx));
+        }
+
+        let (inner_tx, mut inner_rx) = mpsc::unbounded_channel::<Msg>();
+        let abort = with_terminal_state(self.terminal.clone(), || (sub.spawn)(inner_tx));
+        let msg_tx = self.msg_tx.clone();
+        let paused = self.paused.subscribe();
+
+        tokio::spawn(async move {
+            while let Some(msg) = inner_rx.recv().await {
+                // Values produced while paused are dropped rather than
+                // queued, so timers don't burst on resume.
+                if *paused.borrow() {
+                    continue;
+                }
+                if msg_tx.send(msg).is_err() {
+                    break;
+                }
+            }
+        });
+
+        abort
+    }
+
     /// Diff new subscriptions against active ones.
     /// Start new ones, stop removed ones, keep unchanged ones.
     pub fn reconcile(&mut self, new_subs: Vec<Subscription<Msg>>) {
@@ -184,12 +273,31 @@ impl<Msg: Send + 'static> SubscriptionManager<Msg> {
         // Start subscriptions that are new
         for (id, sub) in new_ids {
             if !self.active.contains_key(&id) {
-                let handle = (sub.spawn)(self.msg_tx.clone());
+                let handle = self.start(sub);
                 self.active.insert(id, handle);
             }
         }
     }
 
+    /// Stop delivering messages from all subscriptions except terminal
+    /// events, without tearing them down. Subscriptions keep their identity
+    /// and resume delivering after [`resume_all`](Self::resume_all).
+    pub fn pause_all(&mut self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resume delivery for subscriptions paused by
+    /// [`pause_all`](Self::pause_all).
+    pub fn resume_all(&mut self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Whether subscription delivery is currently paused (for testing).
+    #[cfg(test)]
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
     /// Abort all active subscriptions.
     pub fn shutdown(&mut self) {
         for (_, handle) in self.active.drain() {
@@ -305,4 +413,96 @@ mod tests {
         manager.shutdown();
         assert_eq!(manager.active_count(), 0);
     }
+
+    #[tokio::test]
+    async fn paused_every_stops_and_resumes() {
+        use crate::subscriptions::Every;
+        use std::time::Duration;
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<std::time::Instant>();
+        let mut manager = SubscriptionManager::new(tx);
+        let every = || subscribe(Every::new(Duration::from_millis(5), "tick"));
+
+        manager.reconcile(vec![every()]);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(rx.try_recv().is_ok());
+
+        manager.pause_all();
+        assert!(manager.is_paused());
+        // Let any tick already past the gate land, then discard it
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        while rx.try_recv().is_ok() {}
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(rx.try_recv().is_err());
+
+        // Reconciling while paused keeps the same subscription alive
+        manager.reconcile(vec![every()]);
+        assert_eq!(manager.active_count(), 1);
+
+        manager.resume_all();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn map_all_preserves_ids_and_maps_every_item() {
+        let subs = vec![
+            Subscription::from_stream(
+                SubscriptionId::new::<String>(1),
+                Box::pin(futures::stream::iter(vec![1, 2])),
+            ),
+            Subscription::from_stream(
+                SubscriptionId::new::<String>(2),
+                Box::pin(futures::stream::iter(vec![10])),
+            ),
+        ];
+
+        let mapped = Subscription::map_all(subs, |n: i32| format!("#{n}"));
+        let ids: Vec<_> = mapped.iter().map(|s| s.id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                SubscriptionId::new::<String>(1),
+                SubscriptionId::new::<String>(2)
+            ]
+        );
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        for sub in mapped {
+            (sub.spawn)(tx.clone());
+        }
+        drop(tx);
+
+        let mut received = Vec::new();
+        while let Some(msg) = rx.recv().await {
+            received.push(msg);
+        }
+        received.sort();
+        assert_eq!(received, vec!["#1", "#10", "#2"]);
+    }
+
+    #[tokio::test]
+    async fn map_all_group_reconciles_like_individual_maps() {
+        let (tx, _rx) = mpsc::unbounded_channel::<String>();
+        let mut manager = SubscriptionManager::new(tx);
+        let group = || {
+            (0..3)
+                .map(|i| {
+                    Subscription::from_stream(
+                        SubscriptionId::new::<i32>(i),
+                        Box::pin(futures::stream::pending::<i32>()),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        manager.reconcile(Subscription::map_all(group(), |n| n.to_string()));
+        assert_eq!(manager.active_count(), 3);
+
+        // Same ids on the next cycle keep the running subscriptions.
+        let mut next = Subscription::map_all(group(), |n| n.to_string());
+        next.pop();
+        manager.reconcile(next);
+        assert_eq!(manager.active_count(), 2);
+    }
 }
@@ -0,0 +1,127 @@
+use super::terminal::current_state;
+use crate::subscription::{subscribe, Subscription, SubscriptionId, SubscriptionSource};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Fires once each time the user has been idle for `window`.
+///
+/// "Idle" means no keyboard, mouse or paste event has arrived through the
+/// [`terminal_events`](super::terminal_events) subscription, so that
+/// subscription must also be active. The timer restarts on every input
+/// event; after firing, the source waits for the next input before arming
+/// again, so a long pause produces a single message.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use boba_core::subscriptions::idle;
+///
+/// fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+///     vec![
+///         terminal_events(|e| Some(Msg::Event(e))),
+///         idle(Duration::from_secs(2)).map(|()| Msg::Autosave),
+///     ]
+/// }
+/// ```
+pub struct Idle {
+    /// How long input must be quiet before the subscription fires.
+    pub window: Duration,
+}
+
+impl Idle {
+    /// Create an idle source with the given quiet window.
+    pub fn new(window: Duration) -> Self {
+        Self { window }
+    }
+}
+
+impl SubscriptionSource for Idle {
+    type Output = ();
+
+    fn id(&self) -> SubscriptionId {
+        SubscriptionId::new::<Self>(self.window.as_nanos() as u64)
+    }
+
+    fn stream(self) -> BoxStream<'static, ()> {
+        match current_state() {
+            Some(state) => idle_stream(state.input_activity(), self.window),
+            // Outside a program there is no input; keep the sender alive so
+            // the stream still fires once instead of ending.
+            None => {
+                let (tx, rx) = watch::channel(0);
+                Box::pin(idle_stream(rx, self.window).inspect(move |()| {
+                    let _ = &tx;
+                }))
+            }
+        }
+    }
+}
+
+/// Create a subscription that emits `()` after `window` without user input.
+///
+/// Shorthand for `subscribe(Idle::new(window))`; see [`Idle`].
+pub fn idle(window: Duration) -> Subscription<()> {
+    subscribe(Idle::new(window))
+}
+
+/// Emit once per quiet period of `window` on `activity`.
+fn idle_stream(activity: watch::Receiver<u64>, window: Duration) -> BoxStream<'static, ()> {
+    let stream =
+        futures::stream::unfold((activity, true), move |(mut activity, armed)| async move {
+            if !armed {
+                // Stay quiet until the next input starts a new window.
+                activity.changed().await.ok()?;
+            }
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(window) => break,
+                    changed = activity.changed() => changed.ok()?,
+                }
+            }
+            Some(((), (activity, false)))
+        });
+    Box::pin(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn fires_once_after_quiet_window() {
+        let (tx, rx) = watch::channel(0u64);
+        let window = Duration::from_millis(60);
+        let (out_tx, mut out) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            let mut stream = idle_stream(rx, window);
+            while stream.next().await.is_some() {
+                if out_tx.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+        // Input right away restarts the initial window.
+        tx.send_modify(|n| *n += 1);
+
+        // Steady input keeps resetting the timer.
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_millis(15)).await;
+            tx.send_modify(|n| *n += 1);
+        }
+        assert!(out.try_recv().is_err(), "fired while input was active");
+
+        // Quiescence: exactly one message.
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(out.try_recv().is_ok());
+        assert!(out.try_recv().is_err(), "fired more than once");
+
+        // New input re-arms the timer.
+        tx.send_modify(|n| *n += 1);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(out.try_recv().is_ok());
+        task.abort();
+    }
+}
@@ -6,9 +6,21 @@
 //!   mouse, resize, focus, and paste events from the terminal.
 //! - **Timers** ([`Every`], [`After`]) -- repeating and one-shot timer
 //!   subscriptions.
+//! - **Idle** ([`idle`]) -- fires after a period without user input.
+//! - **Piped input** ([`stdin_lines`], [`reader_lines`]) -- lines read from
+//!   stdin or any buffered reader.
+//! - **Signals** (`signal`, Unix only) -- a Unix signal such as `SIGUSR1`.
 
+mod idle;
+#[cfg(unix)]
+mod signal;
+mod stdin;
 mod terminal;
 mod timer;
 
+pub use idle::*;
+#[cfg(unix)]
+pub use signal::*;
+pub use stdin::*;
 pub use terminal::*;
 pub use timer::*;
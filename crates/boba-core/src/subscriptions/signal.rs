@@ -0,0 +1,99 @@
+use crate::subscription::{subscribe, Subscription, SubscriptionId, SubscriptionSource};
+use futures::stream::BoxStream;
+pub use tokio::signal::unix::SignalKind;
+
+/// Emits `()` each time the process receives a Unix signal.
+///
+/// Use it for signals the runtime doesn't handle itself, such as `SIGUSR1`
+/// to reload configuration or `SIGWINCH` to react to resizes directly.
+/// Signals that arrive in quick succession may be coalesced into one
+/// message.
+///
+/// Once a signal has been subscribed to, its default action (for `SIGUSR1`,
+/// terminating the process) stays replaced for the life of the process,
+/// even after the subscription is dropped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use boba_core::subscriptions::{signal, SignalKind};
+///
+/// fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+///     vec![signal(SignalKind::user_defined1()).map(|()| Msg::Reload)]
+/// }
+/// ```
+pub struct Signal {
+    /// The signal to listen for.
+    pub kind: SignalKind,
+}
+
+impl Signal {
+    /// Create a source for the given signal.
+    pub fn new(kind: SignalKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl SubscriptionSource for Signal {
+    type Output = ();
+
+    fn id(&self) -> SubscriptionId {
+        SubscriptionId::new::<Self>(self.kind.as_raw_value() as u64)
+    }
+
+    fn stream(self) -> BoxStream<'static, ()> {
+        match tokio::signal::unix::signal(self.kind) {
+            Ok(mut signal) => Box::pin(futures::stream::poll_fn(move |cx| signal.poll_recv(cx))),
+            // The signal can't be handled (e.g. SIGKILL); never emit.
+            Err(_) => Box::pin(futures::stream::pending()),
+        }
+    }
+}
+
+/// Create a subscription that emits `()` whenever the process receives
+/// `kind`.
+///
+/// Shorthand for `subscribe(Signal::new(kind))`; see [`Signal`].
+pub fn signal(kind: SignalKind) -> Subscription<()> {
+    subscribe(Signal::new(kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn raised_signal_is_delivered() {
+        // Keep a listener of our own so raising SIGUSR1 never falls back to
+        // the default action, even before the subscription is running.
+        let _guard = tokio::signal::unix::signal(SignalKind::user_defined1()).unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let abort = (signal(SignalKind::user_defined1()).spawn)(tx);
+
+        // The subscription registers asynchronously; raise until it hears one.
+        let received = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                unsafe {
+                    libc::raise(libc::SIGUSR1);
+                }
+                tokio::select! {
+                    msg = rx.recv() => return msg,
+                    _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                }
+            }
+        })
+        .await;
+        assert_eq!(received.expect("signal should be delivered"), Some(()));
+        abort.abort();
+    }
+
+    #[test]
+    fn id_depends_on_signal() {
+        let usr1 = signal(SignalKind::user_defined1());
+        assert_eq!(usr1.id, signal(SignalKind::user_defined1()).id);
+        assert_ne!(usr1.id, signal(SignalKind::window_change()).id);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::subscription::{Subscription, SubscriptionId};
+use std::io::BufRead;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// Marker type giving line-reader subscriptions their own identity space.
+struct ReaderLines;
+
+/// Deliver each line piped into stdin as a message.
+///
+/// Lines are read on a blocking thread and forwarded without their trailing
+/// newline; the subscription ends at EOF. Keyboard input keeps working
+/// because terminal events are read from `/dev/tty` when stdin is not a
+/// TTY, but the UI should render to stderr so it doesn't mix with data on a
+/// piped stdout -- pair this with
+/// [`OutputTarget::Stderr`](crate::OutputTarget::Stderr):
+///
+/// ```rust,ignore
+/// // cat data.txt | myapp
+/// let options = ProgramOptions {
+///     output: OutputTarget::Stderr,
+///     ..ProgramOptions::default()
+/// };
+///
+/// fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+///     vec![stdin_lines().map(Msg::Line)]
+/// }
+/// ```
+pub fn stdin_lines() -> Subscription<String> {
+    reader_lines("stdin", std::io::BufReader::new(std::io::stdin()))
+}
+
+/// Deliver each line read from `reader` as a message.
+///
+/// This is the general form of [`stdin_lines`]: the reader is drained on a
+/// blocking thread, and `id` distinguishes concurrent readers. Reading stops
+/// at EOF, on the first I/O error, or once the subscription is dropped and
+/// the next line is read.
+pub fn reader_lines<R>(id: &str, reader: R) -> Subscription<String>
+where
+    R: BufRead + Send + 'static,
+{
+    Subscription {
+        id: SubscriptionId::with_str::<ReaderLines>(id),
+        spawn: Box::new(move |tx: mpsc::UnboundedSender<String>| -> AbortHandle {
+            let handle = tokio::task::spawn_blocking(move || {
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            handle.abort_handle()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn each_line_becomes_a_message() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sub = reader_lines("test", Cursor::new(b"alpha\nbeta\r\ngamma".to_vec()));
+        let _abort = (sub.spawn)(tx);
+
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        assert_eq!(lines, ["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn readers_with_different_ids_are_distinct() {
+        let a = reader_lines("a", Cursor::new(Vec::new()));
+        let b = reader_lines("b", Cursor::new(Vec::new()));
+        assert_ne!(a.id, b.id);
+        assert_eq!(a.id, reader_lines("a", Cursor::new(Vec::new())).id);
+    }
+}
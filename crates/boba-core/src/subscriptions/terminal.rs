@@ -1,9 +1,159 @@
 use crate::event::TerminalEvent;
 use crate::subscription::{SubscriptionId, SubscriptionSource};
-use crossterm::event::EventStream;
+use crossterm::event::{Event, EventStream};
 use futures::stream::BoxStream;
-use futures::StreamExt;
-use std::sync::Arc;
+use futures::{Stream, StreamExt};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// A program's raw terminal event preprocessor; see
+/// [`Program::with_event_filter`](crate::Program::with_event_filter).
+pub(crate) type EventFilter = Box<dyn FnMut(TerminalEvent) -> Option<TerminalEvent> + Send>;
+
+/// One program's view of its terminal events, shared between the runtime
+/// and its subscription tasks so that programs never see each other's
+/// state.
+pub(crate) struct TerminalState {
+    /// The program's event filter. A filter installed after the
+    /// subscription started still applies.
+    filter: Mutex<Option<EventFilter>>,
+    /// Set when a focus event arrives, so the runtime can redraw even if
+    /// the model ignores it.
+    focus_changed: AtomicBool,
+    /// Bumped on every keyboard, mouse or paste event so idle
+    /// subscriptions can reset their timers.
+    input_activity: watch::Sender<u64>,
+}
+
+impl Default for TerminalState {
+    fn default() -> Self {
+        Self {
+            filter: Mutex::default(),
+            focus_changed: AtomicBool::default(),
+            input_activity: watch::Sender::new(0),
+        }
+    }
+}
+
+impl TerminalState {
+    /// Preprocess raw terminal events with `filter`.
+    pub(crate) fn set_filter(&self, filter: EventFilter) {
+        *self.filter.lock().unwrap_or_else(PoisonError::into_inner) = Some(filter);
+    }
+
+    /// Record runtime-relevant side information about `event`, then pass
+    /// it through the filter, if there is one.
+    pub(crate) fn preprocess(&self, event: TerminalEvent) -> Option<TerminalEvent> {
+        match event {
+            TerminalEvent::FocusGained | TerminalEvent::FocusLost => {
+                self.focus_changed.store(true, Ordering::SeqCst);
+            }
+            TerminalEvent::Key(_) | TerminalEvent::Mouse(_) | TerminalEvent::Paste(_) => {
+                self.input_activity.send_modify(|n| *n = n.wrapping_add(1));
+            }
+            _ => {}
+        }
+        // A filter that panicked poisons the lock; keep delivering events
+        // rather than taking every later subscription down with it.
+        match self
+            .filter
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            Some(filter) => filter(event),
+            None => Some(event),
+        }
+    }
+
+    /// Return whether focus changed since the last call, clearing the flag.
+    pub(crate) fn take_focus_change(&self) -> bool {
+        self.focus_changed.swap(false, Ordering::SeqCst)
+    }
+
+    /// Subscribe to user input activity seen by the terminal events source.
+    pub(crate) fn input_activity(&self) -> watch::Receiver<u64> {
+        self.input_activity.subscribe()
+    }
+}
+
+tokio::task_local! {
+    /// The state of the program whose subscriptions are being started.
+    static TERMINAL_STATE: Arc<TerminalState>;
+}
+
+/// Run `f`, which starts subscriptions, on behalf of the program owning
+/// `state`.
+pub(crate) fn with_terminal_state<R>(state: Arc<TerminalState>, f: impl FnOnce() -> R) -> R {
+    TERMINAL_STATE.sync_scope(state, f)
+}
+
+/// The state of the program starting subscriptions, if any.
+pub(crate) fn current_state() -> Option<Arc<TerminalState>> {
+    TERMINAL_STATE.try_with(Arc::clone).ok()
+}
+
+/// Run `fut` on behalf of the program starting it, so a subscription
+/// source can find that program's state when its task starts.
+pub(crate) fn in_program<F: std::future::Future>(
+    fut: F,
+) -> impl std::future::Future<Output = F::Output> {
+    let state = current_state();
+    async move {
+        match state {
+            Some(state) => TERMINAL_STATE.scope(state, fut).await,
+            None => fut.await,
+        }
+    }
+}
+
+/// Pass `event` through `state`, or unchanged without a program.
+fn preprocess(state: Option<&TerminalState>, event: TerminalEvent) -> Option<TerminalEvent> {
+    match state {
+        Some(state) => state.preprocess(event),
+        None => Some(event),
+    }
+}
+
+/// How long to wait for the next chunk of a split paste.
+const PASTE_GAP: Duration = Duration::from_millis(10);
+
+/// Merge paste events that arrive back to back into one.
+///
+/// crossterm turns a bracketed paste into a single [`Event::Paste`] once it
+/// has read the end marker, but some terminals split a large paste into
+/// several bracketed pastes. Each paste event is extended with any further
+/// paste events arriving within [`PASTE_GAP`], so a component sees the whole
+/// text in one message. The first other event ends the paste and is passed
+/// on unchanged.
+///
+/// The wait is a real timeout rather than a non-blocking peek: crossterm's
+/// `EventStream` keeps the first waker it is polled with, so polling it with
+/// a no-op waker would leave the task asleep.
+fn coalesce_pastes<S>(events: S) -> impl Stream<Item = std::io::Result<Event>>
+where
+    S: Stream<Item = std::io::Result<Event>> + Send + 'static,
+{
+    let events = Box::pin(events.fuse());
+    futures::stream::unfold((events, None), |(mut events, held)| async move {
+        let next = match held {
+            Some(event) => event,
+            None => events.next().await?,
+        };
+        let Ok(Event::Paste(mut text)) = next else {
+            return Some((next, (events, None)));
+        };
+        loop {
+            match tokio::time::timeout(PASTE_GAP, events.next()).await {
+                Ok(Some(Ok(Event::Paste(more)))) => text.push_str(&more),
+                Ok(Some(other)) => return Some((Ok(Event::Paste(text)), (events, Some(other)))),
+                Ok(None) | Err(_) => return Some((Ok(Event::Paste(text)), (events, None))),
+            }
+        }
+    })
+}
 
 /// Subscription source for terminal events (keyboard, mouse, resize, focus, paste).
 ///
@@ -26,11 +176,12 @@ impl SubscriptionSource for TerminalEvents {
     }
 
     fn stream(self) -> BoxStream<'static, TerminalEvent> {
-        let stream = EventStream::new().filter_map(|result| async move {
-            match result {
-                Ok(event) => Some(TerminalEvent::from(event)),
-                Err(_) => None,
-            }
+        let state = current_state();
+        let stream = coalesce_pastes(EventStream::new()).filter_map(move |result| {
+            let event = result
+                .ok()
+                .and_then(|event| preprocess(state.as_deref(), TerminalEvent::from(event)));
+            async move { event }
         });
         Box::pin(stream)
     }
@@ -48,14 +199,33 @@ impl SubscriptionSource for TerminalEvents {
 /// fn subscriptions(&self) -> Vec<Subscription<Msg>> {
 ///     vec![terminal_events(|event| match event {
 ///         TerminalEvent::Key(key) => Some(Msg::KeyPress(key)),
+///         TerminalEvent::Mouse(mouse) => Some(Msg::Mouse(mouse)),
 ///         TerminalEvent::Resize(w, h) => Some(Msg::Resize(w, h)),
 ///         _ => None,
 ///     })]
 /// }
 /// ```
+///
+/// Mouse events are only produced while mouse capture is enabled; see
+/// [`TerminalEvent::Mouse`].
 pub fn terminal_events<Msg: Send + 'static>(
     map: impl Fn(TerminalEvent) -> Option<Msg> + Send + Sync + 'static,
 ) -> crate::subscription::Subscription<Msg> {
+    terminal_events_from(EventStream::new, map)
+}
+
+/// Build the terminal events subscription over an arbitrary event source.
+///
+/// `make_stream` is called inside the spawned task so the source is only
+/// created once the subscription actually starts.
+fn terminal_events_from<Msg, S>(
+    make_stream: impl FnOnce() -> S + Send + 'static,
+    map: impl Fn(TerminalEvent) -> Option<Msg> + Send + Sync + 'static,
+) -> crate::subscription::Subscription<Msg>
+where
+    Msg: Send + 'static,
+    S: Stream<Item = std::io::Result<Event>> + Send + 'static,
+{
     use crate::subscription::Subscription;
     use tokio::sync::mpsc;
     use tokio::task::AbortHandle;
@@ -70,15 +240,15 @@ pub fn terminal_events<Msg: Send + 'static>(
     Subscription {
         id,
         spawn: Box::new(move |tx: mpsc::UnboundedSender<Msg>| -> AbortHandle {
+            // Started by the subscription manager inside the program's scope.
+            let state = current_state();
             let handle = tokio::spawn(async move {
-                let stream = EventStream::new().filter_map(move |result| {
+                let stream = coalesce_pastes(make_stream()).filter_map(move |result| {
                     let map = map.clone();
-                    async move {
-                        match result {
-                            Ok(event) => map(TerminalEvent::from(event)),
-                            Err(_) => None,
-                        }
-                    }
+                    let event = result
+                        .ok()
+                        .and_then(|event| preprocess(state.as_deref(), TerminalEvent::from(event)));
+                    async move { map(event?) }
                 });
                 futures::pin_mut!(stream);
                 while let Some(msg) = stream.next().await {
@@ -91,3 +261,213 @@ pub fn terminal_events<Msg: Send + 'static>(
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn mouse_click_is_delivered_through_subscription() {
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 7,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        };
+        let sub = terminal_events_from(
+            move || futures::stream::iter([Ok(Event::Mouse(click))]),
+            |event| match event {
+                TerminalEvent::Mouse(mouse) => Some(mouse),
+                _ => None,
+            },
+        );
+        assert_eq!(sub.id, SubscriptionId::of::<TerminalEvents>());
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _handle = (sub.spawn)(tx);
+        assert_eq!(rx.recv().await, Some(click));
+    }
+
+    #[tokio::test]
+    async fn chunked_paste_arrives_as_one_message() {
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let events = [
+            Ok(Event::Paste("first half, ".into())),
+            Ok(Event::Paste("second half\n".into())),
+            Ok(Event::Paste("and the end".into())),
+            Ok(Event::Key(KeyEvent::from(KeyCode::Enter))),
+            Ok(Event::Paste("later".into())),
+        ];
+        let sub = terminal_events_from(move || futures::stream::iter(events), Some);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _handle = (sub.spawn)(tx);
+        assert_eq!(
+            rx.recv().await,
+            Some(TerminalEvent::Paste(
+                "first half, second half\nand the end".into()
+            ))
+        );
+        assert!(matches!(rx.recv().await, Some(TerminalEvent::Key(_))));
+        assert_eq!(rx.recv().await, Some(TerminalEvent::Paste("later".into())));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    /// Yields each event after its delay and, like crossterm's
+    /// `EventStream`, only remembers the waker it was first polled with
+    /// while pending.
+    struct SlowEvents {
+        events: std::collections::VecDeque<(Duration, Event)>,
+        ready: Arc<AtomicBool>,
+        waiting: bool,
+    }
+
+    impl Stream for SlowEvents {
+        type Item = std::io::Result<Event>;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<Self::Item>> {
+            let Some(&(delay, _)) = self.events.front() else {
+                return std::task::Poll::Ready(None);
+            };
+            if delay.is_zero() || self.ready.swap(false, Ordering::SeqCst) {
+                self.waiting = false;
+                return std::task::Poll::Ready(self.events.pop_front().map(|(_, e)| Ok(e)));
+            }
+            if !self.waiting {
+                self.waiting = true;
+                let (ready, waker) = (self.ready.clone(), cx.waker().clone());
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    ready.store(true, Ordering::SeqCst);
+                    waker.wake();
+                });
+            }
+            std::task::Poll::Pending
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn paste_waits_for_chunks_that_are_not_ready_yet() {
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let events = SlowEvents {
+            events: [
+                (Duration::ZERO, Event::Paste("first half, ".into())),
+                (PASTE_GAP / 2, Event::Paste("second half".into())),
+                (PASTE_GAP * 5, Event::Key(KeyEvent::from(KeyCode::Enter))),
+            ]
+            .into(),
+            ready: Arc::default(),
+            waiting: false,
+        };
+        let mut stream = std::pin::pin!(coalesce_pastes(events));
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(Event::Paste(text))) if text == "first half, second half"
+        ));
+        // The key arrives after the paste gap; the stream must still wake.
+        let key = tokio::time::timeout(Duration::from_secs(1), stream.next()).await;
+        assert!(matches!(key, Ok(Some(Ok(Event::Key(_))))));
+    }
+
+    #[tokio::test]
+    async fn event_filter_consumes_keys_before_they_become_messages() {
+        use crate::subscription::SubscriptionManager;
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let keys = ['a', ' ', 'b'].map(|c| Ok(Event::Key(KeyEvent::from(KeyCode::Char(c)))));
+        let sub = terminal_events_from(
+            move || futures::stream::iter(keys),
+            |event| match event {
+                TerminalEvent::Key(key) => Some(key.code),
+                _ => None,
+            },
+        );
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut manager = SubscriptionManager::new(tx);
+        // A leader key: swallow Space and upper-case the key after it.
+        let mut leader = false;
+        manager.set_event_filter(Box::new(move |event| match event {
+            TerminalEvent::Key(key) if key.code == KeyCode::Char(' ') => {
+                leader = true;
+                None
+            }
+            TerminalEvent::Key(mut key) if std::mem::take(&mut leader) => {
+                if let KeyCode::Char(c) = key.code {
+                    key.code = KeyCode::Char(c.to_ascii_uppercase());
+                }
+                Some(TerminalEvent::Key(key))
+            }
+            event => Some(event),
+        }));
+        manager.reconcile(vec![sub]);
+
+        assert_eq!(rx.recv().await, Some(KeyCode::Char('a')));
+        assert_eq!(rx.recv().await, Some(KeyCode::Char('B')));
+        manager.shutdown();
+    }
+
+    #[tokio::test]
+    async fn focus_changes_stay_with_their_program() {
+        use crate::subscription::SubscriptionManager;
+
+        let focus =
+            || terminal_events_from(|| futures::stream::iter([Ok(Event::FocusGained)]), Some);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut focused = SubscriptionManager::new(tx);
+        let (other_tx, _other_rx) = mpsc::unbounded_channel::<TerminalEvent>();
+        let other = SubscriptionManager::new(other_tx);
+
+        focused.reconcile(vec![focus()]);
+        assert_eq!(rx.recv().await, Some(TerminalEvent::FocusGained));
+        assert!(!other.terminal().take_focus_change());
+        assert!(focused.terminal().take_focus_change());
+        focused.shutdown();
+    }
+
+    #[test]
+    fn input_activity_stays_with_its_program() {
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let typed = TerminalState::default();
+        let other = TerminalState::default();
+        let mut typed_rx = typed.input_activity();
+        let other_rx = other.input_activity();
+
+        typed.preprocess(TerminalEvent::Key(KeyEvent::from(KeyCode::Char('a'))));
+        assert!(typed_rx.has_changed().unwrap());
+        assert!(!other_rx.has_changed().unwrap());
+        typed_rx.mark_unchanged();
+
+        // Resizes aren't user input.
+        typed.preprocess(TerminalEvent::Resize(80, 24));
+        assert!(!typed_rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn panicking_filter_does_not_poison_later_events() {
+        let state = TerminalState::default();
+        state.set_filter(Box::new(|event| match event {
+            TerminalEvent::FocusLost => panic!("filter bug"),
+            event => Some(event),
+        }));
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            state.preprocess(TerminalEvent::FocusLost)
+        }));
+        assert!(panicked.is_err());
+
+        assert_eq!(
+            state.preprocess(TerminalEvent::Resize(80, 24)),
+            Some(TerminalEvent::Resize(80, 24))
+        );
+        state.set_filter(Box::new(|_| None));
+        assert_eq!(state.preprocess(TerminalEvent::Resize(80, 24)), None);
+    }
+}
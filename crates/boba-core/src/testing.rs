@@ -1,8 +1,14 @@
 use crate::command::{Action, Command, CommandInner};
 use crate::model::Model;
 use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
+use ratatui::layout::{Rect, Size};
 use ratatui::Terminal;
+#[cfg(any(test, feature = "test-util"))]
+use {
+    crate::subscription::{Subscription, SubscriptionId, SubscriptionManager},
+    std::time::Duration,
+    tokio::sync::mpsc,
+};
 
 /// A headless test harness that drives a [`Model`] without a real terminal.
 ///
@@ -10,7 +16,8 @@ use ratatui::Terminal;
 /// a plain `#[test]` function -- no tokio runtime or TTY required.  Synchronous
 /// commands (e.g. [`Command::message`]) are collected and can be flushed with
 /// [`drain_messages`](TestProgram::drain_messages); async commands and terminal
-/// commands are silently ignored.
+/// commands are silently ignored. [`Command::capture_frame`] renders the model
+/// at 80x24 and queues its message like any other.
 ///
 /// # Example
 ///
@@ -25,9 +32,62 @@ use ratatui::Terminal;
 /// let output = prog.render_string(40, 1);          // render to string
 /// assert!(output.contains("Count: 2"));
 /// ```
+///
+/// # Virtual time
+///
+/// With the `test-util` feature, [`advance`](TestProgram::advance) runs the
+/// model's subscriptions against a paused clock, so timer-driven behavior
+/// ([`Every`](crate::subscriptions::Every), [`After`](crate::subscriptions::After),
+/// or anything built on `tokio::time`) can be tested without sleeping.
 pub struct TestProgram<M: Model> {
     model: M,
     pending_messages: Vec<M::Message>,
+    #[cfg(any(test, feature = "test-util"))]
+    virtual_time: Option<VirtualTime<M::Message>>,
+}
+
+/// Size [`Command::capture_frame`] renders at, matching a default terminal.
+const CAPTURE_SIZE: Size = Size::new(80, 24);
+
+/// Paused-clock runtime that drives subscriptions for
+/// [`TestProgram::advance`].
+#[cfg(any(test, feature = "test-util"))]
+struct VirtualTime<Msg: Send + 'static> {
+    runtime: tokio::runtime::Runtime,
+    subscriptions: SubscriptionManager<Msg>,
+    rx: mpsc::UnboundedReceiver<Msg>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl<Msg: Send + 'static> VirtualTime<Msg> {
+    fn new() -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("failed to build virtual-time runtime");
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            runtime,
+            subscriptions: SubscriptionManager::new(tx),
+            rx,
+        }
+    }
+
+    fn reconcile(&mut self, subs: Vec<Subscription<Msg>>) {
+        let _guard = self.runtime.enter();
+        self.subscriptions.reconcile(without_terminal_events(subs));
+    }
+}
+
+/// Drop terminal event subscriptions: tests feed input through
+/// [`TestProgram::send`] instead.
+#[cfg(any(test, feature = "test-util"))]
+fn without_terminal_events<Msg: Send + 'static>(
+    subs: Vec<Subscription<Msg>>,
+) -> Vec<Subscription<Msg>> {
+    let terminal = SubscriptionId::of::<crate::subscriptions::TerminalEvents>();
+    subs.into_iter().filter(|s| s.id != terminal).collect()
 }
 
 impl<M: Model> TestProgram<M> {
@@ -41,6 +101,8 @@ impl<M: Model> TestProgram<M> {
         let mut program = Self {
             model,
             pending_messages: Vec::new(),
+            #[cfg(any(test, feature = "test-util"))]
+            virtual_time: None,
         };
         program.collect_sync_messages(init_cmd);
         program
@@ -54,6 +116,83 @@ impl<M: Model> TestProgram<M> {
     pub fn send(&mut self, msg: M::Message) {
         let cmd = self.model.update(msg);
         self.collect_sync_messages(cmd);
+        #[cfg(any(test, feature = "test-util"))]
+        if let Some(vt) = self.virtual_time.as_mut() {
+            vt.reconcile(self.model.subscriptions());
+        }
+    }
+
+    /// Advance virtual time by `duration`, delivering subscription messages
+    /// as their timers come due.
+    ///
+    /// The first call starts the model's subscriptions on a paused tokio
+    /// clock; from then on they stay in sync with
+    /// [`Model::subscriptions`] after every update. Each message is passed
+    /// to [`Model::update`] at its virtual instant, so an update that drops a
+    /// timer stops it immediately. Synchronous commands returned along the
+    /// way are queued as with [`send`](TestProgram::send). Terminal event
+    /// subscriptions are not started.
+    ///
+    /// Note that tokio intervals tick once immediately, so
+    /// `Every::new(100ms)` yields ticks at 0, 100, 200, ... ms.
+    ///
+    /// The clock lives on a private runtime that `advance` blocks on, so call
+    /// it from a plain `#[test]`; it panics inside an async runtime such as
+    /// `#[tokio::test]`.
+    ///
+    /// ```rust,ignore
+    /// let mut prog = TestProgram::<Clock>::new(());
+    /// prog.advance(Duration::from_millis(950));
+    /// assert_eq!(prog.model().ticks, 10);
+    /// ```
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn advance(&mut self, duration: Duration) {
+        assert!(
+            tokio::runtime::Handle::try_current().is_err(),
+            "TestProgram::advance runs its own runtime; call it from a plain #[test], \
+             not from inside an async runtime"
+        );
+        let vt = self.virtual_time.get_or_insert_with(VirtualTime::new);
+        vt.reconcile(self.model.subscriptions());
+
+        let model = &mut self.model;
+        let pending = &mut self.pending_messages;
+        let VirtualTime {
+            runtime,
+            subscriptions,
+            rx,
+        } = vt;
+        let mut deliver = |msg| {
+            let cmd = model.update(msg);
+            Self::collect_into(model, pending, cmd);
+            subscriptions.reconcile(without_terminal_events(model.subscriptions()));
+        };
+        runtime.block_on(async {
+            let deadline = tokio::time::sleep(duration);
+            tokio::pin!(deadline);
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(msg) = rx.recv() => deliver(msg),
+                    _ = &mut deadline => break,
+                }
+            }
+            // Timers due exactly at the deadline may still be in flight, and
+            // delivering their messages can start subscriptions that are
+            // ready at once. Let the other tasks run until a pass yields
+            // nothing new.
+            loop {
+                tokio::task::yield_now().await;
+                let mut quiet = true;
+                while let Ok(msg) = rx.try_recv() {
+                    deliver(msg);
+                    quiet = false;
+                }
+                if quiet {
+                    break;
+                }
+            }
+        });
     }
 
     /// Process all pending synchronous messages produced by [`Command::message`].
@@ -122,25 +261,32 @@ impl<M: Model> TestProgram<M> {
     }
 
     fn collect_sync_messages(&mut self, cmd: Command<M::Message>) {
+        Self::collect_into(&self.model, &mut self.pending_messages, cmd);
+    }
+
+    fn collect_into(model: &M, pending: &mut Vec<M::Message>, cmd: Command<M::Message>) {
         match cmd.inner {
             CommandInner::None => {}
             CommandInner::Action(Action::Message(msg)) => {
-                self.pending_messages.push(msg);
+                pending.push(msg);
             }
-            CommandInner::Action(Action::Quit) => {}
-            CommandInner::Batch(cmds) => {
+            CommandInner::Action(Action::Quit) | CommandInner::Action(Action::Redraw) => {}
+            CommandInner::Batch(cmds) | CommandInner::Sequence(cmds) => {
                 for cmd in cmds {
-                    self.collect_sync_messages(cmd);
-                }
-            }
-            CommandInner::Sequence(cmds) => {
-                for cmd in cmds {
-                    self.collect_sync_messages(cmd);
+                    Self::collect_into(model, pending, cmd);
                 }
             }
             // Async commands can't be executed synchronously in tests
             CommandInner::Future(_) | CommandInner::Stream(_) => {}
-            CommandInner::Terminal(_) => {}
+            CommandInner::Task { .. } | CommandInner::Cancel(_) => {}
+            CommandInner::CaptureFrame(on_captured) => {
+                pending.push(on_captured(crate::capture::capture(
+                    model,
+                    None,
+                    CAPTURE_SIZE,
+                )));
+            }
+            CommandInner::Terminal(_) | CommandInner::WithTerminal(_) => {}
             CommandInner::Exec { .. } => {}
         }
     }
@@ -296,4 +442,139 @@ mod tests {
         prog.drain_messages();
         assert_eq!(prog.model().steps, vec!["started", "auto"]);
     }
+
+    // Captures its own screen and records what it saw
+    struct Snapshot {
+        seen: Option<String>,
+    }
+
+    #[derive(Debug)]
+    enum SnapshotMsg {
+        Capture,
+        Captured(String),
+    }
+
+    impl Model for Snapshot {
+        type Message = SnapshotMsg;
+        type Flags = ();
+
+        fn init(_: ()) -> (Self, Command<SnapshotMsg>) {
+            (Snapshot { seen: None }, Command::none())
+        }
+
+        fn update(&mut self, msg: SnapshotMsg) -> Command<SnapshotMsg> {
+            match msg {
+                SnapshotMsg::Capture => {
+                    Command::capture_frame(|frame| SnapshotMsg::Captured(frame.text()))
+                }
+                SnapshotMsg::Captured(text) => {
+                    self.seen = Some(text);
+                    Command::none()
+                }
+            }
+        }
+
+        fn view(&self, frame: &mut ratatui::Frame) {
+            let text = if self.seen.is_some() {
+                "again"
+            } else {
+                "first"
+            };
+            frame.render_widget(Paragraph::new(text), frame.area());
+        }
+    }
+
+    #[test]
+    fn capture_frame_delivers_the_rendered_screen() {
+        let mut prog = TestProgram::<Snapshot>::new(());
+        prog.send(SnapshotMsg::Capture);
+        assert_eq!(prog.model().seen, None);
+        prog.drain_messages();
+        assert_eq!(prog.model().seen.as_deref(), Some("first"));
+    }
+
+    struct Ticker {
+        ticks: usize,
+        limit: usize,
+        fired: bool,
+    }
+
+    #[derive(Debug)]
+    enum TickMsg {
+        Tick,
+        Fired,
+    }
+
+    impl Model for Ticker {
+        type Message = TickMsg;
+        type Flags = usize;
+
+        fn init(limit: usize) -> (Self, Command<TickMsg>) {
+            (
+                Ticker {
+                    ticks: 0,
+                    limit,
+                    fired: false,
+                },
+                Command::none(),
+            )
+        }
+
+        fn update(&mut self, msg: TickMsg) -> Command<TickMsg> {
+            match msg {
+                TickMsg::Tick => self.ticks += 1,
+                TickMsg::Fired => self.fired = true,
+            }
+            Command::none()
+        }
+
+        fn view(&self, _frame: &mut ratatui::Frame) {}
+
+        fn subscriptions(&self) -> Vec<Subscription<TickMsg>> {
+            use crate::subscription::subscribe;
+            use crate::subscriptions::{After, Every};
+            let mut subs =
+                vec![subscribe(After::new(Duration::from_secs(2))).map(|_| TickMsg::Fired)];
+            if self.ticks < self.limit {
+                subs.push(
+                    subscribe(Every::new(Duration::from_millis(100), "tick"))
+                        .map(|_| TickMsg::Tick),
+                );
+            }
+            subs
+        }
+    }
+
+    #[test]
+    fn advance_drives_every_ticks_in_virtual_time() {
+        let mut prog = TestProgram::<Ticker>::new(usize::MAX);
+        let start = std::time::Instant::now();
+
+        // Ticks at 0, 100, ..., 900 ms.
+        prog.advance(Duration::from_millis(950));
+        assert_eq!(prog.model().ticks, 10);
+        assert!(!prog.model().fired);
+
+        // Ticks at 1000, ..., 1400 ms.
+        prog.advance(Duration::from_millis(500));
+        assert_eq!(prog.model().ticks, 15);
+
+        prog.advance(Duration::from_millis(600));
+        assert!(prog.model().fired);
+        assert!(start.elapsed() < Duration::from_secs(1), "used real time");
+    }
+
+    #[test]
+    fn advance_stops_timers_dropped_by_update() {
+        let mut prog = TestProgram::<Ticker>::new(3);
+        prog.advance(Duration::from_secs(1));
+        assert_eq!(prog.model().ticks, 3);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "plain #[test]")]
+    async fn advance_rejects_an_enclosing_runtime() {
+        let mut prog = TestProgram::<Ticker>::new(3);
+        prog.advance(Duration::from_secs(1));
+    }
 }
@@ -1,5 +1,6 @@
 //! Convenience helpers for common widget chrome patterns.
 
+use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders};
 
@@ -18,3 +19,14 @@ pub fn focus_block(title: &str, focused: bool) -> Block<'_> {
         .title(title)
         .border_style(Style::default().fg(color))
 }
+
+/// Return the `(width, height)` consumed by a block's borders, padding and
+/// titles, or `(0, 0)` when there is no block.
+pub(crate) fn block_chrome(block: Option<&Block<'_>>) -> (u16, u16) {
+    let Some(block) = block else {
+        return (0, 0);
+    };
+    let probe = Rect::new(0, 0, 100, 100);
+    let inner = block.inner(probe);
+    (probe.width - inner.width, probe.height - inner.height)
+}
@@ -0,0 +1,72 @@
+//! Monotonic clock abstraction shared by the time-based widgets.
+//!
+//! [`Stopwatch`](crate::stopwatch::Stopwatch) and [`Timer`](crate::timer::Timer)
+//! measure time through a [`Clock`] rather than reading [`Instant::now`]
+//! directly. Production code uses [`SystemClock`]; tests can inject a
+//! [`ManualClock`] and advance it explicitly instead of sleeping.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of monotonic time.
+pub trait Clock: Send + Sync {
+    /// Return the current instant.
+    fn now(&self) -> Instant;
+}
+
+/// The real monotonic clock, backed by [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same underlying time, so a test can keep one handle
+/// and pass another to a widget via `with_clock`.
+///
+/// # Example
+///
+/// ```rust
+/// use boba_widgets::clock::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_secs(2));
+/// assert_eq!(clock.now() - start, Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Create a manual clock starting at the current instant.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward by `d`.
+    pub fn advance(&self, d: Duration) {
+        *self.now.lock().unwrap() += d;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
@@ -1,17 +1,25 @@
 //! Standalone dropdown overlay for displaying a list of selectable items.
 //!
-//! Items are managed externally — there is no built-in text input or
-//! filtering. It renders as a bordered overlay anchored above or below a
-//! given area. See the `autocomplete` example for composing this with a
+//! Items are managed externally and there is no built-in text input. It
+//! renders as a bordered overlay anchored above or below a given area. See
+//! the `autocomplete` example for composing this with a
 //! [`TextInput`](crate::text_input::TextInput).
+//!
+//! With [`Dropdown::with_filter`], typing narrows the items the way a
+//! [`List`](crate::list::List) filter does and highlights the matched text.
+//!
+//! Short items such as colors or emoji can be laid out in a grid with
+//! [`Dropdown::with_columns`]; arrow keys then move in two dimensions.
 
+use crate::matcher;
 use crate::selection::SelectionState;
 use boba_core::command::Command;
 use boba_core::component::Component;
-use crossterm::event::{KeyCode, KeyEvent};
+use boba_core::impl_terminal_message;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Clear, Paragraph};
 use ratatui::Frame;
 
@@ -32,6 +40,10 @@ pub struct DropdownStyle {
     pub item: Style,
     /// Style for the currently selected item.
     pub selected_item: Style,
+    /// Style for the more-items indicators shown when the list scrolls.
+    pub indicator: Style,
+    /// Style patched over the text matching the filter.
+    pub matched: Style,
 }
 
 impl Default for DropdownStyle {
@@ -41,6 +53,10 @@ impl Default for DropdownStyle {
             selected_item: Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
+            indicator: Style::default().fg(Color::DarkGray),
+            matched: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::UNDERLINED),
         }
     }
 }
@@ -56,6 +72,10 @@ pub enum Message {
     Dismissed,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// A standalone dropdown overlay for displaying selectable items.
 ///
 /// # Example
@@ -77,6 +97,14 @@ pub struct Dropdown {
     position: Position,
     visible: bool,
     block: Option<Block<'static>>,
+    /// Number of grid columns; 1 is a plain list.
+    columns: usize,
+    /// First visible grid row when `columns > 1`.
+    row_offset: usize,
+    filterable: bool,
+    query: String,
+    /// Indices of the items matching `query`, with the matched positions.
+    shown: Vec<(usize, Vec<usize>)>,
 }
 
 impl Dropdown {
@@ -91,6 +119,11 @@ impl Dropdown {
             position: Position::default(),
             visible: false,
             block: None,
+            columns: 1,
+            row_offset: 0,
+            filterable: false,
+            query: String::new(),
+            shown: Vec::new(),
         }
     }
 
@@ -100,13 +133,38 @@ impl Dropdown {
         self
     }
 
-    /// Set the maximum number of visible items before scrolling.
+    /// Set the maximum number of visible items before scrolling (default: 8).
+    ///
+    /// The overlay never grows taller than this many rows (plus borders).
+    /// When there are more items, the list scrolls with the selection and
+    /// `▲`/`▼` indicators mark hidden items above and below.
     pub fn with_max_visible(mut self, max: usize) -> Self {
         self.max_visible = max.max(1);
         self.selection.set_visible(self.max_visible);
         self
     }
 
+    /// Lay items out in a grid of `columns` columns (default: 1).
+    ///
+    /// Items flow left to right, then top to bottom. Left/Right move within
+    /// a row and Up/Down move to the same column in the adjacent row; all
+    /// four stop at the grid's edges. `max_visible` then counts rows.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Filter the items as the user types (default: false).
+    ///
+    /// Printable keys extend the filter and Backspace shortens it. Only
+    /// items containing the filter, ignoring case, are shown, with the
+    /// match highlighted; indices in messages still refer to
+    /// [`items`](Self::items).
+    pub fn with_filter(mut self, enabled: bool) -> Self {
+        self.filterable = enabled;
+        self
+    }
+
     /// Set the style configuration.
     pub fn with_style(mut self, style: DropdownStyle) -> Self {
         self.style = style;
@@ -125,12 +183,38 @@ impl Dropdown {
         self
     }
 
-    /// Set the items and show the dropdown. Resets selection and scroll.
+    /// Set the items and show the dropdown. Resets selection, scroll and
+    /// filter.
     pub fn set_items(&mut self, items: Vec<String>) {
         self.visible = !items.is_empty();
         self.items = items;
-        self.selection.set_count(self.items.len());
+        self.set_filter("");
+    }
+
+    /// The current filter text.
+    pub fn filter(&self) -> &str {
+        &self.query
+    }
+
+    /// Show only the items containing `query`, ignoring case. Resets
+    /// selection and scroll.
+    pub fn set_filter(&mut self, query: &str) {
+        self.query = query.to_string();
+        self.shown = matcher::filter(self.items.iter().map(String::as_str), query);
+        self.selection.set_count(self.shown.len());
         self.selection.select(0);
+        self.row_offset = 0;
+    }
+
+    /// Positions (in chars) of the filter's match within item `index`, or
+    /// `None` if the filter hides it.
+    pub fn filter_match(&self, index: usize) -> Option<Vec<usize>> {
+        matcher::find(self.items.get(index)?, &self.query)
+    }
+
+    /// Indices of the items the filter shows, in order.
+    pub fn shown_indices(&self) -> Vec<usize> {
+        self.shown.iter().map(|(i, _)| *i).collect()
     }
 
     /// Set the title (mutable variant).
@@ -158,27 +242,155 @@ impl Dropdown {
         &self.items
     }
 
-    /// Get the currently selected index.
+    /// Get the index in [`items`](Self::items) of the selected item.
     pub fn selected_index(&self) -> usize {
-        self.selection.cursor()
+        self.shown
+            .get(self.selection.cursor())
+            .map_or(0, |(i, _)| *i)
     }
 
     /// Get the currently selected item value.
     pub fn selected_value(&self) -> Option<&str> {
-        self.items.get(self.selection.cursor()).map(|s| s.as_str())
+        let (i, _) = self.shown.get(self.selection.cursor())?;
+        Some(self.items[*i].as_str())
     }
 
-    /// Set the selected index programmatically.
+    /// Select item `index` of [`items`](Self::items). Does nothing if the
+    /// filter hides it.
     pub fn set_selected(&mut self, index: usize) {
-        self.selection.select(index);
+        if let Some(pos) = self.shown.iter().position(|(i, _)| *i == index) {
+            self.selection.select(pos);
+            self.scroll_grid();
+        }
     }
 
     fn select_next(&mut self) {
         self.selection.move_down();
+        self.scroll_grid();
     }
 
     fn select_prev(&mut self) {
         self.selection.move_up();
+        self.scroll_grid();
+    }
+
+    fn is_grid(&self) -> bool {
+        self.columns > 1
+    }
+
+    /// Move the grid cursor by whole rows or single cells, stopping at the
+    /// edges. Moving down onto a short last row lands on its last item.
+    fn move_in_grid(&mut self, code: KeyCode) {
+        let cursor = self.selection.cursor();
+        let (row, col) = (cursor / self.columns, cursor % self.columns);
+        let last = self.shown.len().saturating_sub(1);
+        let target = match code {
+            KeyCode::Left if col > 0 => cursor - 1,
+            KeyCode::Right if col + 1 < self.columns => (cursor + 1).min(last),
+            KeyCode::Up if row > 0 => cursor - self.columns,
+            KeyCode::Down if row < last / self.columns => (cursor + self.columns).min(last),
+            _ => cursor,
+        };
+        self.selection.select(target);
+        self.scroll_grid();
+    }
+
+    /// Keep the cursor's grid row inside the visible rows.
+    fn scroll_grid(&mut self) {
+        let row = self.selection.cursor() / self.columns;
+        if row < self.row_offset {
+            self.row_offset = row;
+        } else if row >= self.row_offset + self.max_visible {
+            self.row_offset = row + 1 - self.max_visible;
+        }
+    }
+
+    /// Render the items as a grid inside `inner`.
+    fn view_grid(&self, frame: &mut Frame, inner: Rect) {
+        let total_rows = self.shown.len().div_ceil(self.columns);
+        let rows = (inner.height as usize).min(total_rows.min(self.max_visible));
+        let cursor = self.selection.cursor();
+        let cursor_row = cursor / self.columns;
+        let mut offset = self.row_offset;
+        if cursor_row >= offset + rows {
+            offset = cursor_row + 1 - rows;
+        }
+
+        let cell_width = inner.width / self.columns as u16;
+        for (r, row_items) in self
+            .shown
+            .chunks(self.columns)
+            .skip(offset)
+            .take(rows)
+            .enumerate()
+        {
+            for (c, (item, matched)) in row_items.iter().enumerate() {
+                let area = Rect::new(
+                    inner.x + c as u16 * cell_width,
+                    inner.y + r as u16,
+                    cell_width,
+                    1,
+                );
+                let is_selected = (offset + r) * self.columns + c == cursor;
+                self.render_item(frame, area, *item, matched, is_selected);
+            }
+        }
+
+        if rows > 0 && inner.width > 0 {
+            let x = inner.right() - 1;
+            if offset > 0 {
+                frame.buffer_mut()[(x, inner.y)]
+                    .set_symbol("▲")
+                    .set_style(self.style.indicator);
+            }
+            if offset + rows < total_rows {
+                let y = inner.y + rows as u16 - 1;
+                frame.buffer_mut()[(x, y)]
+                    .set_symbol("▼")
+                    .set_style(self.style.indicator);
+            }
+        }
+    }
+
+    /// Render item `index` with its selection marker and highlighted
+    /// `matched` positions, truncated to `area`.
+    fn render_item(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        index: usize,
+        matched: &[usize],
+        is_selected: bool,
+    ) {
+        let item = &self.items[index];
+        let style = if is_selected {
+            self.style.selected_item
+        } else {
+            self.style.item
+        };
+        let prefix = if is_selected { "▸ " } else { "  " };
+
+        // Truncate if needed
+        let max_text_width = area.width.saturating_sub(2) as usize; // prefix is 2 chars
+        let (kept, ellipsis) = if item.chars().count() > max_text_width {
+            let kept: String = item
+                .chars()
+                .take(max_text_width.saturating_sub(3))
+                .collect();
+            (kept, "...")
+        } else {
+            (item.clone(), "")
+        };
+
+        let mut spans = vec![Span::styled(prefix, style)];
+        spans.extend(matcher::highlight(
+            &kept,
+            matched,
+            style,
+            self.style.matched,
+        ));
+        spans.push(Span::styled(ellipsis, style));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
     }
 }
 
@@ -198,6 +410,10 @@ impl Component for Dropdown {
                     self.visible = false;
                     Command::message(Message::Dismissed)
                 }
+                KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down if self.is_grid() => {
+                    self.move_in_grid(key.code);
+                    Command::none()
+                }
                 KeyCode::Up => {
                     self.select_prev();
                     Command::none()
@@ -207,14 +423,30 @@ impl Component for Dropdown {
                     Command::none()
                 }
                 KeyCode::Enter => {
-                    let idx = self.selection.cursor();
-                    if let Some(value) = self.items.get(idx).cloned() {
+                    if let Some(value) = self.selected_value().map(str::to_string) {
                         self.visible = false;
-                        Command::message(Message::Selected(idx, value))
+                        Command::message(Message::Selected(self.selected_index(), value))
                     } else {
                         Command::none()
                     }
                 }
+                KeyCode::Char(c)
+                    if self.filterable
+                        && !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                {
+                    let mut query = std::mem::take(&mut self.query);
+                    query.push(c);
+                    self.set_filter(&query);
+                    Command::none()
+                }
+                KeyCode::Backspace if self.filterable => {
+                    let mut query = std::mem::take(&mut self.query);
+                    query.pop();
+                    self.set_filter(&query);
+                    Command::none()
+                }
                 _ => Command::none(),
             },
             Message::Selected(..) | Message::Dismissed => Command::none(),
@@ -226,7 +458,8 @@ impl Component for Dropdown {
             return;
         }
 
-        let visible_count = self.items.len().min(self.max_visible);
+        let total_rows = self.shown.len().div_ceil(self.columns);
+        let visible_count = total_rows.min(self.max_visible);
         let dropdown_height = if self.block.is_some() {
             visible_count as u16 + 2 // +2 for borders
         } else {
@@ -241,7 +474,8 @@ impl Component for Dropdown {
             }
             Position::Below => {
                 let y = anchor.y + anchor.height;
-                Rect::new(anchor.x, y, anchor.width, dropdown_height)
+                let height = dropdown_height.min(frame.area().bottom().saturating_sub(y));
+                Rect::new(anchor.x, y, anchor.width, height)
             }
         };
 
@@ -260,15 +494,22 @@ impl Component for Dropdown {
             dropdown_area
         };
 
+        if self.is_grid() {
+            self.view_grid(frame, inner);
+            return;
+        }
+
+        // The overlay may have been clipped to fewer rows than requested;
+        // keep the selection inside whatever is actually on screen.
+        let rows = (inner.height as usize).min(visible_count);
+        let cursor = self.selection.cursor();
+        let mut offset = self.selection.offset();
+        if cursor >= offset + rows {
+            offset = cursor + 1 - rows;
+        }
+
         // Render items
-        let offset = self.selection.offset();
-        for (i, item) in self
-            .items
-            .iter()
-            .skip(offset)
-            .take(visible_count)
-            .enumerate()
-        {
+        for (i, (item, matched)) in self.shown.iter().skip(offset).take(rows).enumerate() {
             let row_area = Rect {
                 y: inner.y + i as u16,
                 height: 1,
@@ -276,28 +517,34 @@ impl Component for Dropdown {
             };
 
             let is_selected = i + offset == self.selection.cursor();
-            let style = if is_selected {
-                self.style.selected_item
-            } else {
-                self.style.item
-            };
-            let prefix = if is_selected { "▸ " } else { "  " };
-
-            // Truncate if needed
-            let max_text_width = row_area.width.saturating_sub(2) as usize; // prefix is 2 chars
-            let display = if item.len() > max_text_width {
-                format!("{}{}...", prefix, &item[..max_text_width.saturating_sub(3)])
-            } else {
-                format!("{}{}", prefix, item)
-            };
+            self.render_item(frame, row_area, *item, matched, is_selected);
+        }
 
-            frame.render_widget(Paragraph::new(Span::styled(display, style)), row_area);
+        // More-items indicators in the last column of the first/last row.
+        if rows > 0 && inner.width > 0 {
+            let x = inner.right() - 1;
+            if offset > 0 {
+                frame.buffer_mut()[(x, inner.y)]
+                    .set_symbol("▲")
+                    .set_style(self.style.indicator);
+            }
+            if offset + rows < self.shown.len() {
+                let y = inner.y + rows as u16 - 1;
+                frame.buffer_mut()[(x, y)]
+                    .set_symbol("▼")
+                    .set_style(self.style.indicator);
+            }
         }
     }
 
     fn focused(&self) -> bool {
         self.visible
     }
+
+    /// `true` while the dropdown is shown; Esc dismisses it.
+    fn wants_escape(&self) -> bool {
+        self.is_visible()
+    }
 }
 
 #[cfg(test)]
@@ -408,8 +655,10 @@ mod tests {
     #[test]
     fn esc_dismisses() {
         let mut dropdown = Dropdown::new();
+        assert!(!dropdown.wants_escape());
         dropdown.set_items(vec!["a".into()]);
         assert!(dropdown.is_visible());
+        assert!(dropdown.wants_escape());
 
         let cmd = dropdown.update(Message::KeyPress(key(KeyCode::Esc)));
         match cmd.into_message() {
@@ -420,6 +669,7 @@ mod tests {
             ),
         }
         assert!(!dropdown.is_visible());
+        assert!(!dropdown.wants_escape());
     }
 
     #[test]
@@ -436,6 +686,59 @@ mod tests {
         assert_eq!(dropdown.selection.offset(), 2);
     }
 
+    fn render(dropdown: &Dropdown, width: u16, height: u16) -> Vec<String> {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| dropdown.view(f, Rect::new(0, 0, width, 1)))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    fn many_items(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("item {i}")).collect()
+    }
+
+    #[test]
+    fn overlay_height_never_exceeds_cap() {
+        let mut dropdown = Dropdown::new()
+            .with_max_visible(5)
+            .with_block(Block::bordered());
+        dropdown.set_items(many_items(50));
+        let rows = render(&dropdown, 20, 30);
+        let used = rows.iter().skip(1).filter(|r| !r.trim().is_empty()).count();
+        assert_eq!(used, 5 + 2);
+        assert!(rows[2].contains("item 0"));
+        assert!(rows[6].contains("item 4") && rows[6].contains('▼'));
+        assert!(!rows[2].contains('▲'));
+    }
+
+    #[test]
+    fn selection_beyond_window_scrolls_view() {
+        let mut dropdown = Dropdown::new().with_max_visible(3);
+        dropdown.set_items(many_items(10));
+        for _ in 0..5 {
+            dropdown.update(Message::KeyPress(key(KeyCode::Down)));
+        }
+        let rows = render(&dropdown, 20, 10);
+        assert!(rows[1].contains("item 3") && rows[1].contains('▲'));
+        assert!(rows[3].contains("▸ item 5") && rows[3].contains('▼'));
+        assert!(rows[4].trim().is_empty());
+    }
+
+    #[test]
+    fn clipped_overlay_keeps_selection_visible() {
+        let mut dropdown = Dropdown::new().with_max_visible(8);
+        dropdown.set_items(many_items(20));
+        dropdown.set_selected(6);
+        // Only four rows fit below the anchor.
+        let rows = render(&dropdown, 20, 5);
+        assert!(rows[4].contains("▸ item 6"));
+    }
+
     #[test]
     fn hide_and_show() {
         let mut dropdown = Dropdown::new();
@@ -472,4 +775,146 @@ mod tests {
         assert_eq!(dropdown.max_visible, 3);
         assert_eq!(dropdown.position, Position::Above);
     }
+
+    fn grid(n: usize) -> Dropdown {
+        // 3 columns: rows [0 1 2] [3 4 5] [6 7]
+        let mut dropdown = Dropdown::new().with_columns(3);
+        dropdown.set_items((0..n).map(|i| i.to_string()).collect());
+        dropdown
+    }
+
+    fn press(dropdown: &mut Dropdown, code: KeyCode) -> usize {
+        dropdown.update(Message::KeyPress(key(code)));
+        dropdown.selected_index()
+    }
+
+    #[test]
+    fn grid_right_moves_within_row_and_stops_at_edge() {
+        let mut dropdown = grid(8);
+        assert_eq!(press(&mut dropdown, KeyCode::Right), 1);
+        assert_eq!(press(&mut dropdown, KeyCode::Right), 2);
+        assert_eq!(press(&mut dropdown, KeyCode::Right), 2);
+        assert_eq!(press(&mut dropdown, KeyCode::Left), 1);
+        assert_eq!(press(&mut dropdown, KeyCode::Left), 0);
+        assert_eq!(press(&mut dropdown, KeyCode::Left), 0);
+    }
+
+    #[test]
+    fn grid_down_keeps_column_and_clamps() {
+        let mut dropdown = grid(8);
+        dropdown.set_selected(1);
+        assert_eq!(press(&mut dropdown, KeyCode::Down), 4);
+        assert_eq!(press(&mut dropdown, KeyCode::Down), 7);
+        assert_eq!(press(&mut dropdown, KeyCode::Down), 7);
+        assert_eq!(press(&mut dropdown, KeyCode::Up), 4);
+        assert_eq!(press(&mut dropdown, KeyCode::Up), 1);
+        assert_eq!(press(&mut dropdown, KeyCode::Up), 1);
+
+        // Column 2 has no cell in the short last row; Down lands on the
+        // row's last item.
+        dropdown.set_selected(5);
+        assert_eq!(press(&mut dropdown, KeyCode::Down), 7);
+        // Right at the end of a short row stays put.
+        assert_eq!(press(&mut dropdown, KeyCode::Right), 7);
+    }
+
+    #[test]
+    fn grid_renders_items_across_columns() {
+        let mut dropdown = grid(8);
+        dropdown.set_selected(4);
+        let rows = render(&dropdown, 24, 5);
+        // 24 columns / 3 = 8-wide cells.
+        assert_eq!(rows[1].trim_end(), "  0       1       2");
+        assert_eq!(rows[2].trim_end(), "  3     ▸ 4       5");
+        assert_eq!(rows[3].trim_end(), "  6       7");
+    }
+
+    #[test]
+    fn grid_scrolls_by_rows() {
+        let mut dropdown = grid(12).with_max_visible(2);
+        for _ in 0..2 {
+            press(&mut dropdown, KeyCode::Down);
+        }
+        assert_eq!(dropdown.selected_index(), 6);
+        let rows = render(&dropdown, 24, 4);
+        assert!(rows[1].starts_with("  3"), "{rows:?}");
+        assert!(rows[1].ends_with('▲'), "{rows:?}");
+        assert!(
+            rows[2].contains("▸ 6") && rows[2].ends_with('▼'),
+            "{rows:?}"
+        );
+    }
+
+    fn filtered(query: &str) -> Dropdown {
+        let mut dropdown = Dropdown::new().with_filter(true);
+        dropdown.set_items(
+            ["Apple", "Banana", "Grape", "pineapple"]
+                .map(String::from)
+                .to_vec(),
+        );
+        for c in query.chars() {
+            press(&mut dropdown, KeyCode::Char(c));
+        }
+        dropdown
+    }
+
+    #[test]
+    fn typing_filters_like_list() {
+        use crate::list::{self, List};
+
+        for query in ["ap", "AN", "e", "zz"] {
+            let dropdown = filtered(query);
+            let mut list = List::new(vec!["Apple", "Banana", "Grape", "pineapple"]);
+            list.focus();
+            list.update(list::Message::KeyPress(key(KeyCode::Char('/'))));
+            for c in query.chars() {
+                list.update(list::Message::KeyPress(key(KeyCode::Char(c))));
+            }
+
+            for i in 0..4 {
+                assert_eq!(
+                    dropdown.filter_match(i),
+                    list.filter_match(i),
+                    "{query} {i}"
+                );
+            }
+            let shown: Vec<usize> = (0..4).filter(|&i| list.filter_match(i).is_some()).collect();
+            assert_eq!(dropdown.shown_indices(), shown, "{query}");
+        }
+    }
+
+    #[test]
+    fn filter_selects_original_indices() {
+        let mut dropdown = filtered("ap");
+        assert_eq!(dropdown.shown_indices(), [0, 2, 3]);
+        press(&mut dropdown, KeyCode::Down);
+        assert_eq!(dropdown.selected_index(), 2);
+        let cmd = dropdown.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(matches!(
+            cmd.into_message(),
+            Some(Message::Selected(2, v)) if v == "Grape"
+        ));
+
+        let mut dropdown = filtered("app");
+        press(&mut dropdown, KeyCode::Backspace);
+        assert_eq!(dropdown.filter(), "ap");
+        assert_eq!(dropdown.shown_indices(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn view_highlights_matched_chars() {
+        let dropdown = filtered("ap");
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(16, 5)).unwrap();
+        terminal
+            .draw(|f| dropdown.view(f, Rect::new(0, 0, 16, 1)))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let row: String = (0..16).map(|x| buf[(x, 2)].symbol()).collect();
+        assert_eq!(row.trim_end(), "  Grape");
+        let underlined: Vec<bool> = (2..7)
+            .map(|x| buf[(x, 2)].modifier.contains(Modifier::UNDERLINED))
+            .collect();
+        assert_eq!(underlined, [false, false, true, true, false]);
+    }
 }
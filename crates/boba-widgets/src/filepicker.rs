@@ -1,9 +1,13 @@
 //! File system browser component with directory navigation, hidden file
 //! toggling, extension filtering, and async directory loading.
+//!
+//! The current directory is shown as a breadcrumb above the listing. Press
+//! `Ctrl+L` to type a path and jump straight to it.
 
 use boba_core::command::Command;
 use boba_core::component::Component;
-use crossterm::event::{KeyCode, KeyEvent};
+use boba_core::impl_terminal_message;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -28,6 +32,10 @@ pub enum Message {
     FilesLoaded(Vec<FileEntry>),
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// A single entry in the file picker listing.
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -52,6 +60,10 @@ pub struct FilePickerStyle {
     pub file: Style,
     /// Style applied to the currently highlighted entry.
     pub selected: Style,
+    /// Style applied to the current-path breadcrumb.
+    pub breadcrumb: Style,
+    /// Style applied to status messages such as navigation errors.
+    pub error: Style,
 }
 
 impl Default for FilePickerStyle {
@@ -64,6 +76,8 @@ impl Default for FilePickerStyle {
             selected: Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            breadcrumb: Style::default().fg(Color::DarkGray),
+            error: Style::default().fg(Color::Red),
         }
     }
 }
@@ -85,6 +99,11 @@ pub struct FilePicker {
     focus: bool,
     style: FilePickerStyle,
     block: Option<Block<'static>>,
+    show_breadcrumb: bool,
+    /// Path being typed after `Ctrl+L`, if the prompt is open.
+    path_input: Option<String>,
+    /// Status message shown below the listing (e.g. a navigation error).
+    status: Option<String>,
 }
 
 impl FilePicker {
@@ -103,9 +122,18 @@ impl FilePicker {
             focus: false,
             style: FilePickerStyle::default(),
             block: None,
+            show_breadcrumb: true,
+            path_input: None,
+            status: None,
         }
     }
 
+    /// Set whether the current-path breadcrumb is shown above the listing.
+    pub fn with_breadcrumb(mut self, show: bool) -> Self {
+        self.show_breadcrumb = show;
+        self
+    }
+
     /// Set whether hidden files (starting with '.') are shown.
     pub fn with_show_hidden(mut self, show: bool) -> Self {
         self.show_hidden = show;
@@ -169,6 +197,101 @@ impl FilePicker {
         self.entries.get(self.cursor)
     }
 
+    /// Return the current status message, such as a failed navigation.
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    /// Whether the `Ctrl+L` path prompt is open.
+    pub fn is_entering_path(&self) -> bool {
+        self.path_input.is_some()
+    }
+
+    /// Jump to `path` and reload the listing.
+    ///
+    /// Relative paths are resolved against the current directory and a
+    /// leading `~` expands to `$HOME`. If the path doesn't exist or isn't a
+    /// directory, the picker is left unchanged and the error is returned and
+    /// shown as the status message.
+    pub fn navigate_to(&mut self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = self.resolve_path(path.as_ref());
+        if !path.is_dir() {
+            let err = if path.exists() {
+                format!("Not a directory: {}", path.display())
+            } else {
+                format!("No such directory: {}", path.display())
+            };
+            self.status = Some(err.clone());
+            return Err(err);
+        }
+
+        self.entries = read_directory(&path, self.show_hidden, &self.allowed_extensions);
+        self.current_dir = path;
+        self.cursor = 0;
+        self.status = None;
+        Ok(())
+    }
+
+    fn resolve_path(&self, path: &Path) -> PathBuf {
+        let path = match path.strip_prefix("~") {
+            Ok(rest) => match std::env::var_os("HOME") {
+                Some(home) => PathBuf::from(home).join(rest),
+                None => path.to_path_buf(),
+            },
+            Err(_) => path.to_path_buf(),
+        };
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.current_dir.join(path)
+        };
+        normalize(&path)
+    }
+
+    /// Handle a key while the path prompt is open.
+    fn update_path_input(&mut self, key: KeyEvent) -> Command<Message> {
+        let Some(input) = self.path_input.as_mut() else {
+            return Command::none();
+        };
+        match key.code {
+            KeyCode::Esc => self.path_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                let target = self.path_input.take().unwrap_or_default();
+                if self.navigate_to(target.trim()).is_ok() {
+                    return Command::message(Message::EnterDir(self.current_dir.clone()));
+                }
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+
+    /// Build the breadcrumb line for the current directory, dropping leading
+    /// components when it doesn't fit in `width` columns.
+    fn breadcrumb(&self, width: usize) -> Line<'static> {
+        const SEP: &str = " › ";
+        let parts: Vec<String> = self
+            .current_dir
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let mut start = 0;
+        loop {
+            let mut text = parts[start..].join(SEP);
+            if start > 0 {
+                text = format!("…{SEP}{text}");
+            }
+            if crate::runeutil::display_width(&text) <= width || start + 1 >= parts.len() {
+                return Line::from(Span::styled(text, self.style.breadcrumb));
+            }
+            start += 1;
+        }
+    }
+
     /// Build a command to load files asynchronously.
     ///
     /// Directory reading via `std::fs::read_dir` is fast enough to perform
@@ -185,6 +308,26 @@ impl FilePicker {
     }
 }
 
+/// Fold `.` and `..` segments out of `path` without touching the file
+/// system, so a directory reads the same however it was reached.
+fn normalize(path: &Path) -> PathBuf {
+    use std::path::Component;
+    let mut out = PathBuf::new();
+    for part in path.components() {
+        match part {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // `..` above the root is the root itself.
+                if !out.pop() && !out.has_root() {
+                    out.push(part);
+                }
+            }
+            _ => out.push(part),
+        }
+    }
+    out
+}
+
 /// Read directory contents synchronously, returning sorted file entries.
 /// Directories come first, then files. Both groups are sorted alphabetically.
 fn read_directory(dir: &Path, show_hidden: bool, allowed_extensions: &[String]) -> Vec<FileEntry> {
@@ -286,6 +429,22 @@ impl Component for FilePicker {
 
     fn update(&mut self, msg: Message) -> Command<Message> {
         match msg {
+            Message::KeyPress(key) if self.focus && self.path_input.is_some() => {
+                self.update_path_input(key)
+            }
+            Message::KeyPress(key)
+                if self.focus
+                    && key.code == KeyCode::Char('l')
+                    && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                let mut prefill = self.current_dir.display().to_string();
+                if !prefill.ends_with(std::path::MAIN_SEPARATOR) {
+                    prefill.push(std::path::MAIN_SEPARATOR);
+                }
+                self.path_input = Some(prefill);
+                self.status = None;
+                Command::none()
+            }
             Message::KeyPress(key) if self.focus => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => {
                     if self.cursor > 0 {
@@ -392,6 +551,36 @@ impl Component for FilePicker {
             return;
         }
 
+        // Breadcrumb on top, prompt or status at the bottom, listing between.
+        let mut inner = inner;
+        if self.show_breadcrumb && inner.height > 1 {
+            let row = Rect { height: 1, ..inner };
+            frame.render_widget(Paragraph::new(self.breadcrumb(inner.width as usize)), row);
+            inner.y += 1;
+            inner.height -= 1;
+        }
+        let footer = if let Some(ref input) = self.path_input {
+            Some(Line::from(vec![
+                Span::styled("Go to: ", self.style.breadcrumb),
+                Span::raw(input.clone()),
+            ]))
+        } else {
+            self.status
+                .as_ref()
+                .map(|status| Line::from(Span::styled(status.clone(), self.style.error)))
+        };
+        if let Some(footer) = footer {
+            if inner.height > 1 {
+                inner.height -= 1;
+                let row = Rect {
+                    y: inner.y + inner.height,
+                    height: 1,
+                    ..inner
+                };
+                frame.render_widget(Paragraph::new(footer), row);
+            }
+        }
+
         // Determine visible range (scrolling)
         let visible_height = inner.height as usize;
         let scroll_offset = if self.cursor >= visible_height {
@@ -467,3 +656,107 @@ impl Component for FilePicker {
         self.focus
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    /// Create a scratch tree `<tmp>/<name>/{sub/inner.txt, top.txt}`.
+    fn scratch_tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("boba-fp-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("top.txt"), "x").unwrap();
+        std::fs::write(root.join("sub").join("inner.txt"), "y").unwrap();
+        root
+    }
+
+    fn names(picker: &FilePicker) -> Vec<String> {
+        picker.entries.iter().map(|e| e.name.clone()).collect()
+    }
+
+    #[test]
+    fn navigate_to_updates_listing() {
+        let root = scratch_tree("nav");
+        let mut picker = FilePicker::new(root.clone());
+        assert_eq!(names(&picker), ["sub", "top.txt"]);
+
+        picker.navigate_to(root.join("sub")).unwrap();
+        assert_eq!(picker.current_dir(), root.join("sub"));
+        assert_eq!(names(&picker), ["inner.txt"]);
+        assert_eq!(picker.status(), None);
+
+        // Relative paths resolve against the current directory
+        picker.navigate_to("..").unwrap();
+        assert_eq!(picker.current_dir(), root);
+        assert!(names(&picker).contains(&"top.txt".to_string()));
+
+        // `.` and `..` segments are folded away.
+        picker.navigate_to("./sub/../sub").unwrap();
+        assert_eq!(picker.current_dir(), root.join("sub"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn navigate_to_invalid_path_reports_error() {
+        let root = scratch_tree("invalid");
+        let mut picker = FilePicker::new(root.clone());
+
+        assert!(picker.navigate_to(root.join("missing")).is_err());
+        assert!(picker.status().unwrap().contains("No such directory"));
+        assert_eq!(picker.current_dir(), root);
+        assert_eq!(names(&picker), ["sub", "top.txt"]);
+
+        assert!(picker.navigate_to(root.join("top.txt")).is_err());
+        assert!(picker.status().unwrap().contains("Not a directory"));
+        assert_eq!(picker.current_dir(), root);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn ctrl_l_prompt_jumps_to_typed_path() {
+        let root = scratch_tree("prompt");
+        let mut picker = FilePicker::new(root.clone());
+        picker.focus();
+
+        picker.update(Message::KeyPress(key(
+            KeyCode::Char('l'),
+            KeyModifiers::CONTROL,
+        )));
+        assert!(picker.is_entering_path());
+        for c in "sub".chars() {
+            picker.update(Message::KeyPress(key(KeyCode::Char(c), KeyModifiers::NONE)));
+        }
+        let cmd = picker.update(Message::KeyPress(key(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(matches!(cmd.into_message(), Some(Message::EnterDir(p)) if p == root.join("sub")));
+        assert!(!picker.is_entering_path());
+        assert_eq!(names(&picker), ["inner.txt"]);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn breadcrumb_renders_and_truncates() {
+        let mut picker = FilePicker::new(PathBuf::from("/"));
+        picker.current_dir = PathBuf::from("/home/user/projects/boba");
+
+        let full = picker.breadcrumb(80).to_string();
+        assert!(full.ends_with("home › user › projects › boba"), "{full}");
+
+        let short = picker.breadcrumb(16).to_string();
+        assert!(short.starts_with('…'), "{short}");
+        assert!(short.ends_with("boba"), "{short}");
+    }
+}
@@ -33,14 +33,26 @@
 ///
 /// See `examples/input_form.rs` for a complete working example with
 /// `FocusGroup` routing between two `TextArea` widgets and a submit button.
+///
+/// # Focus traps
+///
+/// When an overlay such as a [`Modal`](crate::modal::Modal) opens, call
+/// [`trap`](Self::trap) to remember the focused slot and stop Tab cycling
+/// between the underlying slots. When the overlay closes, call
+/// [`release`](Self::release) to put focus back where it was.
 pub struct FocusGroup<const N: usize> {
     focused: usize,
+    /// Slot that was focused when the current trap began.
+    trapped_from: Option<usize>,
 }
 
 impl<const N: usize> FocusGroup<N> {
     /// Create a new focus group with focus on the first slot (index 0).
     pub fn new() -> Self {
-        Self { focused: 0 }
+        Self {
+            focused: 0,
+            trapped_from: None,
+        }
     }
 
     /// Return the index of the currently focused slot.
@@ -49,13 +61,19 @@ impl<const N: usize> FocusGroup<N> {
     }
 
     /// Move focus to the next slot, wrapping around after the last.
+    /// Does nothing while focus is trapped.
     pub fn focus_next(&mut self) {
-        self.focused = (self.focused + 1) % N;
+        if !self.is_trapped() {
+            self.focused = (self.focused + 1) % N;
+        }
     }
 
     /// Move focus to the previous slot, wrapping around before the first.
+    /// Does nothing while focus is trapped.
     pub fn focus_prev(&mut self) {
-        self.focused = (self.focused + N - 1) % N;
+        if !self.is_trapped() {
+            self.focused = (self.focused + N - 1) % N;
+        }
     }
 
     /// Set focus to the given slot index, clamped to the valid range.
@@ -67,6 +85,28 @@ impl<const N: usize> FocusGroup<N> {
     pub fn is_focused(&self, index: usize) -> bool {
         self.focused == index
     }
+
+    /// Trap focus in an overlay, remembering the currently focused slot.
+    ///
+    /// Returns the remembered slot. Trapping while already trapped keeps the
+    /// slot recorded by the outermost trap.
+    pub fn trap(&mut self) -> usize {
+        *self.trapped_from.get_or_insert(self.focused)
+    }
+
+    /// End the current trap and restore focus to the slot that was focused
+    /// when it began. Returns the focused slot.
+    pub fn release(&mut self) -> usize {
+        if let Some(slot) = self.trapped_from.take() {
+            self.focused = slot;
+        }
+        self.focused
+    }
+
+    /// Return whether focus is currently trapped in an overlay.
+    pub fn is_trapped(&self) -> bool {
+        self.trapped_from.is_some()
+    }
 }
 
 impl<const N: usize> Default for FocusGroup<N> {
@@ -118,4 +158,21 @@ mod tests {
         assert!(!fg.is_focused(0));
         assert!(fg.is_focused(1));
     }
+
+    #[test]
+    fn trap_blocks_cycling_and_release_restores() {
+        let mut fg = FocusGroup::<3>::new();
+        fg.focus(2);
+        assert_eq!(fg.trap(), 2);
+        assert!(fg.is_trapped());
+        fg.focus_next();
+        fg.focus_prev();
+        assert_eq!(fg.focused(), 2);
+
+        fg.focus(0);
+        assert_eq!(fg.release(), 2);
+        assert!(!fg.is_trapped());
+        fg.focus_next();
+        assert_eq!(fg.focused(), 0);
+    }
 }
@@ -6,10 +6,22 @@
 //!
 //! For overlay behavior (centered rect, scroll, key handling), compose
 //! `help.full_help_view()` with [`overlay::render_overlay()`](crate::overlay::render_overlay)
-//! and a scrollable widget like [`Paragraph`](ratatui::widgets::Paragraph).
+//! and a scrollable widget like [`Paragraph`](ratatui::widgets::Paragraph),
+//! or use the ready-made [`HelpModal`](crate::help_modal::HelpModal).
+//!
+//! In an app with several focusable widgets, [`ContextualHelp`] picks the
+//! focused widget's [`KeyMap`] through a [`FocusGroup`] so the help line
+//! follows focus.
 
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget};
+
+use crate::focus::FocusGroup;
+use crate::key::{Binding, KeyDisplayStyle, KeyMap};
+use crate::runeutil::buffer_lines;
 
 /// A single keybinding entry displayed in help views.
 #[derive(Debug, Clone)]
@@ -22,6 +34,40 @@ pub struct HelpBinding {
     pub group: String,
 }
 
+impl HelpBinding {
+    /// Build a help entry from a [`Binding`], joining its key combinations
+    /// with `/` (e.g. "up/k").
+    pub fn from_binding(binding: &Binding, group: impl Into<String>) -> Self {
+        Self::from_binding_with(binding, group, KeyDisplayStyle::Text, false)
+    }
+
+    /// Build a help entry from a [`Binding`], writing its keys in `display`
+    /// style. With `long`, the entry uses the binding's
+    /// [`long_help`](Binding::long_help) text, as the full help does.
+    pub fn from_binding_with(
+        binding: &Binding,
+        group: impl Into<String>,
+        display: KeyDisplayStyle,
+        long: bool,
+    ) -> Self {
+        let description = if long {
+            binding.long_help()
+        } else {
+            &binding.description
+        };
+        Self {
+            keys: binding
+                .keys
+                .iter()
+                .map(|k| k.display(display))
+                .collect::<Vec<_>>()
+                .join("/"),
+            description: description.to_string(),
+            group: group.into(),
+        }
+    }
+}
+
 /// Visual style configuration for [`Help`] formatting.
 #[derive(Debug, Clone)]
 pub struct HelpStyle {
@@ -81,6 +127,7 @@ pub struct Help {
     separator: String,
     max_width: Option<u16>,
     ellipsis: String,
+    key_display: KeyDisplayStyle,
 }
 
 impl Help {
@@ -92,6 +139,7 @@ impl Help {
             separator: " \u{2022} ".to_string(), // " • "
             max_width: None,
             ellipsis: "\u{2026}".to_string(), // "…"
+            key_display: KeyDisplayStyle::Text,
         }
     }
 
@@ -126,6 +174,43 @@ impl Help {
         self
     }
 
+    /// Set how keys from a [`KeyMap`] are written (default:
+    /// [`KeyDisplayStyle::Text`]). Applies to
+    /// [`short_help_bindings`](Help::short_help_bindings) and
+    /// [`full_help_bindings`](Help::full_help_bindings); entries added as
+    /// strings are shown as given.
+    pub fn with_key_display(mut self, style: KeyDisplayStyle) -> Self {
+        self.key_display = style;
+        self
+    }
+
+    /// The enabled bindings of `keymap`'s short help, with their short
+    /// descriptions.
+    pub fn short_help_bindings(&self, keymap: &dyn KeyMap) -> Vec<HelpBinding> {
+        keymap
+            .short_help()
+            .into_iter()
+            .filter(|b| b.enabled)
+            .map(|b| HelpBinding::from_binding_with(b, "", self.key_display, false))
+            .collect()
+    }
+
+    /// The enabled bindings of `keymap`'s full help, grouped as the key map
+    /// groups them, with their long descriptions.
+    pub fn full_help_bindings(&self, keymap: &dyn KeyMap) -> Vec<Vec<HelpBinding>> {
+        keymap
+            .full_help()
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .filter(|b| b.enabled)
+                    .map(|b| HelpBinding::from_binding_with(b, "", self.key_display, true))
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Append a single keybinding entry with the given keys, description, and group.
     pub fn add_binding(
         &mut self,
@@ -163,8 +248,13 @@ impl Help {
                 Span::styled(b.description.clone(), self.style.description),
             ];
 
-            let entry_width: usize = b.keys.len() + 1 + b.description.len();
-            let sep_width = if idx > 0 { self.separator.len() } else { 0 };
+            // Display width, since keys may be written as glyphs.
+            let entry_width: usize = entry_spans.iter().map(Span::width).sum();
+            let sep_width = if idx > 0 {
+                Span::raw(self.separator.as_str()).width()
+            } else {
+                0
+            };
 
             if let Some(max_w) = max {
                 if total_width + sep_width + entry_width > max_w {
@@ -208,6 +298,28 @@ impl Help {
         }
         lines
     }
+
+    /// Render the full help for all registered bindings as owned lines,
+    /// clipped to `width` columns.
+    ///
+    /// Consecutive bindings that share a group are listed under one
+    /// heading, as in [`full_help_view`](Help::full_help_view). The result
+    /// can be handed straight to a
+    /// [`Viewport`](crate::viewport::Viewport) for scrolling.
+    pub fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut groups: Vec<Vec<HelpBinding>> = Vec::new();
+        for binding in &self.bindings {
+            match groups.last_mut() {
+                Some(group) if group[0].group == binding.group => group.push(binding.clone()),
+                _ => groups.push(vec![binding.clone()]),
+            }
+        }
+        let lines = self.full_help_view(&groups);
+        let area = Rect::new(0, 0, width, lines.len() as u16);
+        let mut buf = Buffer::empty(area);
+        Paragraph::new(lines).render(area, &mut buf);
+        buffer_lines(&buf)
+    }
 }
 
 impl Default for Help {
@@ -216,9 +328,62 @@ impl Default for Help {
     }
 }
 
+/// Short help that follows keyboard focus.
+///
+/// Holds one [`KeyMap`] per [`FocusGroup`] slot and formats the focused
+/// slot's [`short_help`](KeyMap::short_help) with a [`Help`]. It borrows the
+/// key maps, so build it where it is drawn:
+///
+/// ```ignore
+/// use boba_widgets::help::ContextualHelp;
+///
+/// // In view, with `focus: FocusGroup<2>` choosing between the two:
+/// let help = ContextualHelp::new([self.list.key_bindings(), self.table.key_bindings()]);
+/// frame.render_widget(Paragraph::new(help.short_help_line(&self.focus)), status_area);
+/// ```
+pub struct ContextualHelp<'a, const N: usize> {
+    keymaps: [&'a dyn KeyMap; N],
+    help: Help,
+}
+
+impl<'a, const N: usize> ContextualHelp<'a, N> {
+    /// Create contextual help from the key maps of each focus slot, in slot
+    /// order.
+    pub fn new(keymaps: [&'a dyn KeyMap; N]) -> Self {
+        Self {
+            keymaps,
+            help: Help::new(),
+        }
+    }
+
+    /// Format with `help`'s style, separator and width limit. Its own
+    /// bindings are ignored.
+    pub fn with_help(mut self, help: Help) -> Self {
+        self.help = help;
+        self
+    }
+
+    /// The key map of the focused slot.
+    pub fn focused(&self, focus: &FocusGroup<N>) -> &'a dyn KeyMap {
+        self.keymaps[focus.focused()]
+    }
+
+    /// The focused slot's enabled short-help bindings.
+    pub fn bindings(&self, focus: &FocusGroup<N>) -> Vec<HelpBinding> {
+        self.help.short_help_bindings(self.focused(focus))
+    }
+
+    /// Build the short help line for the focused slot.
+    pub fn short_help_line(&self, focus: &FocusGroup<N>) -> Line<'_> {
+        self.help.short_help_view(&self.bindings(focus))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::list::ListKeyBindings;
+    use crate::table::TableKeyBindings;
 
     #[test]
     fn short_help_line_produces_expected_spans() {
@@ -324,4 +489,96 @@ mod tests {
         assert_eq!(help.bindings()[0].keys, "q");
         assert_eq!(help.bindings()[1].keys, "?");
     }
+
+    #[test]
+    fn render_lines_groups_and_clips() {
+        let mut help = Help::new();
+        help.add_binding("j", "Down", "Navigation");
+        help.add_binding("k", "Up", "Navigation");
+        help.add_binding("q", "Quit the application", "General");
+
+        let lines = help.render_lines(20);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(text.len(), 6);
+        assert_eq!(text[0].trim_end(), "Navigation");
+        assert_eq!(text[1].trim_end(), "j           Down");
+        assert_eq!(text[3].trim_end(), "");
+        assert_eq!(text[4].trim_end(), "General");
+        // "q" padded to 12 columns leaves room for 8 characters of description.
+        assert_eq!(text[5], "q           Quit the");
+    }
+
+    struct EditorKeys {
+        save: Binding,
+        quit: Binding,
+    }
+
+    impl KeyMap for EditorKeys {
+        fn short_help(&self) -> Vec<&Binding> {
+            vec![&self.save, &self.quit]
+        }
+
+        fn full_help(&self) -> Vec<Vec<&Binding>> {
+            vec![vec![&self.save, &self.quit]]
+        }
+    }
+
+    #[test]
+    fn keymap_help_uses_glyphs_and_short_or_long_text() {
+        use crate::key::KeyCombination;
+        use crossterm::event::KeyCode;
+
+        let keys = EditorKeys {
+            save: Binding::new(KeyCombination::ctrl(KeyCode::Char('s')), "Save")
+                .with_long_help("Save the file to disk"),
+            quit: Binding::with_keys(
+                vec![
+                    KeyCombination::new(KeyCode::Esc),
+                    KeyCombination::parse("super+q").unwrap(),
+                ],
+                "Quit",
+            ),
+        };
+        let help = Help::new().with_key_display(KeyDisplayStyle::Symbols);
+
+        let short = help
+            .short_help_view(&help.short_help_bindings(&keys))
+            .to_string();
+        assert_eq!(short, "⌃s Save • ⎋/⌘q Quit");
+
+        let groups = help.full_help_bindings(&keys);
+        let full: Vec<String> = help
+            .full_help_view(&groups)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(
+            full,
+            ["⌃s          Save the file to disk", "⎋/⌘q        Quit"]
+        );
+
+        // The default display keeps the parseable names.
+        let plain = Help::new().short_help_bindings(&keys);
+        assert_eq!(plain[1].keys, "esc/super+q");
+    }
+
+    #[test]
+    fn contextual_help_follows_focus() {
+        let list_keys = ListKeyBindings::default();
+        let mut table_keys = TableKeyBindings::default();
+        table_keys.confirm.enabled = false;
+        let help = ContextualHelp::new([&list_keys as &dyn KeyMap, &table_keys]);
+        let mut focus = FocusGroup::<2>::new();
+
+        let list_line = help.short_help_line(&focus).to_string();
+        assert!(list_line.contains("Filter"), "{list_line}");
+
+        focus.focus_next();
+        let table_line = help.short_help_line(&focus).to_string();
+        assert_ne!(table_line, list_line);
+        assert!(!table_line.contains("Filter"), "{table_line}");
+        // Disabled bindings are left out.
+        assert!(!table_line.contains("Confirm"), "{table_line}");
+        assert_eq!(help.bindings(&focus).len(), 2);
+    }
 }
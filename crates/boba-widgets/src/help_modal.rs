@@ -0,0 +1,342 @@
+//! Full-screen help overlay that lays key bindings out in two columns and
+//! pages through them with a [`Paginator`].
+//!
+//! Build one from any [`KeyMap`] with [`HelpModal::from_keymap`], or from
+//! pre-grouped [`HelpBinding`]s with [`HelpModal::new`]. Left/Right (or
+//! `h`/`l`) change pages; Esc, `q` or `?` emit [`Message::Close`].
+
+use std::cell::Cell;
+
+use boba_core::command::Command;
+use boba_core::component::Component;
+use boba_core::impl_terminal_message;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+
+use crate::help::{Help, HelpBinding, HelpStyle};
+use crate::key::KeyMap;
+use crate::overlay;
+use crate::paginator::{self, Paginator};
+
+/// Number of binding columns shown per page.
+const COLUMNS: usize = 2;
+
+/// Width reserved for the key label in each row.
+const KEY_WIDTH: usize = 12;
+
+/// Messages for the help modal.
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// A key press event forwarded to the modal.
+    KeyPress(KeyEvent),
+    /// Emitted when the user asks to close the help (Esc, `q` or `?`).
+    Close,
+}
+
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
+/// A paginated, two-column help overlay.
+///
+/// # Example
+///
+/// ```ignore
+/// use boba_widgets::help_modal::HelpModal;
+///
+/// let help = HelpModal::from_keymap("Keys", &self.keymap);
+/// // In view, after drawing the rest of the UI:
+/// help.view(frame, frame.area());
+/// ```
+pub struct HelpModal {
+    title: String,
+    groups: Vec<Vec<HelpBinding>>,
+    style: HelpStyle,
+    paginator: Paginator,
+    width_percent: u16,
+    height_percent: u16,
+    /// Rows available per column at the last render.
+    rows_per_column: Cell<usize>,
+}
+
+impl HelpModal {
+    /// Create a help modal from bindings that are already grouped.
+    pub fn new(title: impl Into<String>, groups: Vec<Vec<HelpBinding>>) -> Self {
+        let mut paginator = Paginator::new(1);
+        paginator.focus();
+        Self {
+            title: title.into(),
+            groups: groups.into_iter().filter(|g| !g.is_empty()).collect(),
+            style: HelpStyle::default(),
+            paginator,
+            width_percent: 80,
+            height_percent: 70,
+            rows_per_column: Cell::new(10),
+        }
+    }
+
+    /// Create a help modal from a key map's [`full_help`](KeyMap::full_help)
+    /// groups. Disabled bindings are left out.
+    pub fn from_keymap(title: impl Into<String>, keymap: &impl KeyMap) -> Self {
+        Self::from_keymap_with(title, keymap, &Help::new())
+    }
+
+    /// Like [`from_keymap`](Self::from_keymap), writing keys in `help`'s
+    /// [key display style](Help::with_key_display).
+    pub fn from_keymap_with(title: impl Into<String>, keymap: &impl KeyMap, help: &Help) -> Self {
+        Self::new(title, help.full_help_bindings(keymap))
+    }
+
+    /// Set the help style.
+    pub fn with_style(mut self, style: HelpStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the overlay size as a percentage of the available area.
+    pub fn with_size(mut self, width_percent: u16, height_percent: u16) -> Self {
+        self.width_percent = width_percent.min(100);
+        self.height_percent = height_percent.min(100);
+        self
+    }
+
+    /// Set how many rows fit in each column. Normally this is measured from
+    /// the area passed to `view`.
+    pub fn with_rows_per_column(self, rows: usize) -> Self {
+        self.rows_per_column.set(rows.max(1));
+        self
+    }
+
+    /// The current page (zero-indexed).
+    pub fn page(&self) -> usize {
+        self.paginator.page().min(self.page_count() - 1)
+    }
+
+    /// The number of pages needed at the current size.
+    pub fn page_count(&self) -> usize {
+        let per_page = self.rows_per_column.get() * COLUMNS;
+        self.lines().len().div_ceil(per_page).max(1)
+    }
+
+    /// All help rows in order: group headings, bindings, and blank lines
+    /// between groups.
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (i, group) in self.groups.iter().enumerate() {
+            if i > 0 {
+                lines.push(Line::raw(""));
+            }
+            if !group[0].group.is_empty() {
+                lines.push(Line::styled(group[0].group.clone(), self.style.group));
+            }
+            for binding in group {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$}", binding.keys, width = KEY_WIDTH),
+                        self.style.key,
+                    ),
+                    Span::styled(binding.description.clone(), self.style.description),
+                ]));
+            }
+        }
+        lines
+    }
+
+    /// The columns of the given page, each at most `rows_per_column` long.
+    fn page_columns(&self, page: usize) -> Vec<Vec<Line<'static>>> {
+        let rows = self.rows_per_column.get();
+        let mut lines = self.lines().into_iter().skip(page * rows * COLUMNS);
+        (0..COLUMNS)
+            .map(|_| lines.by_ref().take(rows).collect())
+            .collect()
+    }
+}
+
+impl Component for HelpModal {
+    type Message = Message;
+
+    fn update(&mut self, msg: Message) -> Command<Message> {
+        match msg {
+            Message::KeyPress(key) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                    Command::message(Message::Close)
+                }
+                _ => {
+                    self.paginator.set_total_pages(self.page_count());
+                    // Page changes are internal; the host only cares about Close.
+                    let _ = self.paginator.update(paginator::Message::KeyPress(key));
+                    Command::none()
+                }
+            },
+            Message::Close => Command::none(),
+        }
+    }
+
+    fn view(&self, frame: &mut Frame, area: Rect) {
+        let modal_area = overlay::centered_rect(self.width_percent, self.height_percent, area);
+        let block = Block::bordered().title(Span::styled(self.title.clone(), self.style.title));
+        let inner = overlay::render_overlay(frame, modal_area, Some(&block));
+        if inner.height < 2 || inner.width == 0 {
+            return;
+        }
+
+        // Reserve the bottom row for the page indicator.
+        let [body, footer] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .areas(inner);
+        self.rows_per_column.set(body.height as usize);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, COLUMNS as u32); COLUMNS])
+            .split(body);
+        for (lines, column) in self
+            .page_columns(self.page())
+            .into_iter()
+            .zip(columns.iter())
+        {
+            frame.render_widget(Paragraph::new(lines), *column);
+        }
+
+        let pages = self.page_count();
+        if pages > 1 {
+            let mut indicator = Paginator::new(pages);
+            indicator.set_page(self.page());
+            let width = (pages * 2 - 1) as u16;
+            let x = footer.x + footer.width.saturating_sub(width) / 2;
+            indicator.view(frame, Rect::new(x, footer.y, width.min(footer.width), 1));
+        }
+    }
+
+    fn focused(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::{Binding, KeyCombination};
+    use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
+
+    fn key(code: KeyCode) -> Message {
+        Message::KeyPress(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    struct Keys {
+        bindings: Vec<Binding>,
+    }
+
+    impl KeyMap for Keys {
+        fn short_help(&self) -> Vec<&Binding> {
+            self.bindings.iter().take(2).collect()
+        }
+
+        fn full_help(&self) -> Vec<Vec<&Binding>> {
+            self.bindings
+                .chunks(4)
+                .map(|c| c.iter().collect())
+                .collect()
+        }
+    }
+
+    fn keys(n: usize) -> Keys {
+        let bindings = (0..n)
+            .map(|i| {
+                let c = char::from(b'a' + i as u8);
+                Binding::new(KeyCombination::new(KeyCode::Char(c)), format!("action {i}"))
+            })
+            .collect();
+        Keys { bindings }
+    }
+
+    fn page_text(help: &HelpModal, page: usize) -> String {
+        help.page_columns(page)
+            .iter()
+            .flatten()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn pages_contain_every_binding() {
+        let help = HelpModal::from_keymap("Keys", &keys(20)).with_rows_per_column(4);
+        // 20 bindings in 5 groups of 4, plus 4 separators = 24 rows,
+        // 8 rows per page.
+        assert_eq!(help.page_count(), 3);
+
+        let all: String = (0..help.page_count())
+            .map(|p| page_text(&help, p))
+            .collect::<Vec<_>>()
+            .join("\n");
+        for i in 0..20 {
+            let c = char::from(b'a' + i as u8);
+            assert!(
+                all.contains(&format!("{c:<12}action {i}")),
+                "binding {i} missing"
+            );
+        }
+    }
+
+    #[test]
+    fn disabled_bindings_are_omitted() {
+        let mut map = keys(3);
+        map.bindings[1].enabled = false;
+        let help = HelpModal::from_keymap("Keys", &map);
+        let text = page_text(&help, 0);
+        assert!(text.contains("action 0"));
+        assert!(!text.contains("action 1"));
+    }
+
+    #[test]
+    fn navigation_moves_between_pages() {
+        let mut help = HelpModal::from_keymap("Keys", &keys(20)).with_rows_per_column(4);
+        assert_eq!(help.page(), 0);
+
+        help.update(key(KeyCode::Right));
+        assert_eq!(help.page(), 1);
+        assert!(page_text(&help, help.page()).contains("action 7"));
+
+        help.update(key(KeyCode::End));
+        assert_eq!(help.page(), 2);
+        help.update(key(KeyCode::Right));
+        assert_eq!(help.page(), 2);
+
+        help.update(key(KeyCode::Left));
+        assert_eq!(help.page(), 1);
+    }
+
+    #[test]
+    fn escape_requests_close() {
+        let mut help = HelpModal::new("Keys", vec![]);
+        let cmd = help.update(key(KeyCode::Esc));
+        assert!(matches!(cmd.into_message(), Some(Message::Close)));
+    }
+
+    #[test]
+    fn view_renders_two_columns_and_indicator() {
+        let help = HelpModal::from_keymap("Keys", &keys(20)).with_size(100, 100);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 8)).unwrap();
+        terminal.draw(|f| help.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..8)
+            .map(|y| (0..60).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+
+        // Body rows per column follow the measured area.
+        assert!(help.page_count() > 1);
+        assert!(rows[1].contains("action 0"), "{rows:?}");
+        assert!(rows.iter().any(|r| r.contains("●")), "{rows:?}");
+    }
+}
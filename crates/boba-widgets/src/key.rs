@@ -1,5 +1,8 @@
 //! Key binding definitions and key map trait for help display integration.
 
+use std::fmt;
+use std::str::FromStr;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// A key binding that maps one or more key combinations to a described action.
@@ -8,6 +11,9 @@ pub struct Binding {
     pub keys: Vec<KeyCombination>,
     /// A human-readable description of the action this binding performs.
     pub description: String,
+    /// A longer description for the full help, if it differs from
+    /// `description`.
+    pub long_description: Option<String>,
     /// Whether this binding is currently active. Disabled bindings never match.
     pub enabled: bool,
 }
@@ -27,6 +33,7 @@ impl Binding {
         Self {
             keys: vec![key],
             description: description.into(),
+            long_description: None,
             enabled: true,
         }
     }
@@ -36,6 +43,7 @@ impl Binding {
         Self {
             keys,
             description: description.into(),
+            long_description: None,
             enabled: true,
         }
     }
@@ -56,6 +64,32 @@ impl Binding {
         self.enabled = enabled;
         self
     }
+
+    /// Set a longer description shown in the full help instead of
+    /// `description`, which stays in the short help line.
+    pub fn with_long_help(mut self, description: impl Into<String>) -> Self {
+        self.long_description = Some(description.into());
+        self
+    }
+
+    /// The description for the full help: the long one if set.
+    pub fn long_help(&self) -> &str {
+        self.long_description
+            .as_deref()
+            .unwrap_or(&self.description)
+    }
+}
+
+/// How key combinations are written in help text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyDisplayStyle {
+    /// Names joined with `+`, as parsed by [`KeyCombination::parse`]
+    /// (e.g. "ctrl+s", "enter"). The default.
+    #[default]
+    Text,
+    /// Glyphs as on macOS menus (e.g. "⌃s", "⏎", "⌘q"). Super is shown
+    /// as ⌘.
+    Symbols,
 }
 
 impl KeyCombination {
@@ -95,8 +129,210 @@ impl KeyCombination {
     pub fn with_modifiers(code: KeyCode, modifiers: KeyModifiers) -> Self {
         Self { code, modifiers }
     }
+
+    /// Parse a human-readable key string such as `"ctrl+shift+k"`.
+    ///
+    /// The string is a `+`-separated list of zero or more modifiers (`ctrl`,
+    /// `alt`, `shift`, `super`) followed by a key. The key is either a
+    /// single character or a named key: `enter`, `tab`, `backtab`, `esc`,
+    /// `space`, `backspace`, `delete`, `insert`, `home`, `end`, `pageup`,
+    /// `pagedown`, `up`, `down`, `left`, `right` or `f1`–`f12`. Modifier and
+    /// key names are case-insensitive; single characters keep their case.
+    /// `"+"` and `"ctrl++"` name the plus key itself.
+    ///
+    /// The [`Display`](fmt::Display) impl produces the same canonical form,
+    /// so parsed combinations round-trip.
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        // A trailing `+` after a separator (or on its own) is the plus key.
+        let (mods, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(mods) = s.strip_suffix("++") {
+            (mods, "+")
+        } else {
+            match s.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", s),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        if !mods.is_empty() {
+            for name in mods.split('+') {
+                modifiers |= match name.trim().to_ascii_lowercase().as_str() {
+                    "ctrl" | "control" => KeyModifiers::CONTROL,
+                    "alt" => KeyModifiers::ALT,
+                    "shift" => KeyModifiers::SHIFT,
+                    "super" => KeyModifiers::SUPER,
+                    _ => return Err(ParseError::UnknownModifier(name.to_string())),
+                };
+            }
+        }
+
+        let code = parse_key_code(key.trim()).ok_or_else(|| ParseError::UnknownKey(key.into()))?;
+        Ok(Self { code, modifiers })
+    }
+}
+
+fn parse_key_code(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let n: u8 = lower.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            KeyCode::F(n)
+        }
+    };
+    Some(code)
+}
+
+impl KeyCombination {
+    /// Format the combination for display in help text.
+    ///
+    /// ```
+    /// use boba_widgets::key::{KeyCombination, KeyDisplayStyle};
+    /// use crossterm::event::KeyCode;
+    ///
+    /// let save = KeyCombination::ctrl(KeyCode::Char('s'));
+    /// assert_eq!(save.display(KeyDisplayStyle::Text), "ctrl+s");
+    /// assert_eq!(save.display(KeyDisplayStyle::Symbols), "⌃s");
+    /// ```
+    pub fn display(&self, style: KeyDisplayStyle) -> String {
+        if style == KeyDisplayStyle::Text {
+            return self.to_string();
+        }
+        let mut out = String::new();
+        for (modifier, glyph) in [
+            (KeyModifiers::CONTROL, '⌃'),
+            (KeyModifiers::ALT, '⌥'),
+            (KeyModifiers::SHIFT, '⇧'),
+            (KeyModifiers::SUPER, '⌘'),
+        ] {
+            if self.modifiers.contains(modifier) {
+                out.push(glyph);
+            }
+        }
+        let glyph = match self.code {
+            KeyCode::Enter => '⏎',
+            KeyCode::Tab => '⇥',
+            KeyCode::BackTab => '⇤',
+            KeyCode::Esc => '⎋',
+            KeyCode::Char(' ') => '␣',
+            KeyCode::Backspace => '⌫',
+            KeyCode::Delete => '⌦',
+            KeyCode::Home => '↖',
+            KeyCode::End => '↘',
+            KeyCode::PageUp => '⇞',
+            KeyCode::PageDown => '⇟',
+            KeyCode::Up => '↑',
+            KeyCode::Down => '↓',
+            KeyCode::Left => '←',
+            KeyCode::Right => '→',
+            _ => {
+                let bare = KeyCombination::new(self.code);
+                out.push_str(&bare.to_string());
+                return out;
+            }
+        };
+        out.push(glyph);
+        out
+    }
 }
 
+impl FromStr for KeyCombination {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for KeyCombination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+            (KeyModifiers::SUPER, "super"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Error returned by [`KeyCombination::parse`] for malformed key strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The key string was empty.
+    Empty,
+    /// A modifier name was not one of `ctrl`, `alt`, `shift` or `super`.
+    UnknownModifier(String),
+    /// The key was neither a single character nor a known key name.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => f.write_str("empty key string"),
+            ParseError::UnknownModifier(m) => write!(f, "unknown key modifier {m:?}"),
+            ParseError::UnknownKey(k) => write!(f, "unknown key {k:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Trait for types that define key bindings, enabling integration with the
 /// [`Help`](crate::help::Help) formatter.
 pub trait KeyMap {
@@ -105,3 +341,85 @@ pub trait KeyMap {
     /// Return bindings grouped by category for the full help overlay.
     fn full_help(&self) -> Vec<Vec<&Binding>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips() {
+        for s in [
+            "k",
+            "ctrl+shift+k",
+            "alt+enter",
+            "ctrl+alt+shift+super+f12",
+            "space",
+            "shift+tab",
+            "esc",
+            "pagedown",
+            "ctrl++",
+            "+",
+            "K",
+        ] {
+            let combo = KeyCombination::parse(s).unwrap();
+            assert_eq!(combo.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_builds_expected_combination() {
+        assert_eq!(
+            KeyCombination::parse("ctrl+shift+k").unwrap(),
+            KeyCombination::with_modifiers(
+                KeyCode::Char('k'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )
+        );
+        assert_eq!(
+            "Ctrl+Up".parse::<KeyCombination>().unwrap(),
+            KeyCombination::ctrl(KeyCode::Up)
+        );
+        assert_eq!(
+            KeyCombination::parse("f1").unwrap(),
+            KeyCombination::new(KeyCode::F(1))
+        );
+        // Modifier order is normalised on display
+        assert_eq!(
+            KeyCombination::parse("shift+ctrl+x").unwrap().to_string(),
+            "ctrl+shift+x"
+        );
+    }
+
+    #[test]
+    fn symbols_display_uses_glyphs() {
+        let cases = [
+            ("enter", "⏎"),
+            ("ctrl+s", "⌃s"),
+            ("super+q", "⌘q"),
+            ("shift+tab", "⇧⇥"),
+            ("ctrl+alt+up", "⌃⌥↑"),
+            ("f5", "f5"),
+        ];
+        for (key, glyphs) in cases {
+            let combo = KeyCombination::parse(key).unwrap();
+            assert_eq!(combo.display(KeyDisplayStyle::Symbols), glyphs);
+            assert_eq!(combo.display(KeyDisplayStyle::Text), key);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert_eq!(KeyCombination::parse(""), Err(ParseError::Empty));
+        assert_eq!(
+            KeyCombination::parse("hyper+k"),
+            Err(ParseError::UnknownModifier("hyper".into()))
+        );
+        assert_eq!(
+            KeyCombination::parse("ctrl+banana"),
+            Err(ParseError::UnknownKey("banana".into()))
+        );
+        assert!(KeyCombination::parse("f13").is_err());
+        assert!(KeyCombination::parse("ctrl+").is_err());
+        assert!(KeyCombination::parse("ctrl++k").is_err());
+    }
+}
@@ -12,10 +12,11 @@
 //! | [`dropdown`] | Standalone dropdown overlay for selectable items |
 //! | [`filepicker`] | Interactive file and directory picker |
 //! | [`help`] | Key-binding help formatting utilities |
+//! | [`help_modal`] | Paginated two-column full-help overlay built from a key map |
 //! | [`list`] | Scrollable, filterable list |
 //! | [`modal`] | Generic modal/dialog overlay with action buttons |
 //! | [`paginator`] | Page-dot indicator for multi-page views |
-//! | [`progress`] | Determinate progress bar |
+//! | [`progress`] | Determinate progress bar, and stacked bars for parallel tasks |
 //! | [`search`] | Inline search bar with match navigation |
 //! | [`select`] | Single-choice selection menu (composes [`dropdown`]) |
 //! | [`spinner`] | Animated indeterminate spinner |
@@ -39,14 +40,17 @@
 //!
 //! | Module | Description |
 //! |--------|-------------|
+//! | [`clock`] | [`Clock`](clock::Clock) trait with system and manual clocks for time-based widgets |
 //! | [`focus`] | [`FocusGroup<N>`](focus::FocusGroup) — cycle keyboard focus across N components with Tab/Shift+Tab (see `examples/input_form.rs`) |
 //! | [`key`] | Key-binding helpers and constants |
+//! | [`matcher`] | Case-insensitive substring matching and match highlighting for filters |
 //! | [`overlay`] | Overlay positioning and rendering utilities |
 //! | [`runeutil`] | Unicode-aware string width and truncation utilities |
 //! | [`selection`] | [`SelectionState`](selection::SelectionState) for shared list navigation |
 //! | [`text_edit`] | **Deprecated** – [`TextEditState`](text_edit::TextEditState) for shared single-line text editing; use [`TextArea`](text_area::TextArea) with `.with_single_line(true)` instead |
 
 pub mod chrome;
+pub mod clock;
 #[cfg(feature = "syntax-highlighting")]
 pub mod code_block;
 pub mod cursor;
@@ -54,11 +58,13 @@ pub mod dropdown;
 pub mod filepicker;
 pub mod focus;
 pub mod help;
+pub mod help_modal;
 pub mod junctions;
 pub mod key;
 pub mod list;
 #[cfg(feature = "markdown")]
 pub mod markdown;
+pub mod matcher;
 pub mod modal;
 pub mod overlay;
 pub mod paginator;
@@ -5,14 +5,19 @@
 //! delegates, spinner integration, and item descriptions.
 
 use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use boba_core::key_sequence::KeySequenceTracker;
 use boba_core::subscription::Subscription;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::clock::{Clock, SystemClock};
 use crate::key::Binding;
+use crate::matcher;
 use crate::selection::SelectionState;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -23,6 +28,13 @@ use ratatui::widgets::{
 use ratatui::Frame;
 use std::cell::Cell;
 
+/// Extra items rendered past the visible window so multi-line delegates
+/// still fill the list area.
+const LIST_OVERSCAN: usize = 2;
+
+/// How long a type-ahead prefix is kept between key presses.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Trait for types that can be displayed in a list.
 ///
 /// Any domain type can implement this trait to be used directly with [`List`]
@@ -145,6 +157,10 @@ pub enum Message {
     SpinnerTick,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// Configurable key bindings for the list component.
 ///
 /// Each field is a [`Binding`] that maps one or more
@@ -261,6 +277,7 @@ pub struct List<I: Item> {
     items: Vec<I>,
     state: ListState,
     focus: bool,
+    disabled: bool,
     style: ListStyle,
     block: Option<Block<'static>>,
     title: String,
@@ -277,8 +294,18 @@ pub struct List<I: Item> {
     key_bindings: ListKeyBindings,
     multi_select: bool,
     selected_set: BTreeSet<usize>,
+    type_ahead: bool,
+    /// Prefix typed so far and when its last character arrived.
+    jump_prefix: String,
+    jump_at: Option<Instant>,
+    clock: Arc<dyn Clock>,
+    /// Whether two items are the same logical item, for keeping the
+    /// selection across [`set_items`](List::set_items).
+    identity: Option<IdentityFn<I>>,
 }
 
+type IdentityFn<I> = Box<dyn Fn(&I, &I) -> bool + Send>;
+
 /// Style configuration for the list.
 #[derive(Debug, Clone)]
 pub struct ListStyle {
@@ -288,6 +315,15 @@ pub struct ListStyle {
     pub selected: Style,
     /// Symbol rendered to the left of the selected item (e.g. "▸ ").
     pub highlight_symbol: String,
+    /// Symbol used instead of `highlight_symbol` while the list is
+    /// unfocused. `None` keeps `highlight_symbol`.
+    pub unfocused_highlight_symbol: Option<String>,
+    /// Marker before checked items in multi-select mode (e.g. "[x] ").
+    pub checked_symbol: String,
+    /// Marker before unchecked items in multi-select mode (e.g. "[ ] ").
+    pub unchecked_symbol: String,
+    /// Style patched over every item while the list is disabled.
+    pub disabled: Style,
 }
 
 impl Default for ListStyle {
@@ -298,6 +334,12 @@ impl Default for ListStyle {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
             highlight_symbol: "▸ ".to_string(),
+            unfocused_highlight_symbol: None,
+            checked_symbol: "[x] ".to_string(),
+            unchecked_symbol: "[ ] ".to_string(),
+            disabled: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
         }
     }
 }
@@ -317,6 +359,7 @@ impl<I: Item> List<I> {
             items,
             state,
             focus: false,
+            disabled: false,
             style: ListStyle::default(),
             block: None,
             title: String::new(),
@@ -333,6 +376,11 @@ impl<I: Item> List<I> {
             key_bindings: ListKeyBindings::default(),
             multi_select: false,
             selected_set: BTreeSet::new(),
+            type_ahead: false,
+            jump_prefix: String::new(),
+            jump_at: None,
+            clock: Arc::new(SystemClock),
+            identity: None,
         }
     }
 
@@ -390,6 +438,47 @@ impl<I: Item> List<I> {
         self
     }
 
+    /// Enable type-ahead jumping. When enabled and the filter is not being
+    /// edited, typing printable characters moves the cursor to the next item
+    /// whose `filter_value` starts with the typed prefix (case-insensitive).
+    /// The prefix resets after a second without typing.
+    ///
+    /// Type-ahead takes precedence over letter key bindings such as `j`/`k`,
+    /// so navigate with the arrow keys when it is on. The filter key and
+    /// Space in multi-select mode keep their usual meaning.
+    pub fn with_type_ahead(mut self, enabled: bool) -> Self {
+        self.type_ahead = enabled;
+        self
+    }
+
+    /// Choose whether navigation wraps around the ends (default: true).
+    ///
+    /// When enabled, Down on the last item selects the first and Up on the
+    /// first selects the last. When disabled, they stop at the first or
+    /// last item. Page and half-page moves always stop at the ends.
+    pub fn with_wrap_navigation(mut self, wrap: bool) -> Self {
+        self.selection.set_wrap(wrap);
+        self
+    }
+
+    /// Measure the type-ahead timeout with the given clock instead of the
+    /// system clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Identify items by the key `f` returns, so [`set_items`](Self::set_items)
+    /// keeps the same logical item selected even if it moved.
+    ///
+    /// ```ignore
+    /// let list = List::new(files).with_identity(|file: &File| file.path.clone());
+    /// ```
+    pub fn with_identity<K: PartialEq>(mut self, f: impl Fn(&I) -> K + Send + 'static) -> Self {
+        self.identity = Some(Box::new(move |a, b| f(a) == f(b)));
+        self
+    }
+
     /// Get the set of selected item indices (multi-select mode).
     /// Returns an empty set when multi-select is disabled.
     pub fn selected_items(&self) -> &BTreeSet<usize> {
@@ -431,6 +520,17 @@ impl<I: Item> List<I> {
         self.focus = false;
     }
 
+    /// Enable or disable the list. A disabled list ignores key presses and
+    /// renders its items with [`ListStyle::disabled`].
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    /// Whether the list is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
     /// Returns the selected index in the original (unfiltered) items list.
     pub fn selected(&self) -> Option<usize> {
         if self.filtered_indices.is_empty() {
@@ -440,8 +540,9 @@ impl<I: Item> List<I> {
         }
     }
 
-    /// Programmatically set the selected index (in the original items list).
-    /// If the index is out of range, it will be clamped.
+    /// Programmatically set the selected index (in the original items list)
+    /// and scroll it into view. If the index is out of range, it will be
+    /// clamped.
     pub fn set_selected(&mut self, index: usize) {
         if self.filtered_indices.is_empty() {
             self.state.select(None);
@@ -470,21 +571,48 @@ impl<I: Item> List<I> {
                 self.selection.select(pos);
             }
         }
+        self.ensure_selected_visible();
+    }
+
+    /// Scroll so the selected item is within the window shown by the last
+    /// render.
+    ///
+    /// Navigation keys, [`set_selected`](Self::set_selected) and
+    /// [`set_items`](Self::set_items) already do this.
+    pub fn ensure_selected_visible(&mut self) {
+        self.sync_selection_visible();
+        self.selection.select(self.selection.cursor());
         self.sync_list_state();
     }
 
+    /// Position, among the items the filter shows, of the first item in
+    /// view.
+    pub fn scroll_offset(&self) -> usize {
+        self.selection.offset()
+    }
+
     /// Return a reference to the currently selected item, if any.
     pub fn selected_item(&self) -> Option<&I> {
         self.selected().and_then(|i| self.items.get(i))
     }
 
     /// Replace all items, rebuilding the filter and clamping the selection.
+    ///
+    /// With [`with_identity`](Self::with_identity), the previously selected
+    /// item stays selected if it is still present.
     pub fn set_items(&mut self, items: Vec<I>) {
+        let kept = match (&self.identity, self.selected_item()) {
+            (Some(same), Some(selected)) => items.iter().position(|item| same(selected, item)),
+            _ => None,
+        };
         self.items = items;
         self.selected_set.clear();
         self.rebuild_filtered_indices();
         self.selection.set_count(self.filtered_indices.len());
-        self.sync_list_state();
+        match kept {
+            Some(index) => self.set_selected(index),
+            None => self.ensure_selected_visible(),
+        }
     }
 
     // --- Filtering ---
@@ -502,17 +630,25 @@ impl<I: Item> List<I> {
         }
     }
 
+    /// Positions (in chars) of the current filter's match within item
+    /// `index`'s [`filter_value`](Item::filter_value), or `None` if the
+    /// filter hides it. Empty without a filter.
+    ///
+    /// Delegates can pass these to [`matcher::highlight`] to highlight the
+    /// match.
+    pub fn filter_match(&self, index: usize) -> Option<Vec<usize>> {
+        let item = self.items.get(index)?;
+        matcher::find(item.filter_value(), self.filter_value())
+    }
+
     fn rebuild_filtered_indices(&mut self) {
         match &self.filter {
             Some(f) if !f.is_empty() => {
-                let lower = f.to_lowercase();
-                self.filtered_indices = self
-                    .items
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, item)| item.filter_value().to_lowercase().contains(&lower))
-                    .map(|(i, _)| i)
-                    .collect();
+                self.filtered_indices =
+                    matcher::filter(self.items.iter().map(Item::filter_value), f)
+                        .into_iter()
+                        .map(|(i, _)| i)
+                        .collect();
             }
             _ => {
                 self.filtered_indices = (0..self.items.len()).collect();
@@ -624,6 +760,62 @@ impl<I: Item> List<I> {
         self.sync_list_state();
     }
 
+    /// Extend the type-ahead prefix with `c` and move the cursor to the
+    /// best match. Returns `true` if the cursor moved.
+    fn type_ahead_jump(&mut self, c: char) -> bool {
+        let now = self.clock.now();
+        let expired = self
+            .jump_at
+            .is_none_or(|at| now.saturating_duration_since(at) > TYPE_AHEAD_TIMEOUT);
+        if expired {
+            self.jump_prefix.clear();
+        }
+        self.jump_prefix.extend(c.to_lowercase());
+        self.jump_at = Some(now);
+
+        let count = self.filtered_indices.len();
+        if count == 0 {
+            return false;
+        }
+        let cursor = self.selection.cursor();
+        let starts_with = |pos: usize, prefix: &str| {
+            let i = self.filtered_indices[pos];
+            self.items[i]
+                .filter_value()
+                .to_lowercase()
+                .starts_with(prefix)
+        };
+        // A fresh prefix looks past the current item so repeated presses of
+        // the same letter cycle; a longer prefix may still match the current
+        // item, so it refines in place.
+        let first_char = self.jump_prefix.chars().count() == 1;
+        let start = if first_char { cursor + 1 } else { cursor };
+        let mut found = (0..count)
+            .map(|n| (start + n) % count)
+            .find(|&pos| starts_with(pos, &self.jump_prefix));
+        // Typing the same letter again ("bb") cycles through items starting
+        // with it when nothing matches the doubled prefix.
+        if found.is_none() {
+            let mut chars = self.jump_prefix.chars();
+            if let Some(first) = chars.next() {
+                if chars.all(|ch| ch == first) {
+                    let single = first.to_string();
+                    found = (0..count)
+                        .map(|n| (cursor + 1 + n) % count)
+                        .find(|&pos| starts_with(pos, &single));
+                }
+            }
+        }
+        match found {
+            Some(pos) if pos != cursor => {
+                self.selection.select(pos);
+                self.sync_list_state();
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn select_first(&mut self) {
         self.sync_selection_visible();
         self.selection.home();
@@ -689,7 +881,7 @@ impl<I: Item> Component for List<I> {
                 }
                 Command::none()
             }
-            Message::KeyPress(key) if self.focus && self.filtering => {
+            Message::KeyPress(key) if self.focus && !self.disabled && self.filtering => {
                 match key.code {
                     KeyCode::Esc => {
                         self.deactivate_filter();
@@ -737,7 +929,21 @@ impl<I: Item> Component for List<I> {
                     _ => Command::none(),
                 }
             }
-            Message::KeyPress(key) if self.focus => {
+            Message::KeyPress(key) if self.focus && !self.disabled => {
+                if let KeyCode::Char(c) = key.code {
+                    let plain = (key.modifiers - KeyModifiers::SHIFT).is_empty();
+                    let reserved =
+                        self.key_bindings.filter.matches(&key) || (self.multi_select && c == ' ');
+                    if self.type_ahead && plain && !reserved && !c.is_control() {
+                        self.key_seq.clear();
+                        if self.type_ahead_jump(c) {
+                            if let Some(i) = self.selected() {
+                                return Command::message(Message::Select(i));
+                            }
+                        }
+                        return Command::none();
+                    }
+                }
                 // Check for gg sequence (vim go-to-first)
                 if key.code == KeyCode::Char('g') && key.modifiers == KeyModifiers::NONE {
                     if let Some(KeyCode::Char('g')) =
@@ -755,6 +961,11 @@ impl<I: Item> Component for List<I> {
                 }
                 // Any other key clears a pending sequence
                 self.key_seq.clear();
+                if key.code == KeyCode::Esc && self.filter.is_some() {
+                    // Esc clears a filter that was applied with Enter.
+                    self.deactivate_filter();
+                    return Command::message(Message::ToggleFilter);
+                }
                 if self.key_bindings.up.matches(&key) {
                     self.select_prev();
                     if let Some(i) = self.selected() {
@@ -927,21 +1138,39 @@ impl<I: Item> Component for List<I> {
         });
         chunk_idx += 1;
 
-        let items: Vec<ListItem> = self
-            .filtered_indices
+        // Only ask the delegate to render the window of items that can be on
+        // screen. Every item is at least one line tall, so the window never
+        // needs more than `height` items past its start, plus a little
+        // overscan so ratatui can scroll within it for multi-line items.
+        let window_height = list_area.height.max(1) as usize;
+        let cursor = self.selection.cursor();
+        let mut start = self.selection.offset().min(cursor);
+        if cursor >= start + window_height {
+            start = cursor + 1 - window_height;
+        }
+        let end = (start + window_height + LIST_OVERSCAN).min(self.filtered_indices.len());
+        let start = start.min(end);
+
+        let check_indent = " ".repeat(
+            Span::raw(self.style.checked_symbol.as_str())
+                .width()
+                .max(Span::raw(self.style.unchecked_symbol.as_str()).width()),
+        );
+        let items: Vec<ListItem> = self.filtered_indices[start..end]
             .iter()
             .enumerate()
-            .map(|(pos, &i)| {
-                let selected = !self.filtered_indices.is_empty() && pos == self.selection.cursor();
+            .map(|(window_pos, &i)| {
+                let pos = start + window_pos;
+                let selected = pos == cursor;
                 let lines = self
                     .delegate
                     .render(&self.items[i], i, selected, list_area.width);
                 // In multi-select mode, prepend checkbox indicators to the first line
                 let lines = if self.multi_select {
                     let check = if self.selected_set.contains(&i) {
-                        "[x] "
+                        self.style.checked_symbol.as_str()
                     } else {
-                        "[ ] "
+                        self.style.unchecked_symbol.as_str()
                     };
                     lines
                         .into_iter()
@@ -953,7 +1182,7 @@ impl<I: Item> Component for List<I> {
                                 Line::from(spans)
                             } else {
                                 // Indent continuation lines to align with checkbox text
-                                let mut spans = vec![Span::raw("    ")];
+                                let mut spans = vec![Span::raw(check_indent.clone())];
                                 spans.extend(line.spans);
                                 Line::from(spans)
                             }
@@ -966,12 +1195,28 @@ impl<I: Item> Component for List<I> {
             })
             .collect();
 
+        let highlight = if self.disabled {
+            self.style.disabled
+        } else {
+            self.style.selected
+        };
+        let symbol = match &self.style.unfocused_highlight_symbol {
+            Some(symbol) if !self.focus => symbol,
+            _ => &self.style.highlight_symbol,
+        };
         let list = RatatuiList::new(items)
-            .highlight_style(self.style.selected)
-            .highlight_symbol(self.style.highlight_symbol.as_str())
+            .highlight_style(highlight)
+            .highlight_symbol(symbol.as_str())
             .highlight_spacing(HighlightSpacing::Always);
 
-        frame.render_stateful_widget(list, list_area, &mut self.state.clone());
+        // Remap the selection into window-relative coordinates.
+        let mut state =
+            ListState::default().with_selected(self.state.selected().map(|sel| sel - start));
+        frame.render_stateful_widget(list, list_area, &mut state);
+        if self.disabled {
+            // Delegates style their own spans; dim everything on top.
+            frame.buffer_mut().set_style(list_area, self.style.disabled);
+        }
 
         // Render filter display indicator at bottom
         if has_filter_display {
@@ -1013,6 +1258,11 @@ impl<I: Item> Component for List<I> {
     fn focused(&self) -> bool {
         self.focus
     }
+
+    /// `true` while a filter is being typed or applied; Esc clears it.
+    fn wants_escape(&self) -> bool {
+        self.filter.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -1138,6 +1388,119 @@ mod tests {
         assert!(list.selected_items().is_empty());
     }
 
+    struct CountingDelegate(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl ItemDelegate<String> for CountingDelegate {
+        fn render<'a>(
+            &'a self,
+            item: &'a String,
+            _index: usize,
+            _selected: bool,
+            _width: u16,
+        ) -> Vec<Line<'a>> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            vec![Line::raw(item.as_str())]
+        }
+    }
+
+    fn render_list(list: &List<String>, width: u16, height: u16) -> String {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| list.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let mut out = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                out.push_str(buf[(x, y)].symbol());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn view_renders_bounded_number_of_items() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        for total in [100, 100_000] {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let items = (0..total).map(|i| format!("item {i}")).collect();
+            let list = List::new(items).with_delegate(CountingDelegate(calls.clone()));
+            render_list(&list, 20, 10);
+            assert!(calls.load(Ordering::SeqCst) <= 10 + LIST_OVERSCAN);
+        }
+    }
+
+    #[test]
+    fn view_highlights_selection_deep_in_large_list() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let items = (0..100_000).map(|i| format!("item {i}")).collect();
+        let mut list = List::new(items).with_delegate(CountingDelegate(calls.clone()));
+        render_list(&list, 20, 10);
+        list.set_selected(76_543);
+        calls.store(0, Ordering::SeqCst);
+
+        let out = render_list(&list, 20, 10);
+        assert!(calls.load(Ordering::SeqCst) <= 10 + LIST_OVERSCAN);
+        let selected_line = out.lines().find(|l| l.contains("▸")).unwrap();
+        assert!(selected_line.contains("item 76543"));
+    }
+
+    #[test]
+    fn view_prefix_reflects_focus_cursor_and_checked_state() {
+        let mut list = List::new(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .with_multi_select(true)
+            .with_style(ListStyle {
+                highlight_symbol: "> ".to_string(),
+                unfocused_highlight_symbol: Some("- ".to_string()),
+                checked_symbol: "✓ ".to_string(),
+                unchecked_symbol: "· ".to_string(),
+                ..ListStyle::default()
+            });
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Down)));
+        list.update(Message::KeyPress(key(KeyCode::Char(' ')))); // check "b"
+        list.update(Message::KeyPress(key(KeyCode::Down)));
+
+        // Focused: cursor on unchecked "c", "b" checked without the cursor.
+        assert_eq!(render_list(&list, 6, 3), "  · a \n  ✓ b \n> · c \n");
+
+        list.update(Message::KeyPress(key(KeyCode::Up)));
+        assert_eq!(render_list(&list, 6, 3), "  · a \n> ✓ b \n  · c \n");
+
+        list.blur();
+        assert_eq!(render_list(&list, 6, 3), "  · a \n- ✓ b \n  · c \n");
+    }
+
+    #[test]
+    fn disabled_list_ignores_keys_and_renders_dimmed() {
+        let mut list = List::new(vec!["a".to_string(), "b".to_string()]);
+        list.focus();
+        list.set_disabled(true);
+        assert!(list.is_disabled());
+
+        let cmd = list.update(Message::KeyPress(key(KeyCode::Down)));
+        assert!(cmd.is_none());
+        assert_eq!(list.selected(), Some(0));
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(10, 2)).unwrap();
+        terminal.draw(|f| list.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        assert_eq!(buf[(2, 0)].fg, Color::DarkGray);
+        assert!(buf[(2, 0)].modifier.contains(Modifier::DIM));
+
+        list.set_disabled(false);
+        list.update(Message::KeyPress(key(KeyCode::Down)));
+        assert_eq!(list.selected(), Some(1));
+    }
+
     #[test]
     fn set_items_clears_selections() {
         let mut list = List::new(vec!["a".to_string(), "b".to_string()]).with_multi_select(true);
@@ -1148,4 +1511,194 @@ mod tests {
         list.set_items(vec!["x".to_string(), "y".to_string()]);
         assert!(list.selected_items().is_empty());
     }
+
+    #[test]
+    fn set_items_keeps_selected_item_by_identity() {
+        let mut list = List::new(vec!["a", "b", "c"]).with_identity(|s: &&str| *s);
+        list.set_selected(1);
+        assert_eq!(list.selected_item(), Some(&"b"));
+
+        list.set_items(vec!["c", "x", "a", "b"]);
+        assert_eq!(list.selected(), Some(3));
+        assert_eq!(list.selected_item(), Some(&"b"));
+    }
+
+    #[test]
+    fn set_selected_scrolls_selection_into_view() {
+        let items: Vec<String> = (0..100).map(|i| format!("item {i}")).collect();
+        let mut list = List::new(items);
+        render_list(&list, 20, 5);
+
+        list.set_selected(50);
+        assert_eq!(list.scroll_offset(), 46);
+        let out = render_list(&list, 20, 5);
+        assert!(
+            out.lines()
+                .any(|l| l.contains("▸") && l.contains("item 50")),
+            "{out}"
+        );
+
+        list.set_selected(3);
+        assert_eq!(list.scroll_offset(), 3);
+    }
+
+    #[test]
+    fn set_items_scrolls_kept_selection_into_view() {
+        let items: Vec<String> = (0..100).map(|i| format!("item {i}")).collect();
+        let mut list = List::new(items.clone()).with_identity(|s: &String| s.clone());
+        render_list(&list, 20, 5);
+        list.set_selected(10);
+        assert_eq!(list.scroll_offset(), 6);
+
+        // The selected item moves far down the new list.
+        let mut moved: Vec<String> = (0..80).map(|i| format!("new {i}")).collect();
+        moved.extend(items);
+        list.set_items(moved);
+        assert_eq!(list.selected(), Some(90));
+        assert_eq!(list.scroll_offset(), 86);
+    }
+
+    #[test]
+    fn set_items_without_identity_keeps_position() {
+        let mut list = List::new(vec!["a", "b", "c"]);
+        list.set_selected(1);
+        list.set_items(vec!["c", "x", "a", "b"]);
+        assert_eq!(list.selected_item(), Some(&"x"));
+
+        // A removed item falls back to the clamped position.
+        let mut list = List::new(vec!["a", "b", "c"]).with_identity(|s: &&str| *s);
+        list.set_selected(2);
+        list.set_items(vec!["a", "b"]);
+        assert_eq!(list.selected_item(), Some(&"b"));
+    }
+
+    fn fruits() -> Vec<String> {
+        ["apple", "avocado", "Blueberry", "banana", "cherry"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn type_ahead_jumps_to_first_matching_item() {
+        let mut list = List::new(fruits()).with_type_ahead(true);
+        list.focus();
+        let cmd = list.update(Message::KeyPress(key(KeyCode::Char('b'))));
+        assert!(matches!(cmd.into_message(), Some(Message::Select(2))));
+        assert_eq!(list.selected(), Some(2));
+    }
+
+    #[test]
+    fn type_ahead_refines_with_quick_second_key() {
+        let clock = crate::clock::ManualClock::new();
+        let mut list = List::new(fruits())
+            .with_type_ahead(true)
+            .with_clock(clock.clone());
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Char('b'))));
+        clock.advance(Duration::from_millis(200));
+        list.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert_eq!(list.selected(), Some(3)); // "banana"
+
+        // After the timeout the prefix starts over.
+        clock.advance(Duration::from_secs(2));
+        list.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert_eq!(list.selected(), Some(0)); // "apple"
+    }
+
+    #[test]
+    fn type_ahead_repeated_letter_cycles() {
+        let mut list = List::new(fruits()).with_type_ahead(true);
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert_eq!(list.selected(), Some(1)); // past "apple" to "avocado"
+        list.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert_eq!(list.selected(), Some(0)); // wraps back to "apple"
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..key(KeyCode::Char(c))
+        }
+    }
+
+    fn numbered(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("item {i}")).collect()
+    }
+
+    #[test]
+    fn wrap_navigation_wraps_at_boundaries() {
+        let mut list = List::new(numbered(3));
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Up)));
+        assert_eq!(list.selected(), Some(2));
+        list.update(Message::KeyPress(key(KeyCode::Down)));
+        assert_eq!(list.selected(), Some(0));
+    }
+
+    #[test]
+    fn no_wrap_navigation_clamps_at_boundaries() {
+        let mut list = List::new(numbered(3)).with_wrap_navigation(false);
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Up)));
+        assert_eq!(list.selected(), Some(0));
+        list.update(Message::KeyPress(key(KeyCode::End)));
+        list.update(Message::KeyPress(key(KeyCode::Down)));
+        assert_eq!(list.selected(), Some(2));
+    }
+
+    #[test]
+    fn page_navigation_stops_at_the_ends() {
+        // Ten visible rows by default: pages move by 10, half pages by 5.
+        let mut list = List::new(numbered(25));
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::PageDown)));
+        list.update(Message::KeyPress(key(KeyCode::PageDown)));
+        list.update(Message::KeyPress(key(KeyCode::PageDown)));
+        assert_eq!(list.selected(), Some(24));
+        list.update(Message::KeyPress(key(KeyCode::PageDown)));
+        list.update(Message::KeyPress(ctrl('d')));
+        assert_eq!(list.selected(), Some(24));
+        list.update(Message::KeyPress(key(KeyCode::Home)));
+        list.update(Message::KeyPress(ctrl('u')));
+        assert_eq!(list.selected(), Some(0));
+
+        let mut list = List::new(numbered(25)).with_wrap_navigation(false);
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::End)));
+        list.update(Message::KeyPress(key(KeyCode::PageDown)));
+        list.update(Message::KeyPress(ctrl('d')));
+        assert_eq!(list.selected(), Some(24));
+        list.update(Message::KeyPress(key(KeyCode::Home)));
+        list.update(Message::KeyPress(key(KeyCode::PageUp)));
+        list.update(Message::KeyPress(ctrl('u')));
+        assert_eq!(list.selected(), Some(0));
+    }
+
+    #[test]
+    fn type_ahead_off_keeps_letter_bindings() {
+        let mut list = List::new(fruits());
+        list.focus();
+        list.update(Message::KeyPress(key(KeyCode::Char('j'))));
+        assert_eq!(list.selected(), Some(1));
+    }
+    #[test]
+    fn wants_escape_only_with_active_filter() {
+        let mut list = List::new(fruits());
+        list.focus();
+        assert!(!list.wants_escape());
+
+        list.update(Message::KeyPress(key(KeyCode::Char('/'))));
+        assert!(list.wants_escape());
+        list.update(Message::KeyPress(key(KeyCode::Char('b'))));
+        list.update(Message::KeyPress(key(KeyCode::Enter)));
+        // Applied but no longer being edited: still dismissable.
+        assert!(list.wants_escape());
+
+        let cmd = list.update(Message::KeyPress(key(KeyCode::Esc)));
+        assert!(matches!(cmd.into_message(), Some(Message::ToggleFilter)));
+        assert!(!list.is_filtered());
+        assert!(!list.wants_escape());
+    }
 }
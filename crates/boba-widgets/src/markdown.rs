@@ -253,10 +253,8 @@ impl Markdown {
                         Style::default().fg(self.style.secondary),
                     ));
                 }
-                Event::SoftBreak => {
-                    if !in_code_block {
-                        current_spans.push(Span::raw(" ".to_string()));
-                    }
+                Event::SoftBreak if !in_code_block => {
+                    current_spans.push(Span::raw(" ".to_string()));
                 }
                 Event::HardBreak => {
                     flush_line(&mut current_spans, &mut lines, &indent);
@@ -0,0 +1,118 @@
+//! Case-insensitive substring matching shared by the filterable widgets.
+//!
+//! [`List`](crate::list::List) and [`Dropdown`](crate::dropdown::Dropdown)
+//! filter their items with [`find`], so the same query keeps the same items
+//! in both. The matched character positions can be drawn with [`highlight`],
+//! which is also what a custom list delegate should use.
+
+use ratatui::style::Style;
+use ratatui::text::Span;
+
+/// Find `query` in `text`, ignoring case.
+///
+/// Returns the positions (in chars) of the characters of `text` that match,
+/// or `None` when `text` doesn't contain `query`. An empty query matches
+/// every text with no positions.
+///
+/// # Example
+///
+/// ```
+/// use boba_widgets::matcher::find;
+///
+/// assert_eq!(find("Hello World", "wor"), Some(vec![6, 7, 8]));
+/// assert_eq!(find("Hello", "xyz"), None);
+/// ```
+pub fn find(text: &str, query: &str) -> Option<Vec<usize>> {
+    if query.is_empty() {
+        return Some(Vec::new());
+    }
+    // Lowercasing can turn one char into several, so compare the expanded
+    // chars and remember which original char each one came from.
+    let lowered: Vec<(usize, char)> = text
+        .chars()
+        .enumerate()
+        .flat_map(|(i, c)| c.to_lowercase().map(move |l| (i, l)))
+        .collect();
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let start = lowered
+        .windows(needle.len())
+        .position(|window| window.iter().map(|&(_, c)| c).eq(needle.iter().copied()))?;
+    let mut positions: Vec<usize> = lowered[start..start + needle.len()]
+        .iter()
+        .map(|&(i, _)| i)
+        .collect();
+    positions.dedup();
+    Some(positions)
+}
+
+/// Match every text against `query`, keeping the index and match positions
+/// of those that contain it, in order.
+pub fn filter<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+    query: &str,
+) -> Vec<(usize, Vec<usize>)> {
+    texts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, text)| Some((i, find(text, query)?)))
+        .collect()
+}
+
+/// Split `text` into spans, patching `matched` over the characters at
+/// `positions` and using `base` for the rest.
+pub fn highlight(
+    text: &str,
+    positions: &[usize],
+    base: Style,
+    matched: Style,
+) -> Vec<Span<'static>> {
+    let matched = base.patch(matched);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let is_match = positions.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let style = if run_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { matched } else { base };
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn find_ignores_case_and_reports_char_positions() {
+        assert_eq!(find("Café Noir", "é n"), Some(vec![3, 4, 5]));
+        assert_eq!(find("abc", ""), Some(vec![]));
+        assert_eq!(
+            filter(["apple", "banana", "grape"], "ap"),
+            [(0, vec![0, 1]), (2, vec![2, 3])]
+        );
+    }
+
+    #[test]
+    fn highlight_groups_runs() {
+        let red = Style::default().fg(Color::Red);
+        let spans = highlight("banana", &[1, 2, 3], Style::default(), red);
+        let parts: Vec<(&str, Style)> = spans.iter().map(|s| (&*s.content, s.style)).collect();
+        assert_eq!(
+            parts,
+            [
+                ("b", Style::default()),
+                ("ana", red),
+                ("na", Style::default())
+            ]
+        );
+    }
+}
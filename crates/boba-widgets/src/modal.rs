@@ -2,10 +2,14 @@
 //!
 //! The modal renders as a centered overlay on top of existing content.
 //! It captures all input when visible (modal behavior) and supports
-//! keyboard navigation of action buttons.
+//! keyboard navigation of action buttons. A [`ModalQueue`] shows several
+//! modals one after another.
+
+use std::collections::VecDeque;
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -13,6 +17,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph, Wrap};
 use ratatui::Frame;
 
+use crate::focus::FocusGroup;
 use crate::overlay;
 
 /// Layout direction for action buttons.
@@ -40,6 +45,9 @@ pub struct Action {
     pub style: Option<Style>,
     /// Optional style override for this action when focused.
     pub focused_style: Option<Style>,
+    /// Disabled actions are skipped by navigation, ignore Enter and their
+    /// shortcut, and render with [`ModalStyle::disabled_action`].
+    pub disabled: bool,
 }
 
 impl Action {
@@ -52,6 +60,7 @@ impl Action {
             shortcut_case_sensitive: false,
             style: None,
             focused_style: None,
+            disabled: false,
         }
     }
 
@@ -87,6 +96,22 @@ impl Action {
         self.focused_style = Some(style);
         self
     }
+
+    /// Create the action disabled or enabled (default: enabled).
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Enable or disable this action.
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    /// Whether this action is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
 }
 
 /// Messages for the modal component.
@@ -100,6 +125,10 @@ pub enum Message {
     Dismiss,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// Style configuration for the modal.
 #[derive(Debug, Clone)]
 pub struct ModalStyle {
@@ -111,6 +140,8 @@ pub struct ModalStyle {
     pub action: Style,
     /// Style for the currently focused action button.
     pub focused_action: Style,
+    /// Style for disabled action buttons, focused or not.
+    pub disabled_action: Style,
 }
 
 impl Default for ModalStyle {
@@ -124,6 +155,9 @@ impl Default for ModalStyle {
             focused_action: Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
+            disabled_action: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
         }
     }
 }
@@ -158,6 +192,10 @@ pub struct Modal {
     fixed_height: Option<u16>,
     /// Optional block (border/title container) for the modal.
     block: Option<Block<'static>>,
+    /// Host focus slot to return to when the modal closes.
+    return_focus: Option<usize>,
+    /// Style patched over the whole view area behind the dialog.
+    backdrop: Option<Style>,
 }
 
 impl Modal {
@@ -175,6 +213,8 @@ impl Modal {
             fixed_width: None,
             fixed_height: None,
             block: None,
+            return_focus: None,
+            backdrop: None,
         }
     }
 
@@ -223,12 +263,56 @@ impl Modal {
         self
     }
 
+    /// Dim everything behind the dialog by patching `style` over the whole
+    /// area passed to `view`, not just the modal's rect (default: off).
+    ///
+    /// ```ignore
+    /// let modal = Modal::new("Confirm")
+    ///     .with_backdrop(Style::default().add_modifier(Modifier::DIM));
+    /// ```
+    pub fn with_backdrop(mut self, style: Style) -> Self {
+        self.backdrop = Some(style);
+        self
+    }
+
     /// Set the action button layout direction.
     pub fn with_action_layout(mut self, layout: ActionLayout) -> Self {
         self.action_layout = layout;
         self
     }
 
+    /// Record the host's focused component so it can be restored when the
+    /// modal closes. Read it back with [`return_focus`](Self::return_focus)
+    /// when handling `Select` or `Dismiss`.
+    pub fn with_return_focus(mut self, id: usize) -> Self {
+        self.return_focus = Some(id);
+        self
+    }
+
+    /// Trap a [`FocusGroup`]'s focus while this modal is open.
+    ///
+    /// The group's focused slot is recorded as the return focus and Tab
+    /// cycling between the group's slots is disabled. Call
+    /// [`FocusGroup::release`] on `Select` or `Dismiss` to restore it.
+    ///
+    /// ```ignore
+    /// self.modal = Some(Modal::new("Discard?").trap_focus(&mut self.focus));
+    /// // later, in update:
+    /// Msg::Modal(modal::Message::Dismiss) => {
+    ///     self.modal = None;
+    ///     self.focus.release();
+    /// }
+    /// ```
+    pub fn trap_focus<const N: usize>(self, group: &mut FocusGroup<N>) -> Self {
+        let slot = group.trap();
+        self.with_return_focus(slot)
+    }
+
+    /// The host focus id recorded when the modal was opened, if any.
+    pub fn return_focus(&self) -> Option<usize> {
+        self.return_focus
+    }
+
     /// Get the index of the currently focused action.
     pub fn focused_action(&self) -> usize {
         self.focused_action
@@ -246,6 +330,29 @@ impl Modal {
         &self.actions
     }
 
+    /// Get a mutable reference to the action at `index`, e.g. to enable or
+    /// disable it while the modal is open.
+    pub fn action_mut(&mut self, index: usize) -> Option<&mut Action> {
+        self.actions.get_mut(index)
+    }
+
+    /// Move focus to the next (or previous) enabled action, wrapping around.
+    /// Focus stays put when no other action is enabled.
+    fn step_focus(&mut self, forward: bool) {
+        let len = self.actions.len();
+        for step in 1..len {
+            let idx = if forward {
+                (self.focused_action + step) % len
+            } else {
+                (self.focused_action + len - step) % len
+            };
+            if !self.actions[idx].disabled {
+                self.focused_action = idx;
+                return;
+            }
+        }
+    }
+
     /// Get the title.
     pub fn title(&self) -> &str {
         &self.title
@@ -261,50 +368,32 @@ impl Component for Modal {
                 match (key.code, key.modifiers) {
                     (KeyCode::Esc, _) => Command::message(Message::Dismiss),
                     (KeyCode::Left, _) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
-                        if !self.actions.is_empty() {
-                            if self.focused_action > 0 {
-                                self.focused_action -= 1;
-                            } else {
-                                self.focused_action = self.actions.len() - 1;
-                            }
-                        }
+                        self.step_focus(false);
                         Command::none()
                     }
                     (KeyCode::Right, _)
                     | (KeyCode::Char('l'), KeyModifiers::NONE)
                     | (KeyCode::Tab, _) => {
-                        if !self.actions.is_empty() {
-                            self.focused_action = (self.focused_action + 1) % self.actions.len();
-                        }
+                        self.step_focus(true);
                         Command::none()
                     }
                     (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
-                        if !self.actions.is_empty() {
-                            if self.focused_action > 0 {
-                                self.focused_action -= 1;
-                            } else {
-                                self.focused_action = self.actions.len() - 1;
-                            }
-                        }
+                        self.step_focus(false);
                         Command::none()
                     }
                     (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
-                        if !self.actions.is_empty() {
-                            self.focused_action = (self.focused_action + 1) % self.actions.len();
-                        }
+                        self.step_focus(true);
                         Command::none()
                     }
-                    (KeyCode::Enter, _) => {
-                        if !self.actions.is_empty() {
-                            Command::message(Message::Select(self.focused_action))
-                        } else {
-                            Command::message(Message::Dismiss)
-                        }
-                    }
+                    (KeyCode::Enter, _) => match self.actions.get(self.focused_action) {
+                        Some(action) if action.disabled => Command::none(),
+                        Some(_) => Command::message(Message::Select(self.focused_action)),
+                        None => Command::message(Message::Dismiss),
+                    },
                     // Number keys for quick focus (1-indexed)
                     (KeyCode::Char(c), KeyModifiers::NONE) if c.is_ascii_digit() && c != '0' => {
                         let idx = (c as u8 - b'1') as usize;
-                        if idx < self.actions.len() {
+                        if self.actions.get(idx).is_some_and(|a| !a.disabled) {
                             self.focused_action = idx;
                         }
                         Command::none()
@@ -312,6 +401,9 @@ impl Component for Modal {
                     // Check shortcuts (case-insensitive by default, exact if configured)
                     (KeyCode::Char(c), _) => {
                         for (i, action) in self.actions.iter().enumerate() {
+                            if action.disabled {
+                                continue;
+                            }
                             if let Some(shortcut) = action.shortcut {
                                 let matches = if action.shortcut_case_sensitive {
                                     shortcut == c
@@ -338,6 +430,9 @@ impl Component for Modal {
         } else {
             overlay::centered_rect(self.width_percent, self.height_percent, area)
         };
+        if let Some(style) = self.backdrop {
+            overlay::render_backdrop(frame, area, style);
+        }
         let inner = overlay::render_overlay(frame, modal_area, self.block.as_ref());
 
         // Layout: body takes remaining space, actions at bottom
@@ -387,7 +482,9 @@ impl Component for Modal {
                         if i > 0 {
                             spans.push(Span::raw("  "));
                         }
-                        let style = if i == self.focused_action {
+                        let style = if action.disabled {
+                            self.style.disabled_action
+                        } else if i == self.focused_action {
                             action.focused_style.unwrap_or(self.style.focused_action)
                         } else {
                             action.style.unwrap_or(self.style.action)
@@ -428,7 +525,9 @@ impl Component for Modal {
                             ..action_area
                         };
 
-                        let base_style = if i == self.focused_action {
+                        let base_style = if action.disabled {
+                            self.style.disabled_action
+                        } else if i == self.focused_action {
                             action.focused_style.unwrap_or(self.style.focused_action)
                         } else {
                             action.style.unwrap_or(self.style.action)
@@ -467,9 +566,116 @@ impl Component for Modal {
         }
     }
 
+    /// Fixed dimensions are reported as lengths; otherwise the configured
+    /// percentages of the available area.
+    fn size_hint(&self, _available: Rect) -> (Constraint, Constraint) {
+        match (self.fixed_width, self.fixed_height) {
+            (Some(w), Some(h)) => (Constraint::Length(w), Constraint::Length(h)),
+            _ => (
+                Constraint::Percentage(self.width_percent),
+                Constraint::Percentage(self.height_percent),
+            ),
+        }
+    }
+
     fn focused(&self) -> bool {
         true // Modals always capture input
     }
+
+    /// Always `true`: a modal on screen is dismissed by Esc.
+    fn wants_escape(&self) -> bool {
+        true
+    }
+}
+
+/// Modals waiting to be shown one at a time.
+///
+/// Only the front modal is drawn and receives keys. When it emits
+/// [`Message::Select`] or [`Message::Dismiss`] and the host forwards that
+/// message back, the queue drops it and shows the next one. Check
+/// [`current`](Self::current) before forwarding to see which modal was
+/// answered.
+///
+/// ```ignore
+/// self.confirms.push(Modal::new("Delete file?").action(Action::new("Yes")));
+/// self.confirms.push(Modal::new("Overwrite?").action(Action::new("Yes")));
+///
+/// // In update:
+/// Msg::Confirm(msg) => {
+///     if let modal::Message::Select(i) = msg {
+///         let title = self.confirms.current().map(|m| m.title().to_owned());
+///         // ... act on the answer ...
+///     }
+///     self.confirms.update(msg).map(Msg::Confirm)
+/// }
+/// ```
+#[derive(Default)]
+pub struct ModalQueue {
+    modals: VecDeque<Modal>,
+}
+
+impl ModalQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a modal to the back of the queue. It is shown straight away if
+    /// the queue was empty.
+    pub fn push(&mut self, modal: Modal) {
+        self.modals.push_back(modal);
+    }
+
+    /// The modal on screen, if any.
+    pub fn current(&self) -> Option<&Modal> {
+        self.modals.front()
+    }
+
+    /// Mutable access to the modal on screen, if any.
+    pub fn current_mut(&mut self) -> Option<&mut Modal> {
+        self.modals.front_mut()
+    }
+
+    /// Number of modals queued, including the one on screen.
+    pub fn len(&self) -> usize {
+        self.modals.len()
+    }
+
+    /// Whether no modal is queued.
+    pub fn is_empty(&self) -> bool {
+        self.modals.is_empty()
+    }
+}
+
+impl Component for ModalQueue {
+    type Message = Message;
+
+    fn update(&mut self, msg: Message) -> Command<Message> {
+        match msg {
+            Message::Select(_) | Message::Dismiss => {
+                self.modals.pop_front();
+                Command::none()
+            }
+            Message::KeyPress(_) => match self.modals.front_mut() {
+                Some(modal) => modal.update(msg),
+                None => Command::none(),
+            },
+        }
+    }
+
+    fn view(&self, frame: &mut Frame, area: Rect) {
+        if let Some(modal) = self.current() {
+            modal.view(frame, area);
+        }
+    }
+
+    fn focused(&self) -> bool {
+        !self.is_empty()
+    }
+
+    fn wants_escape(&self) -> bool {
+        !self.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -520,6 +726,32 @@ mod tests {
         assert_eq!(modal.focused_action(), 2);
     }
 
+    #[test]
+    fn disabled_actions_are_skipped_and_inert() {
+        let mut modal = Modal::new("Test")
+            .action(Action::new("A"))
+            .action(Action::new("B").with_shortcut('b').with_disabled(true))
+            .action(Action::new("C"));
+        assert!(modal.actions()[1].is_disabled());
+
+        modal.update(Message::KeyPress(key(KeyCode::Right)));
+        assert_eq!(modal.focused_action(), 2);
+        modal.update(Message::KeyPress(key(KeyCode::Left)));
+        assert_eq!(modal.focused_action(), 0);
+
+        assert!(modal
+            .update(Message::KeyPress(key(KeyCode::Char('b'))))
+            .is_none());
+        modal.update(Message::KeyPress(key(KeyCode::Char('2'))));
+        assert_eq!(modal.focused_action(), 0);
+
+        // Disabling the focused action makes Enter a no-op.
+        modal.action_mut(0).unwrap().set_disabled(true);
+        assert!(modal
+            .update(Message::KeyPress(key(KeyCode::Enter)))
+            .is_none());
+    }
+
     #[test]
     fn enter_selects_focused() {
         let mut modal = Modal::new("Test")
@@ -540,6 +772,7 @@ mod tests {
     #[test]
     fn esc_dismisses() {
         let mut modal = Modal::new("Test");
+        assert!(modal.wants_escape());
         let cmd = modal.update(Message::KeyPress(key(KeyCode::Esc)));
         match cmd.into_message() {
             Some(Message::Dismiss) => {}
@@ -656,4 +889,143 @@ mod tests {
         assert_eq!(modal.fixed_width, Some(60));
         assert_eq!(modal.fixed_height, Some(20));
     }
+
+    #[test]
+    fn size_hint_reflects_sizing_mode() {
+        let area = Rect::new(0, 0, 80, 24);
+        let modal = Modal::new("Test").with_size(50, 40);
+        assert_eq!(
+            modal.size_hint(area),
+            (Constraint::Percentage(50), Constraint::Percentage(40))
+        );
+
+        let modal = Modal::new("Test").with_fixed_size(30, 8);
+        assert_eq!(
+            modal.size_hint(area),
+            (Constraint::Length(30), Constraint::Length(8))
+        );
+    }
+
+    #[test]
+    fn focus_returns_to_previous_member_after_close() {
+        let mut focus = FocusGroup::<3>::new();
+        focus.focus(1);
+
+        let mut modal = Modal::new("Discard?")
+            .action(Action::new("Yes"))
+            .trap_focus(&mut focus);
+        assert_eq!(modal.return_focus(), Some(1));
+
+        // Tab stays inside the modal while it is open.
+        modal.update(Message::KeyPress(key(KeyCode::Tab)));
+        focus.focus_next();
+        assert_eq!(focus.focused(), 1);
+
+        let cmd = modal.update(Message::KeyPress(key(KeyCode::Esc)));
+        assert!(matches!(cmd.into_message(), Some(Message::Dismiss)));
+        assert_eq!(focus.release(), 1);
+        assert!(focus.is_focused(1));
+    }
+
+    #[test]
+    fn return_focus_defaults_to_none() {
+        let modal = Modal::new("Info");
+        assert_eq!(modal.return_focus(), None);
+        let modal = modal.with_return_focus(4);
+        assert_eq!(modal.return_focus(), Some(4));
+    }
+
+    fn render_over_text(modal: &Modal) -> ratatui::buffer::Buffer {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 10)).unwrap();
+        terminal
+            .draw(|f| {
+                f.render_widget(Paragraph::new("background"), f.area());
+                modal.view(f, f.area());
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn backdrop_dims_cells_outside_modal() {
+        let dim = Style::default()
+            .bg(Color::Black)
+            .add_modifier(Modifier::DIM);
+        let modal = Modal::new("Hi").with_fixed_size(6, 4).with_backdrop(dim);
+        let buf = render_over_text(&modal);
+
+        // Text behind the dialog is kept, with the backdrop patched on.
+        assert_eq!(buf[(0, 0)].symbol(), "b");
+        assert!(buf[(0, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buf[(19, 9)].bg, Color::Black);
+        // The dialog itself is cleared and drawn normally.
+        assert!(!buf[(10, 5)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn backdrop_is_off_by_default() {
+        let modal = Modal::new("Hi").with_fixed_size(6, 4);
+        let buf = render_over_text(&modal);
+        assert_eq!(buf[(0, 0)].symbol(), "b");
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buf[(19, 9)].bg, Color::Reset);
+    }
+
+    /// Send a key to the queue and forward whatever it emits back, as a
+    /// host would.
+    fn press(queue: &mut ModalQueue, code: KeyCode) -> Option<Message> {
+        let msg = queue.update(Message::KeyPress(key(code))).into_message();
+        if let Some(msg) = msg.clone() {
+            queue.update(msg);
+        }
+        msg
+    }
+
+    #[test]
+    fn queue_shows_modals_one_at_a_time() {
+        let mut queue = ModalQueue::new();
+        assert!(queue.current().is_none());
+        for title in ["First", "Second", "Third"] {
+            queue.push(Modal::new(title).action(Action::new("OK")));
+        }
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.current().unwrap().title(), "First");
+
+        assert!(matches!(
+            press(&mut queue, KeyCode::Esc),
+            Some(Message::Dismiss)
+        ));
+        assert_eq!(queue.current().unwrap().title(), "Second");
+
+        assert!(matches!(
+            press(&mut queue, KeyCode::Enter),
+            Some(Message::Select(0))
+        ));
+        assert_eq!(queue.current().unwrap().title(), "Third");
+        assert_eq!(queue.len(), 1);
+
+        press(&mut queue, KeyCode::Esc);
+        assert!(queue.is_empty());
+        assert!(!queue.focused());
+    }
+
+    #[test]
+    fn queue_only_draws_front_modal() {
+        let mut queue = ModalQueue::new();
+        queue.push(Modal::new("A").body("Front").with_fixed_size(10, 4));
+        queue.push(Modal::new("B").body("Back").with_fixed_size(10, 4));
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 10)).unwrap();
+        terminal.draw(|f| queue.view(f, f.area())).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(text.contains("Front"), "{text}");
+        assert!(!text.contains("Back"), "{text}");
+    }
 }
@@ -4,6 +4,7 @@
 //! areas, used by Modal, Help, and any custom overlay composition.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::widgets::{Block, Clear};
 use ratatui::Frame;
 
@@ -60,6 +61,12 @@ pub fn render_overlay(frame: &mut Frame, area: Rect, block: Option<&Block>) -> R
     }
 }
 
+/// Patch `style` over everything already drawn in `area`, e.g. to dim the
+/// screen behind a dialog. Text stays in place; only its style changes.
+pub fn render_backdrop(frame: &mut Frame, area: Rect, style: Style) {
+    frame.buffer_mut().set_style(area, style);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,11 +2,16 @@
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::event::hit_test;
+use boba_core::impl_terminal_message;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Paragraph, Widget};
 use ratatui::Frame;
+use std::cell::Cell;
 
 /// The type of pagination indicator to display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,8 +31,21 @@ pub enum Message {
     PrevPage,
     /// Jump to a specific page (zero-indexed).
     GotoPage(usize),
+    /// A key press event forwarded to the paginator.
+    KeyPress(KeyEvent),
+    /// A mouse event forwarded to the paginator. Clicking a dot jumps to
+    /// that page.
+    Mouse(MouseEvent),
+    /// Emitted when a key press or click changes the page, carrying the
+    /// new page index.
+    PageChanged(usize),
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress,
+    mouse: Mouse,
+});
+
 /// Style configuration for the paginator.
 #[derive(Debug, Clone)]
 pub struct PaginatorStyle {
@@ -55,12 +73,20 @@ impl Default for PaginatorStyle {
 ///
 /// Displays the current page position either as a row of dots
 /// or as an Arabic numeral fraction (e.g. "2/5").
+///
+/// When focused, Left/Right (or `h`/`l`) move between pages and Home/End
+/// jump to the first and last page. Forwarded left clicks on a dot select
+/// that page.
 pub struct Paginator {
     total_pages: usize,
     page: usize,
     per_page: usize,
     paginator_type: PaginatorType,
     style: PaginatorStyle,
+    wrap: bool,
+    focus: bool,
+    /// Area of the last render, used to hit-test mouse clicks.
+    last_area: Cell<Rect>,
 }
 
 impl Paginator {
@@ -73,6 +99,9 @@ impl Paginator {
             per_page: 10,
             paginator_type: PaginatorType::Dots,
             style: PaginatorStyle::default(),
+            wrap: false,
+            focus: false,
+            last_area: Cell::new(Rect::default()),
         }
     }
 
@@ -94,6 +123,24 @@ impl Paginator {
         self
     }
 
+    /// Wrap around at the edges: advancing past the last page returns to
+    /// the first, and going back from the first page moves to the last.
+    /// Defaults to `false` (clamp at the edges).
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Give this paginator keyboard focus.
+    pub fn focus(&mut self) {
+        self.focus = true;
+    }
+
+    /// Remove keyboard focus from this paginator.
+    pub fn blur(&mut self) {
+        self.focus = false;
+    }
+
     /// Get the current page (zero-indexed).
     pub fn page(&self) -> usize {
         self.page
@@ -118,17 +165,23 @@ impl Paginator {
         }
     }
 
-    /// Advance to the next page if not on the last page.
+    /// Advance to the next page. On the last page this wraps to the first
+    /// page when wrapping is enabled and does nothing otherwise.
     pub fn next_page(&mut self) {
         if !self.on_last_page() {
             self.page += 1;
+        } else if self.wrap {
+            self.page = 0;
         }
     }
 
-    /// Go to the previous page if not on the first page.
+    /// Go to the previous page. On the first page this wraps to the last
+    /// page when wrapping is enabled and does nothing otherwise.
     pub fn prev_page(&mut self) {
         if !self.on_first_page() {
             self.page -= 1;
+        } else if self.wrap {
+            self.page = self.total_pages - 1;
         }
     }
 
@@ -158,6 +211,58 @@ impl Paginator {
         let remaining = total_items - start;
         remaining.min(self.per_page)
     }
+
+    /// Return the page whose dot is drawn at the given screen position in
+    /// the last rendered area. Only dot-style paginators are clickable.
+    fn page_at(&self, mouse: &MouseEvent) -> Option<usize> {
+        if self.paginator_type != PaginatorType::Dots {
+            return None;
+        }
+        let pos = hit_test(mouse, self.last_area.get())?;
+        if pos.y != 0 {
+            return None;
+        }
+        // Dots are laid out as "● ○ ○": one cell per dot plus a space.
+        let offset = pos.x as usize;
+        if offset % 2 != 0 {
+            return None;
+        }
+        let page = offset / 2;
+        (page < self.total_pages).then_some(page)
+    }
+
+    /// The indicator line for the current page.
+    fn line(&self) -> Line<'static> {
+        match self.paginator_type {
+            PaginatorType::Dots => {
+                let mut spans = Vec::with_capacity(self.total_pages * 2);
+                for i in 0..self.total_pages {
+                    if i > 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    if i == self.page {
+                        spans.push(Span::styled("●", self.style.active_dot));
+                    } else {
+                        spans.push(Span::styled("○", self.style.inactive_dot));
+                    }
+                }
+                Line::from(spans)
+            }
+            PaginatorType::Arabic => {
+                let text = format!("{}/{}", self.page + 1, self.total_pages);
+                Line::from(Span::styled(text, self.style.text))
+            }
+        }
+    }
+
+    /// Emit `PageChanged` if the page differs from `before`.
+    fn changed_since(&self, before: usize) -> Command<Message> {
+        if self.page != before {
+            Command::message(Message::PageChanged(self.page))
+        } else {
+            Command::none()
+        }
+    }
 }
 
 impl Component for Paginator {
@@ -177,37 +282,149 @@ impl Component for Paginator {
                 self.set_page(n);
                 Command::none()
             }
+            Message::KeyPress(key) if self.focus => {
+                let before = self.page;
+                match key.code {
+                    KeyCode::Left | KeyCode::Char('h') => self.prev_page(),
+                    KeyCode::Right | KeyCode::Char('l') => self.next_page(),
+                    KeyCode::Home => self.page = 0,
+                    KeyCode::End => self.page = self.total_pages - 1,
+                    _ => {}
+                }
+                self.changed_since(before)
+            }
+            Message::Mouse(mouse) => {
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                    return Command::none();
+                }
+                let before = self.page;
+                if let Some(page) = self.page_at(&mouse) {
+                    self.page = page;
+                }
+                self.changed_since(before)
+            }
+            Message::KeyPress(_) | Message::PageChanged(_) => Command::none(),
         }
     }
 
     fn view(&self, frame: &mut Frame, area: Rect) {
+        self.last_area.set(area);
         if area.width == 0 || area.height == 0 {
             return;
         }
 
-        match self.paginator_type {
-            PaginatorType::Dots => {
-                let mut spans = Vec::with_capacity(self.total_pages * 2);
-                for i in 0..self.total_pages {
-                    if i > 0 {
-                        spans.push(Span::raw(" "));
-                    }
-                    if i == self.page {
-                        spans.push(Span::styled("●", self.style.active_dot));
-                    } else {
-                        spans.push(Span::styled("○", self.style.inactive_dot));
-                    }
-                }
-                let line = Line::from(spans);
-                let paragraph = Paragraph::new(line);
-                frame.render_widget(paragraph, area);
-            }
-            PaginatorType::Arabic => {
-                let text = format!("{}/{}", self.page + 1, self.total_pages);
-                let span = Span::styled(text, self.style.text);
-                let paragraph = Paragraph::new(span);
-                frame.render_widget(paragraph, area);
-            }
-        }
+        frame.render_widget(Paragraph::new(self.line()), area);
+    }
+
+    fn render_lines(&self, width: u16) -> Option<Vec<Line<'static>>> {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, 1));
+        Paragraph::new(self.line()).render(buf.area, &mut buf);
+        Some(crate::runeutil::buffer_lines(&buf))
+    }
+
+    fn focused(&self) -> bool {
+        self.focus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn key(code: KeyCode) -> Message {
+        Message::KeyPress(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn click(column: u16, row: u16) -> Message {
+        Message::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn render(paginator: &Paginator, area: Rect) {
+        let mut terminal = Terminal::new(TestBackend::new(20, 3)).unwrap();
+        terminal.draw(|f| paginator.view(f, area)).unwrap();
+    }
+
+    #[test]
+    fn clamps_at_edges_by_default() {
+        let mut p = Paginator::new(3);
+        p.focus();
+        assert!(p.update(key(KeyCode::Left)).is_none());
+        assert_eq!(p.page(), 0);
+
+        p.update(key(KeyCode::End));
+        assert_eq!(p.page(), 2);
+        assert!(p.update(key(KeyCode::Right)).is_none());
+        assert_eq!(p.page(), 2);
+    }
+
+    #[test]
+    fn wraps_at_edges_when_enabled() {
+        let mut p = Paginator::new(3).with_wrap(true);
+        p.focus();
+        let cmd = p.update(key(KeyCode::Left));
+        assert!(matches!(cmd.into_message(), Some(Message::PageChanged(2))));
+        assert_eq!(p.page(), 2);
+
+        let cmd = p.update(key(KeyCode::Right));
+        assert!(matches!(cmd.into_message(), Some(Message::PageChanged(0))));
+        assert_eq!(p.page(), 0);
+    }
+
+    #[test]
+    fn home_and_end_jump_to_bounds() {
+        let mut p = Paginator::new(5);
+        p.focus();
+        let cmd = p.update(key(KeyCode::End));
+        assert!(matches!(cmd.into_message(), Some(Message::PageChanged(4))));
+        let cmd = p.update(key(KeyCode::Home));
+        assert!(matches!(cmd.into_message(), Some(Message::PageChanged(0))));
+    }
+
+    #[test]
+    fn keys_ignored_without_focus() {
+        let mut p = Paginator::new(3);
+        p.update(key(KeyCode::Right));
+        assert_eq!(p.page(), 0);
+    }
+
+    #[test]
+    fn render_lines_matches_view() {
+        let mut p = Paginator::new(3);
+        p.set_page(1);
+        let lines = p.render_lines(7).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].to_string(), "○ ● ○  ");
+
+        let p = Paginator::new(12).with_type(PaginatorType::Arabic);
+        assert_eq!(p.render_lines(3).unwrap()[0].to_string(), "1/1");
+    }
+
+    #[test]
+    fn clicking_a_dot_jumps_to_page() {
+        let mut p = Paginator::new(4);
+        render(&p, Rect::new(2, 1, 10, 1));
+
+        // Dots sit at x = 2, 4, 6, 8.
+        let cmd = p.update(click(6, 1));
+        assert!(matches!(cmd.into_message(), Some(Message::PageChanged(2))));
+        assert_eq!(p.page(), 2);
+
+        // Gaps between dots and other rows are ignored.
+        assert!(p.update(click(5, 1)).is_none());
+        assert!(p.update(click(4, 0)).is_none());
+        assert_eq!(p.page(), 2);
     }
 }
@@ -1,13 +1,18 @@
 //! Animated progress bar with spring physics, gradient colors, and customizable fill characters.
+//!
+//! [`MultiProgress`] stacks several labelled bars, e.g. one per concurrent
+//! download.
 
 use boba_core::command::Command;
 use boba_core::component::Component;
-use boba_core::subscription::Subscription;
+use boba_core::subscription::{Subscription, SubscriptionId};
 use boba_core::subscriptions::Every;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Gauge};
 use ratatui::Frame;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 /// Messages for the progress component.
@@ -27,7 +32,7 @@ pub struct Progress {
     label: String,
     style: ProgressStyle,
     animating: bool,
-    id: &'static str,
+    id: Cow<'static, str>,
     // Spring physics constants
     stiffness: f64,
     damping: f64,
@@ -73,7 +78,7 @@ impl Progress {
             label: String::new(),
             style: ProgressStyle::default(),
             animating: false,
-            id,
+            id: Cow::Borrowed(id),
             stiffness: 180.0,
             damping: 12.0,
             show_percentage: true,
@@ -301,7 +306,8 @@ impl Component for Progress {
     fn subscriptions(&self) -> Vec<Subscription<Message>> {
         if self.animating {
             vec![
-                boba_core::subscription::subscribe(Every::new(Duration::from_millis(16), self.id))
+                boba_core::subscription::subscribe(Every::new(Duration::from_millis(16), ""))
+                    .with_id(SubscriptionId::with_str::<Every>(&self.id))
                     .map(|_: std::time::Instant| Message::Tick),
             ]
         } else {
@@ -310,6 +316,145 @@ impl Component for Progress {
     }
 }
 
+/// Messages for the [`MultiProgress`] component.
+#[derive(Debug, Clone)]
+pub enum MultiMessage {
+    /// Set the named bar to `done` out of `total`, adding it if needed.
+    Set {
+        /// The bar's name, shown as its label.
+        name: String,
+        /// Units completed.
+        done: u64,
+        /// Total units.
+        total: u64,
+    },
+    /// Remove the named bar.
+    Remove(String),
+}
+
+/// A stack of named progress bars, one per row, in insertion order.
+///
+/// Each bar is a [`Progress`] labelled with its name. Bars jump straight to
+/// their new value rather than animating.
+///
+/// # Example
+///
+/// ```ignore
+/// use boba_widgets::progress::MultiProgress;
+///
+/// let mut downloads = MultiProgress::new();
+/// downloads.set("linux.iso", 512, 2048);
+/// downloads.set("notes.pdf", 3, 4);
+/// downloads.remove("notes.pdf");
+/// ```
+pub struct MultiProgress {
+    bars: Vec<(String, Progress)>,
+    style: ProgressStyle,
+}
+
+impl MultiProgress {
+    /// Create an empty stack.
+    pub fn new() -> Self {
+        Self {
+            bars: Vec::new(),
+            style: ProgressStyle::default(),
+        }
+    }
+
+    /// Set the style used for every bar.
+    pub fn with_style(mut self, style: ProgressStyle) -> Self {
+        for (_, bar) in &mut self.bars {
+            bar.style = style.clone();
+        }
+        self.style = style;
+        self
+    }
+
+    /// Set the named bar to `done` out of `total`, appending a new bar if
+    /// there is none by that name. A `total` of zero shows as empty.
+    pub fn set(&mut self, name: &str, done: u64, total: u64) {
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            done as f64 / total as f64
+        };
+        match self.bars.iter_mut().find(|(n, _)| n == name) {
+            Some((_, bar)) => bar.set_progress_immediate(ratio),
+            None => {
+                // Every bar gets its own id, across all stacks.
+                static NEXT_BAR: AtomicU64 = AtomicU64::new(0);
+                let mut bar = Progress::new("")
+                    .with_label(name)
+                    .with_style(self.style.clone());
+                let n = NEXT_BAR.fetch_add(1, Ordering::Relaxed);
+                bar.id = Cow::Owned(format!("multi_progress/{n}"));
+                bar.set_progress_immediate(ratio);
+                self.bars.push((name.to_string(), bar));
+            }
+        }
+    }
+
+    /// Remove the named bar. Returns whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.bars.len();
+        self.bars.retain(|(n, _)| n != name);
+        self.bars.len() != before
+    }
+
+    /// The named bar's progress (0.0 to 1.0), if it exists.
+    pub fn ratio(&self, name: &str) -> Option<f64> {
+        self.bars
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, bar)| bar.progress())
+    }
+
+    /// Bar names in display order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.bars.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// Number of bars.
+    pub fn len(&self) -> usize {
+        self.bars.len()
+    }
+
+    /// Whether there are no bars.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for MultiProgress {
+    type Message = MultiMessage;
+
+    fn update(&mut self, msg: MultiMessage) -> Command<MultiMessage> {
+        match msg {
+            MultiMessage::Set { name, done, total } => self.set(&name, done, total),
+            MultiMessage::Remove(name) => {
+                self.remove(&name);
+            }
+        }
+        Command::none()
+    }
+
+    /// Draws one bar per row from the top; bars that don't fit are cut off.
+    fn view(&self, frame: &mut Frame, area: Rect) {
+        let rows = Layout::vertical(vec![Constraint::Length(1); self.bars.len()]).split(area);
+        for ((_, bar), row) in self.bars.iter().zip(rows.iter()) {
+            if row.height > 0 {
+                bar.view(frame, *row);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -387,4 +532,66 @@ mod tests {
         let result = interpolate_color(from, to, 0.8);
         assert_eq!(result, to);
     }
+
+    #[test]
+    fn multi_progress_updates_one_bar_at_a_time() {
+        let mut bars = MultiProgress::new();
+        bars.set("a", 1, 4);
+        bars.set("b", 0, 10);
+        bars.update(MultiMessage::Set {
+            name: "b".into(),
+            done: 5,
+            total: 10,
+        });
+        assert_eq!(bars.ratio("a"), Some(0.25));
+        assert_eq!(bars.ratio("b"), Some(0.5));
+
+        bars.set("c", 3, 0);
+        assert_eq!(bars.ratio("c"), Some(0.0));
+        assert!(bars.remove("a"));
+        assert!(!bars.remove("a"));
+        assert_eq!(bars.ratio("a"), None);
+        assert_eq!(bars.ratio("b"), Some(0.5));
+        assert_eq!(bars.names().collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn multi_progress_bars_have_distinct_ids() {
+        let mut first = MultiProgress::new();
+        first.set("a", 0, 1);
+        first.set("b", 0, 1);
+        let mut second = MultiProgress::new();
+        second.set("a", 0, 1);
+        let ids: Vec<_> = first
+            .bars
+            .iter()
+            .chain(&second.bars)
+            .map(|(_, bar)| bar.id.clone())
+            .collect();
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[0], ids[2]);
+        assert_ne!(ids[1], ids[2]);
+    }
+
+    #[test]
+    fn multi_progress_renders_in_insertion_order() {
+        let mut bars = MultiProgress::new();
+        bars.set("zeta", 1, 1);
+        bars.set("alpha", 0, 1);
+        bars.set("mid", 1, 2);
+        // Updating an existing bar keeps its place.
+        bars.set("zeta", 1, 2);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 4)).unwrap();
+        terminal.draw(|f| bars.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..20).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(rows[0].contains("zeta 50%"), "{rows:?}");
+        assert!(rows[1].contains("alpha 0%"), "{rows:?}");
+        assert!(rows[2].contains("mid 50%"), "{rows:?}");
+        assert_eq!(rows[3].trim(), "");
+    }
 }
@@ -4,6 +4,7 @@
 //! of Unicode text, truncating strings to fit within a given width, and
 //! parsing ANSI escape sequences into styled ratatui text.
 
+use ratatui::buffer::Buffer;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 
@@ -247,21 +248,17 @@ fn apply_sgr_codes(codes: &[u16], style: &mut Style) {
                     *style = style.bg(c);
                 }
             }
-            38 => {
-                // 256-color foreground: 38;5;N
-                if i + 2 < codes.len() && codes[i + 1] == 5 {
-                    let n = codes[i + 2];
-                    *style = style.fg(Color::Indexed(n as u8));
-                    i += 2;
-                }
+            // 256-color foreground: 38;5;N
+            38 if i + 2 < codes.len() && codes[i + 1] == 5 => {
+                let n = codes[i + 2];
+                *style = style.fg(Color::Indexed(n as u8));
+                i += 2;
             }
-            48 => {
-                // 256-color background: 48;5;N
-                if i + 2 < codes.len() && codes[i + 1] == 5 {
-                    let n = codes[i + 2];
-                    *style = style.bg(Color::Indexed(n as u8));
-                    i += 2;
-                }
+            // 256-color background: 48;5;N
+            48 if i + 2 < codes.len() && codes[i + 1] == 5 => {
+                let n = codes[i + 2];
+                *style = style.bg(Color::Indexed(n as u8));
+                i += 2;
             }
             _ => {}
         }
@@ -358,6 +355,36 @@ pub fn parse_ansi(input: &str) -> Vec<Line<'static>> {
         .collect()
 }
 
+/// Convert every row of a rendered buffer into a styled `Line`.
+///
+/// Adjacent cells with the same style are merged into one span. The blank
+/// cells that trail a wide character are skipped so the text reads the same
+/// as it does on screen.
+pub fn buffer_lines(buf: &Buffer) -> Vec<Line<'static>> {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut skip = 0;
+            for x in area.left()..area.right() {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                let cell = &buf[(x, y)];
+                let symbol = cell.symbol();
+                skip = display_width(symbol).saturating_sub(1);
+                let style = cell.style();
+                match spans.last_mut() {
+                    Some(last) if last.style == style => last.content.to_mut().push_str(symbol),
+                    _ => spans.push(Span::styled(symbol.to_string(), style)),
+                }
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,4 +566,17 @@ mod tests {
         let lines = parse_ansi("");
         assert_eq!(lines.len(), 1);
     }
+
+    #[test]
+    fn buffer_lines_merges_styles_and_skips_wide_padding() {
+        use ratatui::layout::Rect;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buf.set_string(0, 0, "ab", Style::default().fg(Color::Red));
+        buf.set_string(2, 0, "日x", Style::default());
+        let lines = buffer_lines(&buf);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].spans[0].content, "ab");
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Red));
+        assert_eq!(lines[0].spans[1].content, "日x ");
+    }
 }
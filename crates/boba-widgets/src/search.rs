@@ -23,12 +23,14 @@
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
+use std::ops::Range;
 
 use crate::text_area;
 use crate::text_area::TextArea;
@@ -48,6 +50,10 @@ pub enum Message {
     Dismissed,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// Style configuration for the search bar.
 #[derive(Debug, Clone)]
 pub struct SearchStyle {
@@ -63,6 +69,11 @@ pub struct SearchStyle {
     pub no_matches: Style,
     /// Background style for the search bar.
     pub background: Style,
+    /// Style for occurrences in content lines, see
+    /// [`Search::highlight_line`].
+    pub occurrence: Style,
+    /// Style for the active occurrence.
+    pub active_occurrence: Style,
 }
 
 impl Default for SearchStyle {
@@ -74,6 +85,8 @@ impl Default for SearchStyle {
             counter: Style::default().fg(Color::DarkGray),
             no_matches: Style::default().fg(Color::Red),
             background: Style::default(),
+            occurrence: Style::default().bg(Color::DarkGray),
+            active_occurrence: Style::default().fg(Color::Black).bg(Color::Yellow),
         }
     }
 }
@@ -93,6 +106,8 @@ pub struct Search {
     active: bool,
     editor: TextArea,
     matches: Vec<usize>,
+    /// Every occurrence in the content as (line, byte range).
+    occurrences: Vec<(usize, Range<usize>)>,
     current_match: usize,
     style: SearchStyle,
     prompt_char: char,
@@ -113,6 +128,7 @@ impl Search {
             active: false,
             editor: TextArea::new().with_single_line(true),
             matches: Vec::new(),
+            occurrences: Vec::new(),
             current_match: 0,
             style: SearchStyle::default(),
             prompt_char: '/',
@@ -158,6 +174,7 @@ impl Search {
         self.editor.reset();
         self.editor.focus();
         self.matches.clear();
+        self.occurrences.clear();
         self.current_match = 0;
     }
 
@@ -166,6 +183,7 @@ impl Search {
         self.active = false;
         self.editor.reset();
         self.matches.clear();
+        self.occurrences.clear();
         self.current_match = 0;
     }
 
@@ -202,6 +220,57 @@ impl Search {
         &self.matches
     }
 
+    /// Every occurrence of the query in the content, in order, as the line
+    /// index and the byte range within that line. A line with several
+    /// occurrences appears several times.
+    ///
+    /// Only filled when the content is provided with
+    /// [`set_content`](Self::set_content); matches set with
+    /// [`set_matches`](Self::set_matches) have no ranges.
+    pub fn all_matches(&self) -> &[(usize, Range<usize>)] {
+        &self.occurrences
+    }
+
+    /// Index into [`all_matches`](Self::all_matches) of the active
+    /// occurrence: the first one on the current match's line.
+    pub fn active_match(&self) -> Option<usize> {
+        let line = self.current_match_value()?;
+        self.occurrences.iter().position(|(l, _)| *l == line)
+    }
+
+    /// Style line `idx` of the content, marking every occurrence with
+    /// [`SearchStyle::occurrence`] and the active one with
+    /// [`SearchStyle::active_occurrence`].
+    pub fn highlight_line(&self, idx: usize, text: &str) -> Line<'static> {
+        let active = self.active_match();
+        let mut spans = Vec::new();
+        let mut pos = 0;
+        for (i, (_, range)) in self
+            .occurrences
+            .iter()
+            .enumerate()
+            .filter(|(_, (line, _))| *line == idx)
+        {
+            let Some(matched) = text.get(range.clone()) else {
+                continue;
+            };
+            if range.start > pos {
+                spans.push(Span::raw(text[pos..range.start].to_string()));
+            }
+            let style = if active == Some(i) {
+                self.style.active_occurrence
+            } else {
+                self.style.occurrence
+            };
+            spans.push(Span::styled(matched.to_string(), style));
+            pos = range.end;
+        }
+        if pos < text.len() {
+            spans.push(Span::raw(text[pos..].to_string()));
+        }
+        Line::from(spans)
+    }
+
     /// Navigate to the next match (wraps around).
     fn next_match(&mut self) {
         if !self.matches.is_empty() {
@@ -226,26 +295,22 @@ impl Search {
             let query = self.editor.value();
             if query.is_empty() {
                 self.matches.clear();
+                self.occurrences.clear();
                 self.current_match = 0;
                 return;
             }
-            let matches: Vec<usize> = match self.match_strategy {
-                MatchStrategy::CaseInsensitive => {
-                    let q = query.to_lowercase();
-                    content
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, s)| s.to_lowercase().contains(&q))
-                        .map(|(i, _)| i)
-                        .collect()
-                }
-                MatchStrategy::CaseSensitive => content
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, s)| s.contains(&query))
-                    .map(|(i, _)| i)
-                    .collect(),
-            };
+            let occurrences: Vec<(usize, Range<usize>)> = content
+                .iter()
+                .enumerate()
+                .flat_map(|(i, line)| {
+                    find_occurrences(line, &query, self.match_strategy)
+                        .into_iter()
+                        .map(move |range| (i, range))
+                })
+                .collect();
+            let mut matches: Vec<usize> = occurrences.iter().map(|(i, _)| *i).collect();
+            matches.dedup();
+            self.occurrences = occurrences;
             self.set_matches(matches);
         }
     }
@@ -374,6 +439,34 @@ impl Component for Search {
     }
 }
 
+/// Byte ranges of the non-overlapping occurrences of `query` in `line`.
+fn find_occurrences(line: &str, query: &str, strategy: MatchStrategy) -> Vec<Range<usize>> {
+    match strategy {
+        MatchStrategy::CaseSensitive => line
+            .match_indices(query)
+            .map(|(start, m)| start..start + m.len())
+            .collect(),
+        MatchStrategy::CaseInsensitive => {
+            // Lowercasing can change byte lengths, so remember where each
+            // lowercased byte came from in the original line.
+            let mut lower = String::new();
+            let mut origin = Vec::new();
+            for (i, c) in line.char_indices() {
+                for l in c.to_lowercase() {
+                    lower.push(l);
+                    origin.resize(lower.len(), i);
+                }
+            }
+            origin.push(line.len());
+            let query = query.to_lowercase();
+            lower
+                .match_indices(&query)
+                .map(|(start, m)| origin[start]..origin[start + m.len()])
+                .collect()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -646,4 +739,64 @@ mod tests {
         search.set_matches(vec![0, 5, 10]);
         assert_eq!(search.match_count(), 3);
     }
+
+    #[test]
+    fn all_matches_reports_every_occurrence() {
+        let mut search = Search::new();
+        search.set_content(vec![
+            "banana".to_string(),
+            "cherry".to_string(),
+            "Ananas".to_string(),
+        ]);
+        search.activate();
+        search.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        search.update(Message::KeyPress(key(KeyCode::Char('n'))));
+
+        assert_eq!(
+            search.all_matches(),
+            &[(0, 1..3), (0, 3..5), (2, 0..2), (2, 2..4)]
+        );
+        // Navigation still steps through matching lines.
+        assert_eq!(search.matches(), &[0, 2]);
+        assert_eq!(search.active_match(), Some(0));
+
+        search.update(Message::KeyPress(ctrl_key(KeyCode::Char('n'))));
+        assert_eq!(search.active_match(), Some(2));
+        let (line, range) = &search.all_matches()[2];
+        assert_eq!(&["banana", "cherry", "Ananas"][*line][range.clone()], "An");
+
+        search.update(Message::KeyPress(ctrl_key(KeyCode::Char('p'))));
+        assert_eq!(search.active_match(), Some(0));
+    }
+
+    #[test]
+    fn highlight_line_marks_active_occurrence_distinctly() {
+        let mut search = Search::new();
+        search.set_content(vec!["a-b-a".to_string()]);
+        search.activate();
+        search.update(Message::KeyPress(key(KeyCode::Char('a'))));
+
+        let line = search.highlight_line(0, "a-b-a");
+        let style = SearchStyle::default();
+        let spans: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("a", style.active_occurrence),
+                ("-b-", Style::default()),
+                ("a", style.occurrence),
+            ]
+        );
+    }
+
+    #[test]
+    fn case_insensitive_ranges_follow_original_text() {
+        // "İ" lowercases to two characters, shifting later offsets.
+        let ranges = find_occurrences("İx X", "x", MatchStrategy::CaseInsensitive);
+        assert_eq!(ranges, [2..3, 4..5]);
+    }
 }
@@ -3,28 +3,56 @@
 //! This is a convenience wrapper that composes a one-line trigger display
 //! with a [`Dropdown`] overlay for the actual
 //! item list and navigation.
+//!
+//! Options may also be supplied asynchronously: mark the select as loading,
+//! respond to [`Message::OptionsRequested`] by fetching the options, and
+//! deliver them with [`Message::OptionsLoaded`] (or [`Select::set_options`]).
+//! While loading, the open dropdown shows a spinner in place of the items.
+//!
+//! Options can be any `Clone + PartialEq + Display` type (`String` by
+//! default). The selected option is handed back as a value, so the host
+//! doesn't need to keep a parallel list to resolve an index.
 
 use crate::dropdown::{self, Dropdown, DropdownStyle};
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::event::TerminalMessage;
+use boba_core::subscription::Subscription;
 use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::{Block, Clear, Paragraph};
 use ratatui::Frame;
+use std::fmt::Display;
 
 /// Messages for the select component.
 #[derive(Debug, Clone)]
-pub enum Message {
+pub enum Message<T = String> {
     /// A key press event forwarded to the select component.
     KeyPress(KeyEvent),
     /// Request to open the dropdown menu.
     Open,
     /// Request to close the dropdown menu.
     Close,
-    /// Emitted when an option is selected, carrying the index and value.
-    Selected(usize, String),
+    /// Emitted when an option is selected, carrying its index in the
+    /// current options and a clone of the option itself.
+    Selected(usize, T),
+    /// Emitted when the dropdown is opened while the select is loading.
+    /// The host should start fetching the options and reply with
+    /// [`Message::OptionsLoaded`].
+    OptionsRequested,
+    /// Replace the options with a freshly loaded set and clear the loading
+    /// state.
+    OptionsLoaded(Vec<T>),
+    /// Internal tick used to advance the loading spinner animation.
+    SpinnerTick,
+}
+
+impl<T> TerminalMessage for Message<T> {
+    fn key_press(key: KeyEvent) -> Option<Self> {
+        Some(Message::KeyPress(key))
+    }
 }
 
 /// A dropdown/picker component that presents a list of options in a
@@ -32,8 +60,22 @@ pub enum Message {
 ///
 /// Internally this composes a [`Dropdown`] for the overlay portion, while
 /// rendering its own single-line trigger display.
-pub struct Select {
-    options: Vec<String>,
+///
+/// # Example
+///
+/// ```ignore
+/// use boba_widgets::select::{Message, Select};
+///
+/// #[derive(Clone, PartialEq)]
+/// enum Theme { Light, Dark }
+/// // impl Display for Theme { ... }
+///
+/// let select = Select::new(vec![Theme::Light, Theme::Dark]);
+/// // Later, in update:
+/// // Message::Selected(_, theme) => self.theme = theme,
+/// ```
+pub struct Select<T = String> {
+    options: Vec<T>,
     selected: Option<usize>,
     dropdown: Dropdown,
     open: bool,
@@ -41,6 +83,10 @@ pub struct Select {
     placeholder: String,
     style: SelectStyle,
     block: Option<Block<'static>>,
+    loading: bool,
+    spinner: Option<crate::spinner::Spinner>,
+    /// Subscription id of the loading spinner.
+    spinner_id: &'static str,
 }
 
 /// Visual style configuration for the [`Select`] component.
@@ -66,11 +112,13 @@ impl Default for SelectStyle {
     }
 }
 
-impl Select {
+impl<T: Clone + PartialEq + Display + Send + 'static> Select<T> {
     /// Create a new select component with the given list of options.
-    pub fn new(options: Vec<String>) -> Self {
+    ///
+    /// Options are labelled in the dropdown using their `Display` output.
+    pub fn new(options: Vec<T>) -> Self {
         let mut dropdown = Dropdown::new().with_max_visible(10);
-        dropdown.set_items(options.clone());
+        dropdown.set_items(labels(&options));
         // Dropdown auto-shows on set_items; hide it since Select starts closed
         dropdown.hide();
 
@@ -83,6 +131,9 @@ impl Select {
             placeholder: "Select...".to_string(),
             style: SelectStyle::default(),
             block: None,
+            loading: false,
+            spinner: None,
+            spinner_id: "select-spinner",
         }
     }
 
@@ -92,6 +143,13 @@ impl Select {
         self
     }
 
+    /// Lay the options out in a grid of `columns` columns, for many short
+    /// options such as colors or emoji. See [`Dropdown::with_columns`].
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.dropdown = self.dropdown.with_columns(columns);
+        self
+    }
+
     /// Set the placeholder text shown when no option is selected.
     pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
         self.placeholder = placeholder.into();
@@ -106,6 +164,7 @@ impl Select {
         self.dropdown = self.dropdown.with_style(DropdownStyle {
             item: style.normal,
             selected_item: style.selected,
+            ..DropdownStyle::default()
         });
         self.style = style;
         self
@@ -123,6 +182,71 @@ impl Select {
         self
     }
 
+    /// Set the loading state. While loading, opening the dropdown emits
+    /// [`Message::OptionsRequested`] and shows a spinner instead of the
+    /// options.
+    pub fn with_loading(mut self, loading: bool) -> Self {
+        self.set_loading(loading);
+        self
+    }
+
+    /// Set the subscription id of the loading spinner (default:
+    /// `"select-spinner"`). Selects that can load at the same time need
+    /// distinct ids, or their spinner timers collide.
+    pub fn with_spinner_id(mut self, id: &'static str) -> Self {
+        self.spinner_id = id;
+        self.spinner = None;
+        if self.loading {
+            self.set_loading(true);
+        }
+        self
+    }
+
+    /// Mutable setter for the loading state.
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+        if loading && self.spinner.is_none() {
+            self.spinner =
+                Some(crate::spinner::Spinner::new(self.spinner_id).with_title("Loading..."));
+        }
+    }
+
+    /// Return whether the select is waiting for its options to load.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Replace the available options and clear the loading state.
+    ///
+    /// The current selection is kept if its value is still present in the
+    /// new set, otherwise it is cleared. An open dropdown stays open and
+    /// shows the new options.
+    pub fn set_options(&mut self, options: Vec<T>) {
+        self.selected = self
+            .selected_value()
+            .and_then(|value| options.iter().position(|o| o == value));
+        self.dropdown.set_items(labels(&options));
+        self.options = options;
+        self.loading = false;
+        if self.open {
+            if let Some(i) = self.selected {
+                self.dropdown.set_selected(i);
+            }
+        } else {
+            self.dropdown.hide();
+        }
+    }
+
+    /// Return the current list of options.
+    pub fn options(&self) -> &[T] {
+        &self.options
+    }
+
+    /// Return whether the dropdown is currently open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
     /// Give this select component keyboard focus.
     pub fn focus(&mut self) {
         self.focus = true;
@@ -140,19 +264,33 @@ impl Select {
         self.selected
     }
 
-    /// Return the string value of the currently selected option, if any.
-    pub fn selected_value(&self) -> Option<&str> {
-        self.selected
-            .and_then(|i| self.options.get(i).map(|s| s.as_str()))
+    /// Return the currently selected option, if any.
+    pub fn selected_value(&self) -> Option<&T> {
+        self.selected.and_then(|i| self.options.get(i))
     }
 
-    /// Open the dropdown, forwarding to the internal Dropdown.
-    fn open_dropdown(&mut self) {
+    /// Return the option currently highlighted in the open dropdown, if any.
+    pub fn highlighted_value(&self) -> Option<&T> {
+        if self.open && !self.loading {
+            self.options.get(self.dropdown.selected_index())
+        } else {
+            None
+        }
+    }
+
+    /// Open the dropdown, forwarding to the internal Dropdown. While
+    /// loading, asks the host for the options instead.
+    fn open_dropdown(&mut self) -> Command<Message<T>> {
         self.open = true;
         self.dropdown.show();
         if let Some(i) = self.selected {
             self.dropdown.set_selected(i);
         }
+        if self.loading {
+            Command::message(Message::OptionsRequested)
+        } else {
+            Command::none()
+        }
     }
 
     /// Close the dropdown.
@@ -163,22 +301,35 @@ impl Select {
 
     /// Map a [`dropdown::Message`] to a [`Message`], updating internal state
     /// as needed. Returns the [`Command`] to emit.
-    fn handle_dropdown_result(&mut self, cmd: Command<dropdown::Message>) -> Command<Message> {
+    fn handle_dropdown_result(&mut self, cmd: Command<dropdown::Message>) -> Command<Message<T>> {
         cmd.map(|dmsg| match dmsg {
-            dropdown::Message::Selected(idx, val) => Message::Selected(idx, val),
+            // Enter is resolved in `update` so the typed option can be
+            // reported; the dropdown's string label is never forwarded.
+            dropdown::Message::Selected(..) => Message::Close,
             dropdown::Message::Dismissed => Message::Close,
             dropdown::Message::KeyPress(k) => Message::KeyPress(k),
         })
     }
 }
 
-impl Component for Select {
-    type Message = Message;
+/// Dropdown labels for a set of options.
+fn labels<T: Display>(options: &[T]) -> Vec<String> {
+    options.iter().map(ToString::to_string).collect()
+}
+
+impl<T: Clone + PartialEq + Display + Send + 'static> Component for Select<T> {
+    type Message = Message<T>;
 
-    fn update(&mut self, msg: Message) -> Command<Message> {
+    fn update(&mut self, msg: Message<T>) -> Command<Message<T>> {
         match msg {
             Message::KeyPress(key) if self.focus => {
-                if self.open {
+                if self.open && self.loading {
+                    // Nothing to navigate yet; only allow dismissing.
+                    if key.code == KeyCode::Esc {
+                        self.close_dropdown();
+                    }
+                    Command::none()
+                } else if self.open {
                     // Forward to Dropdown and map the result
                     let cmd = self.dropdown.update(dropdown::Message::KeyPress(key));
                     // Check if the dropdown closed itself (Selected or Dismissed)
@@ -197,22 +348,28 @@ impl Component for Select {
                     self.handle_dropdown_result(cmd)
                 } else {
                     match key.code {
-                        KeyCode::Enter | KeyCode::Char(' ') => {
-                            self.open_dropdown();
-                            Command::none()
-                        }
+                        KeyCode::Enter | KeyCode::Char(' ') => self.open_dropdown(),
                         _ => Command::none(),
                     }
                 }
             }
-            Message::Open => {
-                self.open_dropdown();
-                Command::none()
-            }
+            Message::Open => self.open_dropdown(),
             Message::Close => {
                 self.close_dropdown();
                 Command::none()
             }
+            Message::OptionsLoaded(options) => {
+                self.set_options(options);
+                Command::none()
+            }
+            Message::SpinnerTick => {
+                if self.loading {
+                    if let Some(ref mut spinner) = self.spinner {
+                        let _ = spinner.update(crate::spinner::Message::Tick);
+                    }
+                }
+                Command::none()
+            }
             _ => Command::none(),
         }
     }
@@ -228,7 +385,7 @@ impl Component for Select {
         };
 
         let display_text = if let Some(i) = self.selected {
-            Span::styled(&self.options[i], self.style.normal)
+            Span::styled(self.options[i].to_string(), self.style.normal)
         } else {
             Span::styled(&self.placeholder, Style::default().fg(Color::DarkGray))
         };
@@ -242,13 +399,246 @@ impl Component for Select {
         let paragraph = Paragraph::new(line);
         frame.render_widget(paragraph, inner);
 
+        if !self.open {
+            return;
+        }
+
+        if self.loading {
+            if let Some(ref spinner) = self.spinner {
+                // Show the spinner on the row the first option would occupy
+                let y = area.y + area.height;
+                if y < frame.area().bottom() {
+                    let spinner_area = Rect::new(area.x, y, area.width, 1);
+                    frame.render_widget(Clear, spinner_area);
+                    spinner.view(frame, spinner_area);
+                }
+            }
+            return;
+        }
+
         // Delegate dropdown overlay rendering to the internal Dropdown
-        if self.open {
-            self.dropdown.view(frame, area);
+        self.dropdown.view(frame, area);
+    }
+
+    /// The trigger is a single line; the dropdown overlay draws outside
+    /// the hinted area.
+    fn size_hint(&self, _available: Rect) -> (Constraint, Constraint) {
+        let (_, chrome_h) = crate::chrome::block_chrome(self.block.as_ref());
+        (Constraint::Min(0), Constraint::Length(1 + chrome_h))
+    }
+
+    fn subscriptions(&self) -> Vec<Subscription<Message<T>>> {
+        if self.loading && self.open {
+            if let Some(ref spinner) = self.spinner {
+                return spinner
+                    .subscriptions()
+                    .into_iter()
+                    .map(|sub| sub.map(|_| Message::SpinnerTick))
+                    .collect();
+            }
         }
+        vec![]
     }
 
     fn focused(&self) -> bool {
         self.focus
     }
+
+    /// `true` while the dropdown is open; Esc closes it.
+    fn wants_escape(&self) -> bool {
+        self.open
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn loading_select() -> Select {
+        let mut select = Select::new(vec![]).with_loading(true);
+        select.focus();
+        select
+    }
+
+    #[test]
+    fn opening_while_loading_requests_options() {
+        let mut select = loading_select();
+        let cmd = select.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(matches!(
+            cmd.into_message(),
+            Some(Message::OptionsRequested)
+        ));
+        assert!(select.is_open());
+        assert!(!select.subscriptions().is_empty());
+
+        // Navigation keys don't close the dropdown while loading
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        assert!(select.is_open());
+    }
+
+    #[test]
+    fn options_update_after_load() {
+        let mut select = loading_select();
+        select.update(Message::Open);
+        select.update(Message::OptionsLoaded(vec![
+            "main".into(),
+            "develop".into(),
+            "release".into(),
+        ]));
+
+        assert!(!select.is_loading());
+        assert!(select.is_open());
+        assert_eq!(select.options(), ["main", "develop", "release"]);
+        assert!(select.subscriptions().is_empty());
+    }
+
+    #[test]
+    fn navigation_works_on_loaded_options() {
+        let mut select = loading_select();
+        select.update(Message::Open);
+        select.update(Message::OptionsLoaded(vec![
+            "main".into(),
+            "develop".into(),
+            "release".into(),
+        ]));
+
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        let cmd = select.update(Message::KeyPress(key(KeyCode::Enter)));
+
+        match cmd.into_message() {
+            Some(Message::Selected(idx, val)) => {
+                assert_eq!(idx, 2);
+                assert_eq!(val, "release");
+            }
+            other => panic!("expected Selected, got {:?}", other),
+        }
+        assert_eq!(select.selected_value().map(String::as_str), Some("release"));
+        assert!(!select.is_open());
+    }
+
+    #[test]
+    fn set_options_keeps_selection_by_value() {
+        let mut select = Select::new(vec!["a".into(), "b".into()]);
+        select.focus();
+        select.update(Message::Open);
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        select.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert_eq!(select.selected_value().map(String::as_str), Some("b"));
+
+        select.set_options(vec!["b".into(), "c".into()]);
+        assert_eq!(select.selected_index(), Some(0));
+
+        select.set_options(vec!["x".into()]);
+        assert_eq!(select.selected_index(), None);
+    }
+
+    #[test]
+    fn view_shows_spinner_while_loading() {
+        let mut select = loading_select();
+        select.update(Message::Open);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 3)).unwrap();
+        terminal
+            .draw(|frame| select.view(frame, Rect::new(0, 0, 20, 1)))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let row: String = (0..20).map(|x| buf[(x, 1)].symbol()).collect();
+        assert!(row.contains("Loading..."), "row was {:?}", row);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Branch {
+        Main,
+        Feature(u32),
+    }
+
+    impl Display for Branch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Branch::Main => write!(f, "main"),
+                Branch::Feature(n) => write!(f, "feature-{n}"),
+            }
+        }
+    }
+
+    #[test]
+    fn selected_message_carries_typed_value() {
+        let mut select = Select::new(vec![Branch::Main, Branch::Feature(7)]);
+        select.focus();
+        select.update(Message::Open);
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        assert_eq!(select.highlighted_value(), Some(&Branch::Feature(7)));
+
+        let cmd = select.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(matches!(
+            cmd.into_message(),
+            Some(Message::Selected(1, Branch::Feature(7)))
+        ));
+        assert_eq!(select.selected_value(), Some(&Branch::Feature(7)));
+        assert_eq!(select.highlighted_value(), None);
+    }
+
+    #[test]
+    fn value_follows_highlight_after_options_reorder() {
+        let mut select = Select::new(vec![Branch::Main, Branch::Feature(1), Branch::Feature(2)]);
+        select.focus();
+        select.update(Message::Open);
+        select.update(Message::KeyPress(key(KeyCode::Down)));
+        select.update(Message::KeyPress(key(KeyCode::Enter)));
+
+        // The list is narrowed and reordered while open; the highlight
+        // follows the selected value rather than its old index.
+        select.update(Message::Open);
+        select.set_options(vec![Branch::Feature(2), Branch::Feature(1)]);
+        assert_eq!(select.highlighted_value(), Some(&Branch::Feature(1)));
+
+        let cmd = select.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(matches!(
+            cmd.into_message(),
+            Some(Message::Selected(1, Branch::Feature(1)))
+        ));
+    }
+
+    #[test]
+    fn trigger_shows_display_label() {
+        let mut select = Select::new(vec![Branch::Main, Branch::Feature(3)]);
+        select.focus();
+        select.update(Message::Open);
+        select.update(Message::KeyPress(key(KeyCode::Up)));
+        select.update(Message::KeyPress(key(KeyCode::Enter)));
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 1)).unwrap();
+        terminal
+            .draw(|frame| select.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let row: String = (0..20).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(row.starts_with("feature-3"), "row was {:?}", row);
+    }
+    #[test]
+    fn wants_escape_only_while_open() {
+        let mut select = Select::new(vec!["a".to_string(), "b".to_string()]);
+        select.focus();
+        assert!(!select.wants_escape());
+
+        select.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(select.wants_escape());
+
+        select.update(Message::KeyPress(key(KeyCode::Esc)));
+        assert!(!select.is_open());
+        assert!(!select.wants_escape());
+    }
 }
@@ -1,8 +1,9 @@
 //! Shared selectable-list navigation state.
 //!
 //! `SelectionState` tracks a cursor position and scroll offset for a
-//! collection of selectable items, providing wrapping move_up/move_down,
-//! page navigation, and home/end operations.
+//! collection of selectable items, providing move_up/move_down, page
+//! navigation, and home/end operations. Stepping past either end wraps to
+//! the other unless wrapping is turned off; page moves stop at the ends.
 
 /// Tracks cursor position and scroll offset for a selectable collection.
 pub struct SelectionState {
@@ -10,6 +11,7 @@ pub struct SelectionState {
     offset: usize,
     count: usize,
     visible: usize,
+    wrap: bool,
 }
 
 impl SelectionState {
@@ -19,6 +21,7 @@ impl SelectionState {
             offset: 0,
             count,
             visible,
+            wrap: true,
         }
     }
 
@@ -35,6 +38,16 @@ impl SelectionState {
         self.visible
     }
 
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Choose whether stepping past the last item goes to the first (and
+    /// back) or stops at the end. Page moves always stop at the ends.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
     pub fn set_count(&mut self, count: usize) {
         self.count = count;
         if self.count == 0 {
@@ -52,60 +65,52 @@ impl SelectionState {
     }
 
     pub fn move_up(&mut self) {
-        if self.count == 0 {
-            return;
-        }
-        self.cursor = if self.cursor == 0 {
-            self.count - 1
+        if self.wrap && self.cursor == 0 {
+            self.end();
         } else {
-            self.cursor - 1
-        };
-        self.ensure_visible();
+            self.step_up(1);
+        }
     }
 
     pub fn move_down(&mut self) {
-        if self.count == 0 {
-            return;
-        }
-        self.cursor = if self.cursor + 1 >= self.count {
-            0
+        if self.wrap && self.cursor + 1 >= self.count {
+            self.home();
         } else {
-            self.cursor + 1
-        };
-        self.ensure_visible();
+            self.step_down(1);
+        }
     }
 
     pub fn page_up(&mut self) {
-        if self.count == 0 {
-            return;
-        }
-        self.cursor = self.cursor.saturating_sub(self.visible);
-        self.ensure_visible();
+        self.step_up(self.visible);
     }
 
     pub fn page_down(&mut self) {
-        if self.count == 0 {
-            return;
-        }
-        self.cursor = (self.cursor + self.visible).min(self.count - 1);
-        self.ensure_visible();
+        self.step_down(self.visible);
     }
 
     pub fn half_page_up(&mut self) {
+        self.step_up(self.visible / 2);
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.step_down(self.visible / 2);
+    }
+
+    /// Move the cursor up by `by`, stopping at the first item.
+    fn step_up(&mut self, by: usize) {
         if self.count == 0 {
             return;
         }
-        let half = self.visible / 2;
-        self.cursor = self.cursor.saturating_sub(half);
+        self.cursor = self.cursor.saturating_sub(by);
         self.ensure_visible();
     }
 
-    pub fn half_page_down(&mut self) {
+    /// Move the cursor down by `by`, stopping at the last item.
+    fn step_down(&mut self, by: usize) {
         if self.count == 0 {
             return;
         }
-        let half = self.visible / 2;
-        self.cursor = (self.cursor + half).min(self.count - 1);
+        self.cursor = (self.cursor + by).min(self.count - 1);
         self.ensure_visible();
     }
 
@@ -172,6 +177,17 @@ mod tests {
         assert_eq!(s.cursor(), 1);
     }
 
+    #[test]
+    fn no_wrap_clamps_at_ends() {
+        let mut s = SelectionState::new(3, 10);
+        s.set_wrap(false);
+        s.move_up();
+        assert_eq!(s.cursor(), 0);
+        s.end();
+        s.move_down();
+        assert_eq!(s.cursor(), 2);
+    }
+
     #[test]
     fn page_down_clamps() {
         let mut s = SelectionState::new(20, 5);
@@ -180,6 +196,9 @@ mod tests {
         s.select(18);
         s.page_down(); // 18 -> 19 (clamped)
         assert_eq!(s.cursor(), 19);
+        s.page_down(); // stays on the last row, even with wrapping on
+        s.half_page_down();
+        assert_eq!(s.cursor(), 19);
     }
 
     #[test]
@@ -191,6 +210,9 @@ mod tests {
         s.select(2);
         s.page_up(); // 2 -> 0 (clamped)
         assert_eq!(s.cursor(), 0);
+        s.page_up();
+        s.half_page_up();
+        assert_eq!(s.cursor(), 0);
     }
 
     #[test]
@@ -6,7 +6,7 @@ use boba_core::subscription::Subscription;
 use boba_core::subscriptions::Every;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 use std::time::Duration;
@@ -44,6 +44,9 @@ pub enum Message {
 
 /// An animated spinner component that cycles through a set of frames
 /// at a configurable interval while spinning is active.
+///
+/// Call [`set_done`](Spinner::set_done) when the work finishes to replace
+/// the animation with a ✓ or ✗.
 pub struct Spinner {
     frames: &'static [&'static str],
     frame_index: usize,
@@ -51,6 +54,9 @@ pub struct Spinner {
     style: Style,
     interval: Duration,
     spinning: bool,
+    /// Set once the work finished, holding whether it succeeded.
+    done: Option<Result<(), ()>>,
+    done_glyphs: (&'static str, &'static str),
     id: &'static str,
 }
 
@@ -65,6 +71,8 @@ impl Spinner {
             style: Style::default().fg(Color::Cyan),
             interval: Duration::from_millis(100),
             spinning: true,
+            done: None,
+            done_glyphs: ("✓", "✗"),
             id,
         }
     }
@@ -93,9 +101,30 @@ impl Spinner {
         self
     }
 
-    /// Start the spinner animation.
+    /// Set the glyphs shown after [`set_done`](Self::set_done) (default:
+    /// `✓` and `✗`).
+    pub fn with_done_glyphs(mut self, success: &'static str, failure: &'static str) -> Self {
+        self.done_glyphs = (success, failure);
+        self
+    }
+
+    /// Start the spinner animation, clearing any done state.
     pub fn start(&mut self) {
         self.spinning = true;
+        self.done = None;
+    }
+
+    /// Stop animating and show the success glyph in green for `Ok`, or the
+    /// failure glyph in red for `Err`, before the title.
+    pub fn set_done(&mut self, result: Result<(), ()>) {
+        self.spinning = false;
+        self.done = Some(result);
+    }
+
+    /// Return whether [`set_done`](Self::set_done) was called since the last
+    /// [`start`](Self::start).
+    pub fn is_done(&self) -> bool {
+        self.done.is_some()
     }
 
     /// Stop the spinner animation.
@@ -124,19 +153,21 @@ impl Component for Spinner {
     }
 
     fn view(&self, frame: &mut Frame, area: Rect) {
-        if !self.spinning || area.width == 0 || area.height == 0 {
+        if area.width == 0 || area.height == 0 {
             return;
         }
-
-        let spinner_frame = self.frames[self.frame_index];
-        let text = if self.title.is_empty() {
-            spinner_frame.to_string()
-        } else {
-            format!("{} {}", spinner_frame, self.title)
+        let (glyph, glyph_style) = match self.done {
+            Some(Ok(())) => (self.done_glyphs.0, self.style.fg(Color::Green)),
+            Some(Err(())) => (self.done_glyphs.1, self.style.fg(Color::Red)),
+            None if self.spinning => (self.frames[self.frame_index], self.style),
+            None => return,
         };
 
-        let paragraph = Paragraph::new(Span::styled(text, self.style));
-        frame.render_widget(paragraph, area);
+        let mut spans = vec![Span::styled(glyph, glyph_style)];
+        if !self.title.is_empty() {
+            spans.push(Span::styled(format!(" {}", self.title), self.style));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
     }
 
     fn subscriptions(&self) -> Vec<Subscription<Message>> {
@@ -150,3 +181,40 @@ impl Component for Spinner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(spinner: &Spinner) -> (String, Style) {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(8, 1)).unwrap();
+        terminal.draw(|f| spinner.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let text = (0..8).map(|x| buf[(x, 0)].symbol()).collect();
+        (text, buf[(0, 0)].style())
+    }
+
+    #[test]
+    fn done_spinner_shows_result_and_stops_ticking() {
+        let mut spinner = Spinner::new("test").with_title("Done");
+        assert_eq!(spinner.subscriptions().len(), 1);
+
+        spinner.set_done(Ok(()));
+        assert!(spinner.is_done());
+        assert!(!spinner.is_spinning());
+        assert!(spinner.subscriptions().is_empty());
+        let (text, style) = render(&spinner);
+        assert_eq!(text, "✓ Done  ");
+        assert_eq!(style.fg, Some(Color::Green));
+
+        // Ticks that were already queued don't change the glyph.
+        spinner.update(Message::Tick);
+        spinner.set_done(Err(()));
+        assert_eq!(render(&spinner).0, "✗ Done  ");
+
+        spinner.start();
+        assert!(!spinner.is_done());
+        assert_eq!(render(&spinner).0, "⠋ Done  ");
+    }
+}
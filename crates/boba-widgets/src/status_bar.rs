@@ -1,12 +1,20 @@
 //! A three-section status line (left / center / right) rendered as a single
 //! row. Unlike most boba widgets this is a **stateless** ratatui `Widget`,
 //! not a `Component`, because it has no internal state or message handling.
+//!
+//! Each section holds one or more styled segments, joined by a separator,
+//! so rich vim-style lines (mode badge, file name, cursor position) can be
+//! built up piece by piece.
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
 use ratatui::Frame;
 
+use crate::runeutil::buffer_lines;
+
 /// A three-section status bar that fills one row.
 ///
 /// Renders left-aligned, centered, and right-aligned content sections.
@@ -23,11 +31,20 @@ use ratatui::Frame;
 ///     .center("Jeff v0.4")
 ///     .right("Tokens: 1234")
 ///     .style(Style::default().bg(Color::DarkGray));
+///
+/// // Several styled segments per section:
+/// let bar = StatusBar::new()
+///     .push_left(Span::styled(" NORMAL ", Style::default().bg(Color::Blue)))
+///     .push_left("src/main.rs")
+///     .push_right("utf-8")
+///     .push_right("12:4")
+///     .separator(" | ");
 /// ```
 pub struct StatusBar<'a> {
-    left: Option<Line<'a>>,
-    center: Option<Line<'a>>,
-    right: Option<Line<'a>>,
+    left: Vec<Line<'a>>,
+    center: Vec<Line<'a>>,
+    right: Vec<Line<'a>>,
+    separator: Span<'a>,
     style: Style,
 }
 
@@ -35,28 +52,54 @@ impl<'a> StatusBar<'a> {
     /// Create an empty status bar.
     pub fn new() -> Self {
         Self {
-            left: None,
-            center: None,
-            right: None,
+            left: Vec::new(),
+            center: Vec::new(),
+            right: Vec::new(),
+            separator: Span::raw(" "),
             style: Style::default(),
         }
     }
 
-    /// Set the left-aligned content.
+    /// Set the left-aligned content, replacing any segments.
     pub fn left(mut self, content: impl Into<Line<'a>>) -> Self {
-        self.left = Some(content.into());
+        self.left = vec![content.into()];
         self
     }
 
-    /// Set the center-aligned content.
+    /// Set the center-aligned content, replacing any segments.
     pub fn center(mut self, content: impl Into<Line<'a>>) -> Self {
-        self.center = Some(content.into());
+        self.center = vec![content.into()];
         self
     }
 
-    /// Set the right-aligned content.
+    /// Set the right-aligned content, replacing any segments.
     pub fn right(mut self, content: impl Into<Line<'a>>) -> Self {
-        self.right = Some(content.into());
+        self.right = vec![content.into()];
+        self
+    }
+
+    /// Append a segment to the left section.
+    pub fn push_left(mut self, segment: impl Into<Line<'a>>) -> Self {
+        self.left.push(segment.into());
+        self
+    }
+
+    /// Append a segment to the center section.
+    pub fn push_center(mut self, segment: impl Into<Line<'a>>) -> Self {
+        self.center.push(segment.into());
+        self
+    }
+
+    /// Append a segment to the right section.
+    pub fn push_right(mut self, segment: impl Into<Line<'a>>) -> Self {
+        self.right.push(segment.into());
+        self
+    }
+
+    /// Set the separator drawn between segments of the same section
+    /// (default: a single space).
+    pub fn separator(mut self, separator: impl Into<Span<'a>>) -> Self {
+        self.separator = separator.into();
         self
     }
 
@@ -71,37 +114,75 @@ impl<'a> StatusBar<'a> {
     /// This is a convenience method for use inside a `Component::view()`.
     /// For direct `Widget` rendering, use `frame.render_widget(bar, area)`.
     pub fn render(&self, frame: &mut Frame, area: Rect) {
+        self.render_to_buffer(area, frame.buffer_mut());
+    }
+
+    /// Render the bar as a single styled line of exactly `width` columns,
+    /// identical to what [`render`](Self::render) draws into a one-row area.
+    ///
+    /// Useful for placing the bar inside scrollable content such as a
+    /// [`Viewport`](crate::viewport::Viewport).
+    pub fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, 1));
+        self.render_to_buffer(buf.area, &mut buf);
+        buffer_lines(&buf)
+    }
+
+    /// Join a section's segments into one line, or `None` if it is empty.
+    fn section(&self, segments: &[Line<'a>]) -> Option<Line<'a>> {
+        let (first, rest) = segments.split_first()?;
+        let mut line = first.clone();
+        for segment in rest {
+            line.spans.push(self.separator.clone());
+            line.spans.extend(segment.spans.iter().cloned());
+        }
+        Some(line.patch_style(self.style))
+    }
+
+    fn render_to_buffer(&self, area: Rect, buf: &mut Buffer) {
+        let left = self.section(&self.left);
+        let center = self.section(&self.center);
+        let right = self.section(&self.right);
+
+        // The side sections take their display width; the center gets the
+        // rest. Without a center the sides split the row evenly so neither
+        // hides the other.
+        let width = |line: &Option<Line>| line.as_ref().map_or(0, |l| l.width() as u16);
+        let constraints = if center.is_some() {
+            [
+                Constraint::Length(width(&left)),
+                Constraint::Min(0),
+                Constraint::Length(width(&right)),
+            ]
+        } else {
+            [
+                Constraint::Max(width(&left)),
+                Constraint::Fill(1),
+                Constraint::Max(width(&right)),
+            ]
+        };
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(33),
-                Constraint::Percentage(34),
-                Constraint::Percentage(33),
-            ])
+            .constraints(constraints)
             .split(area);
 
         // Fill the whole area with the background style first.
-        let bg = ratatui::widgets::Block::default().style(self.style);
-        frame.render_widget(bg, area);
+        Block::default().style(self.style).render(area, buf);
 
-        if let Some(ref line) = self.left {
-            let styled = line.clone().patch_style(self.style);
-            let p = ratatui::widgets::Paragraph::new(styled);
-            frame.render_widget(p, chunks[0]);
+        if let Some(line) = left {
+            Paragraph::new(line).render(chunks[0], buf);
         }
 
-        if let Some(ref line) = self.center {
-            let styled = line.clone().patch_style(self.style);
-            let p = ratatui::widgets::Paragraph::new(styled)
-                .alignment(ratatui::layout::Alignment::Center);
-            frame.render_widget(p, chunks[1]);
+        if let Some(line) = center {
+            Paragraph::new(line)
+                .alignment(Alignment::Center)
+                .render(chunks[1], buf);
         }
 
-        if let Some(ref line) = self.right {
-            let styled = line.clone().patch_style(self.style);
-            let p = ratatui::widgets::Paragraph::new(styled)
-                .alignment(ratatui::layout::Alignment::Right);
-            frame.render_widget(p, chunks[2]);
+        if let Some(line) = right {
+            Paragraph::new(line)
+                .alignment(Alignment::Right)
+                .render(chunks[2], buf);
         }
     }
 }
@@ -120,9 +201,9 @@ mod tests {
     #[test]
     fn default_is_empty() {
         let bar = StatusBar::new();
-        assert!(bar.left.is_none());
-        assert!(bar.center.is_none());
-        assert!(bar.right.is_none());
+        assert!(bar.left.is_empty());
+        assert!(bar.center.is_empty());
+        assert!(bar.right.is_empty());
     }
 
     #[test]
@@ -131,9 +212,9 @@ mod tests {
             .left("left")
             .center("center")
             .right("right");
-        assert!(bar.left.is_some());
-        assert!(bar.center.is_some());
-        assert!(bar.right.is_some());
+        assert_eq!(bar.left.len(), 1);
+        assert_eq!(bar.center.len(), 1);
+        assert_eq!(bar.right.len(), 1);
     }
 
     #[test]
@@ -142,4 +223,82 @@ mod tests {
         let bar = StatusBar::new().style(s);
         assert_eq!(bar.style, s);
     }
+
+    #[test]
+    fn render_lines_matches_drawn_row() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let bar = StatusBar::new()
+            .left("main")
+            .center("boba")
+            .right("42%")
+            .style(Style::default().bg(Color::DarkGray));
+        for width in [12, 30, 61] {
+            let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+            terminal.draw(|f| bar.render(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+
+            let lines = bar.render_lines(width);
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0].width(), width as usize);
+            let mut x = 0;
+            for span in &lines[0].spans {
+                for ch in span.content.chars() {
+                    let cell = &buf[(x, 0)];
+                    assert_eq!(cell.symbol(), ch.to_string(), "width {width}, column {x}");
+                    assert_eq!(cell.style(), span.style, "width {width}, column {x}");
+                    x += 1;
+                }
+            }
+        }
+    }
+
+    fn draw(bar: &StatusBar, width: u16) -> Buffer {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, 1)).unwrap();
+        terminal.draw(|f| bar.render(f, f.area())).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row_text(buf: &Buffer) -> String {
+        (0..buf.area.width).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    #[test]
+    fn styled_segments_keep_their_styles() {
+        let mode = Style::default().fg(Color::Black).bg(Color::Blue);
+        let bar = StatusBar::new()
+            .push_left(Span::styled("NORMAL", mode))
+            .push_left("main.rs")
+            .push_right("12:4")
+            .separator(Span::styled("|", Style::default().fg(Color::Yellow)))
+            .style(Style::default().bg(Color::DarkGray));
+        let buf = draw(&bar, 30);
+
+        assert_eq!(row_text(&buf), format!("NORMAL|main.rs{:>16}", "12:4"));
+        assert_eq!(buf[(0, 0)].style().bg, Some(Color::Blue));
+        assert_eq!(buf[(0, 0)].style().fg, Some(Color::Black));
+        assert_eq!(buf[(6, 0)].style().fg, Some(Color::Yellow));
+        assert_eq!(buf[(7, 0)].style().bg, Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn section_widths_follow_display_width() {
+        // "日本語" is three wide characters: six columns.
+        let bar = StatusBar::new()
+            .push_left("日本語")
+            .push_left("ab")
+            .center("mid")
+            .right("xyz");
+        let buf = draw(&bar, 20);
+        let text = row_text(&buf);
+
+        // Wide chars occupy a cell plus a blank continuation cell, so the
+        // left section is 6 + 1 (separator) + 2 = 9 columns.
+        assert!(text.starts_with("日 本 語  ab "), "{text:?}");
+        assert!(text.ends_with("xyz"), "{text:?}");
+        // The center is centered in the 8 columns between the sides (9..17).
+        assert_eq!(&buf[(12, 0)].symbol(), &"m", "{text:?}");
+    }
 }
@@ -1,5 +1,9 @@
 //! Stopwatch component that counts up from zero.
+//!
+//! Elapsed time is measured with a [`Clock`], so it stays accurate even if
+//! ticks are delayed or dropped. Ticks only drive redraws.
 
+use crate::clock::{Clock, SystemClock};
 use boba_core::command::Command;
 use boba_core::component::Component;
 use boba_core::subscription::Subscription;
@@ -9,12 +13,13 @@ use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Messages for the stopwatch component.
 #[derive(Debug, Clone)]
 pub enum Message {
-    /// Advance elapsed time by one interval tick.
+    /// Periodic tick while running; refreshes the displayed time.
     Tick,
     /// Start the stopwatch.
     Start,
@@ -30,11 +35,14 @@ pub enum Message {
 ///
 /// Displays elapsed time in `MM:SS.T` format (minutes, seconds, tenths of a second).
 pub struct Stopwatch {
+    /// Time accumulated over previous runs.
     elapsed: Duration,
-    running: bool,
+    /// When the current run started, if running.
+    started_at: Option<Instant>,
     interval: Duration,
     style: Style,
     id: &'static str,
+    clock: Arc<dyn Clock>,
 }
 
 impl Stopwatch {
@@ -43,13 +51,20 @@ impl Stopwatch {
     pub fn new(id: &'static str) -> Self {
         Self {
             elapsed: Duration::ZERO,
-            running: false,
+            started_at: None,
             interval: Duration::from_millis(100),
             style: Style::default(),
             id,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Measure time with the given clock instead of the system clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Set the tick interval for time updates.
     pub fn with_interval(mut self, d: Duration) -> Self {
         self.interval = d;
@@ -64,33 +79,43 @@ impl Stopwatch {
 
     /// Get the total elapsed time.
     pub fn elapsed(&self) -> Duration {
-        self.elapsed
+        match self.started_at {
+            Some(start) => self.elapsed + self.clock.now().saturating_duration_since(start),
+            None => self.elapsed,
+        }
     }
 
     /// Whether the stopwatch is currently running.
     pub fn running(&self) -> bool {
-        self.running
+        self.started_at.is_some()
     }
 
     /// Start the stopwatch.
     pub fn start(&mut self) {
-        self.running = true;
+        if self.started_at.is_none() {
+            self.started_at = Some(self.clock.now());
+        }
     }
 
     /// Stop (pause) the stopwatch.
     pub fn stop(&mut self) {
-        self.running = false;
+        self.elapsed = self.elapsed();
+        self.started_at = None;
     }
 
     /// Reset elapsed time to zero and stop.
     pub fn reset(&mut self) {
         self.elapsed = Duration::ZERO;
-        self.running = false;
+        self.started_at = None;
     }
 
     /// Toggle between running and stopped.
     pub fn toggle(&mut self) {
-        self.running = !self.running;
+        if self.running() {
+            self.stop();
+        } else {
+            self.start();
+        }
     }
 }
 
@@ -108,12 +133,8 @@ impl Component for Stopwatch {
 
     fn update(&mut self, msg: Message) -> Command<Message> {
         match msg {
-            Message::Tick => {
-                if self.running {
-                    self.elapsed += self.interval;
-                }
-                Command::none()
-            }
+            // Elapsed time is read from the clock; the tick only triggers a redraw.
+            Message::Tick => Command::none(),
             Message::Start => {
                 self.start();
                 Command::none()
@@ -138,14 +159,14 @@ impl Component for Stopwatch {
             return;
         }
 
-        let text = format_duration(self.elapsed);
+        let text = format_duration(self.elapsed());
         let span = Span::styled(text, self.style);
         let paragraph = Paragraph::new(span);
         frame.render_widget(paragraph, area);
     }
 
     fn subscriptions(&self) -> Vec<Subscription<Message>> {
-        if self.running {
+        if self.running() {
             vec![
                 boba_core::subscription::subscribe(Every::new(self.interval, self.id))
                     .map(|_: std::time::Instant| Message::Tick),
@@ -155,3 +176,38 @@ impl Component for Stopwatch {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    #[test]
+    fn elapsed_follows_manual_clock() {
+        let clock = ManualClock::new();
+        let mut sw = Stopwatch::new("sw").with_clock(clock.clone());
+        sw.start();
+        clock.advance(Duration::from_millis(1500));
+        sw.update(Message::Tick);
+        assert_eq!(sw.elapsed(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn stopped_time_is_not_counted() {
+        let clock = ManualClock::new();
+        let mut sw = Stopwatch::new("sw").with_clock(clock.clone());
+        sw.start();
+        clock.advance(Duration::from_secs(2));
+        sw.stop();
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(sw.elapsed(), Duration::from_secs(2));
+
+        sw.toggle();
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(sw.elapsed(), Duration::from_secs(3));
+
+        sw.reset();
+        assert_eq!(sw.elapsed(), Duration::ZERO);
+        assert!(!sw.running());
+    }
+}
@@ -1,11 +1,13 @@
 //! Data table component with row and column navigation, sorting, per-row
-//! styling, and CSV parsing.
+//! styling, and CSV import/export.
 
+use crate::runeutil;
 use crate::selection::SelectionState;
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Block, Cell as RatatuiCell, Row, Table as RatatuiTable, TableState};
 use ratatui::Frame;
@@ -22,6 +24,10 @@ pub enum Message {
     Confirm(usize),
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// A data table with column/row navigation.
 ///
 /// Renders column headers and data rows with keyboard-driven selection.
@@ -152,12 +158,33 @@ pub struct Table {
     selected_col: Option<usize>,
     block: Option<Block<'static>>,
     row_style_fn: Option<RowStyleFn>,
+    /// Per-column overflow policy; missing entries clip.
+    overflow: Vec<ColumnOverflow>,
+    /// Number of leading columns that stay put during horizontal scrolling.
+    frozen_columns: usize,
+    /// Number of scrollable columns hidden off the left edge.
+    col_offset: usize,
+    /// Scrollable columns that fit at the last render, used to keep the
+    /// selected column on screen.
+    visible_columns: StdCell<usize>,
     key_seq: boba_core::key_sequence::KeySequenceTracker,
     key_bindings: TableKeyBindings,
 }
 
 type RowStyleFn = Box<dyn Fn(usize, &[String]) -> Style + Send>;
 
+/// How a column shows text wider than the column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnOverflow {
+    /// Cut the text at the column edge (default).
+    #[default]
+    Clip,
+    /// Cut the text and end it with `…`, never splitting a character.
+    Truncate,
+    /// Wrap the text onto more lines, making the row taller.
+    Wrap,
+}
+
 /// Style configuration for the table.
 #[derive(Debug, Clone)]
 pub struct TableStyle {
@@ -221,6 +248,10 @@ impl Table {
             selected_col: None,
             block: None,
             row_style_fn: None,
+            overflow: Vec::new(),
+            frozen_columns: 0,
+            col_offset: 0,
+            visible_columns: StdCell::new(usize::MAX),
             key_seq: boba_core::key_sequence::KeySequenceTracker::new(),
             key_bindings: TableKeyBindings::default(),
         }
@@ -237,24 +268,47 @@ impl Table {
         &self.key_bindings
     }
 
-    /// Create a table from simple CSV data.
+    /// Create a table from CSV data.
     ///
-    /// The first non-empty line is treated as column headers. Remaining non-empty
-    /// lines become data rows. Fields are split by comma and trimmed. Quoted fields
-    /// and escaped commas are not handled.
+    /// The first non-empty record is treated as column headers. Remaining
+    /// non-empty records become data rows. Unquoted fields are trimmed.
+    /// Fields wrapped in double quotes keep their content verbatim and may
+    /// contain commas, newlines, and quotes escaped as `""`.
     pub fn from_csv(data: &str) -> Self {
-        let lines: Vec<&str> = data.lines().filter(|l| !l.trim().is_empty()).collect();
-        if lines.is_empty() {
+        let mut records = parse_delimited(data, ',').into_iter();
+        let Some(headers) = records.next() else {
             return Self::new(Vec::new(), Vec::new());
-        }
+        };
+        Self::new(headers, records.collect())
+    }
 
-        let headers: Vec<String> = lines[0].split(',').map(|s| s.trim().to_string()).collect();
-        let rows: Vec<Vec<String>> = lines[1..]
-            .iter()
-            .map(|line| line.split(',').map(|s| s.trim().to_string()).collect())
-            .collect();
+    /// Serialize the headers and rows as CSV, in display order.
+    ///
+    /// Fields containing commas, quotes, newlines, or leading/trailing
+    /// whitespace are quoted, so the output reads back identically through
+    /// [`from_csv`](Self::from_csv).
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',')
+    }
 
-        Self::new(headers, rows)
+    /// Serialize the headers and rows as tab-separated values, quoting
+    /// fields the same way as [`to_csv`](Self::to_csv).
+    pub fn to_tsv(&self) -> String {
+        self.to_delimited('\t')
+    }
+
+    fn to_delimited(&self, delimiter: char) -> String {
+        let mut out = String::new();
+        for record in std::iter::once(&self.headers).chain(&self.rows) {
+            for (i, field) in record.iter().enumerate() {
+                if i > 0 {
+                    out.push(delimiter);
+                }
+                push_field(&mut out, field, delimiter);
+            }
+            out.push('\n');
+        }
+        out
     }
 
     /// Override the column width constraints.
@@ -263,6 +317,42 @@ impl Table {
         self
     }
 
+    /// Set how each column handles text wider than the column, in column
+    /// order. Columns without an entry clip.
+    ///
+    /// ```ignore
+    /// use boba_widgets::table::ColumnOverflow;
+    /// let table = Table::new(headers, rows)
+    ///     .with_overflow(vec![ColumnOverflow::Truncate, ColumnOverflow::Wrap]);
+    /// ```
+    pub fn with_overflow(mut self, overflow: Vec<ColumnOverflow>) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Pin the first `n` columns, such as row labels, so they stay on screen
+    /// while the remaining columns scroll horizontally.
+    ///
+    /// Columns scroll when their fixed widths ([`Constraint::Length`] or
+    /// [`Constraint::Min`]) don't all fit; moving the selected column past
+    /// either edge brings it into view.
+    pub fn with_frozen_columns(mut self, n: usize) -> Self {
+        self.frozen_columns = n;
+        self
+    }
+
+    /// Number of scrollable columns currently hidden off the left edge.
+    pub fn column_offset(&self) -> usize {
+        self.col_offset
+    }
+
+    /// Scroll so that the first `n` scrollable columns are hidden. Frozen
+    /// columns are never scrolled away.
+    pub fn set_column_offset(&mut self, n: usize) {
+        let scrollable = self.widths.len().saturating_sub(self.frozen_columns);
+        self.col_offset = n.min(scrollable.saturating_sub(1));
+    }
+
     /// Set the table border title.
     pub fn with_title(mut self, title: impl Into<String>) -> Self {
         self.title = title.into();
@@ -319,6 +409,7 @@ impl Table {
     /// Set the selected column. Pass `None` to disable column navigation.
     pub fn set_selected_column(&mut self, col: Option<usize>) {
         self.selected_col = col;
+        self.scroll_to_selected_column();
     }
 
     /// Replace the data rows, clamping the selection to the new bounds.
@@ -384,6 +475,102 @@ impl Table {
         self.rows.len()
     }
 
+    fn column_overflow(&self, col: usize) -> ColumnOverflow {
+        self.overflow.get(col).copied().unwrap_or_default()
+    }
+
+    /// Width left for columns once the highlight symbol is drawn.
+    fn columns_area(&self, width: u16, has_selection: bool) -> u16 {
+        let selection = if has_selection {
+            runeutil::display_width(&self.style.highlight_symbol) as u16
+        } else {
+            0
+        };
+        width.saturating_sub(selection)
+    }
+
+    /// Indices of the columns on screen: the frozen ones, then as many
+    /// scrollable columns from the offset as fit in `width`.
+    ///
+    /// Only fixed widths count towards fitting, so flexible constraints never
+    /// push columns off screen.
+    fn shown_columns(&self, width: u16) -> Vec<usize> {
+        let count = self.widths.len();
+        let frozen = self.frozen_columns.min(count);
+        let fixed = |col: usize| match self.widths[col] {
+            Constraint::Length(w) | Constraint::Min(w) => w as usize,
+            _ => 0,
+        };
+        let mut used: usize = (0..frozen).map(|col| fixed(col) + 1).sum();
+        let mut shown: Vec<usize> = (0..frozen).collect();
+        let start = frozen + self.col_offset.min(count.saturating_sub(frozen + 1));
+        for col in start..count {
+            // Always show at least one scrollable column.
+            if shown.len() > frozen && used + fixed(col) > width as usize {
+                break;
+            }
+            used += fixed(col) + 1;
+            shown.push(col);
+        }
+        shown
+    }
+
+    /// Column widths as ratatui's table lays out the `shown` columns, indexed
+    /// by column. Columns that are scrolled away have zero width.
+    fn column_widths(&self, width: u16, shown: &[usize]) -> Vec<u16> {
+        let area = Rect::new(0, 0, width, 1);
+        let mut widths = vec![0; self.widths.len()];
+        let rects = Layout::horizontal(shown.iter().map(|&col| self.widths[col]))
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(area);
+        for (&col, rect) in shown.iter().zip(rects.iter()) {
+            widths[col] = rect.width;
+        }
+        widths
+    }
+
+    /// Adjust the column offset so the selected column is on screen.
+    fn scroll_to_selected_column(&mut self) {
+        let Some(col) = self.selected_col else {
+            return;
+        };
+        let Some(rel) = col.checked_sub(self.frozen_columns) else {
+            return;
+        };
+        let visible = self.visible_columns.get().max(1);
+        if rel < self.col_offset {
+            self.col_offset = rel;
+        } else if rel >= self.col_offset.saturating_add(visible) {
+            self.col_offset = rel + 1 - visible;
+        }
+    }
+
+    /// Cell text after applying the column's overflow policy, one entry per
+    /// line.
+    fn fit_cell(&self, text: &str, col: usize, widths: &[u16]) -> Vec<String> {
+        let width = widths.get(col).copied().unwrap_or(0) as usize;
+        match self.column_overflow(col) {
+            ColumnOverflow::Clip => vec![text.to_string()],
+            ColumnOverflow::Truncate => vec![runeutil::truncate(text, width, "…")],
+            ColumnOverflow::Wrap => wrap_text(text, width),
+        }
+    }
+
+    /// Screen rows needed by data row `idx`.
+    fn row_height(&self, idx: usize, widths: &[u16]) -> usize {
+        if !self.overflow.contains(&ColumnOverflow::Wrap) {
+            return 1;
+        }
+        self.rows[idx]
+            .iter()
+            .enumerate()
+            .filter(|(col, _)| self.column_overflow(*col) == ColumnOverflow::Wrap)
+            .map(|(col, text)| self.fit_cell(text, col, widths).len())
+            .max()
+            .unwrap_or(1)
+    }
+
     /// Move the selected column left.
     fn move_col_left(&mut self) {
         if self.headers.is_empty() {
@@ -393,6 +580,7 @@ impl Table {
             Some(c) => self.selected_col = Some(c.saturating_sub(1)),
             None => self.selected_col = Some(0),
         }
+        self.scroll_to_selected_column();
     }
 
     /// Move the selected column right.
@@ -405,6 +593,7 @@ impl Table {
             Some(c) => self.selected_col = Some((c + 1).min(max_col)),
             None => self.selected_col = Some(0),
         }
+        self.scroll_to_selected_column();
     }
 
     /// Move to the next column, wrapping around to the first.
@@ -418,6 +607,7 @@ impl Table {
             }
             None => self.selected_col = Some(0),
         }
+        self.scroll_to_selected_column();
     }
 }
 
@@ -545,20 +735,56 @@ impl Component for Table {
         self.visible_height
             .set(if data_height > 0 { data_height } else { 10 });
 
-        let header_cells: Vec<RatatuiCell> = self
-            .headers
+        let selected_row = self.state.selected();
+        let columns_width = self.columns_area(inner.width, selected_row.is_some());
+        let shown = self.shown_columns(columns_width);
+        let frozen = self.frozen_columns.min(shown.len());
+        self.visible_columns.set(shown.len() - frozen);
+        let col_widths = self.column_widths(columns_width, &shown);
+
+        let header_cells: Vec<RatatuiCell> = shown
             .iter()
+            .filter_map(|&col| self.headers.get(col))
             .map(|h| RatatuiCell::from(h.as_str()).style(self.style.header))
             .collect();
         let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-        let selected_row = self.state.selected();
+        // Only materialize the rows that fit on screen. The window starts at
+        // the stored scroll offset and is nudged so the selection stays
+        // visible even if the area changed size since the last update.
+        // Wrapped rows can be taller than one line, so fit by height.
+        let window_height = data_height.max(1);
+        let mut start = self.state.offset().min(self.rows.len().saturating_sub(1));
+        if let Some(sel) = selected_row {
+            if sel < start {
+                start = sel;
+            } else {
+                // The earliest start that still shows the selection.
+                let mut earliest = sel;
+                let mut used = self.row_height(sel, &col_widths);
+                while earliest > start {
+                    let h = self.row_height(earliest - 1, &col_widths);
+                    if used + h > window_height {
+                        break;
+                    }
+                    used += h;
+                    earliest -= 1;
+                }
+                start = start.max(earliest);
+            }
+        }
+        let mut end = start;
+        let mut used = 0;
+        while end < self.rows.len() && (end == start || used < window_height) {
+            used += self.row_height(end, &col_widths);
+            end += 1;
+        }
 
-        let rows: Vec<Row> = self
-            .rows
+        let rows: Vec<Row> = self.rows[start..end]
             .iter()
             .enumerate()
-            .map(|(row_idx, row)| {
+            .map(|(i, row)| {
+                let row_idx = start + i;
                 let base_style = if let Some(ref style_fn) = self.row_style_fn {
                     style_fn(row_idx, row)
                 } else {
@@ -567,11 +793,14 @@ impl Component for Table {
 
                 let is_selected_row = selected_row == Some(row_idx);
 
-                let cells: Vec<RatatuiCell> = row
+                let mut height = 1;
+                let cells: Vec<RatatuiCell> = shown
                     .iter()
-                    .enumerate()
+                    .filter_map(|&col_idx| row.get(col_idx).map(|c| (col_idx, c)))
                     .map(|(col_idx, c)| {
-                        let cell = RatatuiCell::from(c.as_str());
+                        let lines = self.fit_cell(c, col_idx, &col_widths);
+                        height = height.max(lines.len());
+                        let cell = RatatuiCell::from(lines.join("\n"));
                         // If this is the active cell (selected row + selected col),
                         // apply the active cell style.
                         if is_selected_row {
@@ -585,16 +814,20 @@ impl Component for Table {
                     })
                     .collect();
 
-                Row::new(cells).style(base_style)
+                Row::new(cells).style(base_style).height(height as u16)
             })
             .collect();
 
-        let table = RatatuiTable::new(rows, &self.widths)
+        let widths = shown.iter().map(|&col| self.widths[col]);
+        let table = RatatuiTable::new(rows, widths)
             .header(header)
             .row_highlight_style(self.style.selected)
             .highlight_symbol(self.style.highlight_symbol.as_str());
 
-        frame.render_stateful_widget(table, inner, &mut self.state.clone());
+        // The window already begins at `start`, so remap the selection into
+        // window-relative coordinates and render without a further offset.
+        let mut state = TableState::default().with_selected(selected_row.map(|sel| sel - start));
+        frame.render_stateful_widget(table, inner, &mut state);
     }
 
     fn focused(&self) -> bool {
@@ -602,6 +835,124 @@ impl Component for Table {
     }
 }
 
+/// Greedily wrap `text` at spaces to lines of at most `width` columns,
+/// hard-breaking words that are longer than a line.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![String::new()];
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in text.split(' ') {
+        let mut word = word;
+        let word_width = runeutil::display_width(word);
+        if line_width > 0 && line_width + 1 + word_width <= width {
+            line.push(' ');
+            line.push_str(word);
+            line_width += 1 + word_width;
+            continue;
+        }
+        if line_width > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        // Hard-break words wider than the column.
+        while runeutil::display_width(word) > width {
+            let mut taken = 0;
+            let split = word
+                .char_indices()
+                .find(|(_, c)| {
+                    taken += runeutil::display_width(c.encode_utf8(&mut [0; 4]));
+                    taken > width
+                })
+                .map_or(word.len(), |(i, _)| i)
+                // Always make progress, even if one character is too wide.
+                .max(word.chars().next().map_or(0, char::len_utf8));
+            lines.push(word[..split].to_string());
+            word = &word[split..];
+        }
+        line_width = runeutil::display_width(word);
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Append `field` to `out`, quoting it if it would not read back verbatim.
+fn push_field(out: &mut String, field: &str, delimiter: char) {
+    let needs_quotes = field.contains([delimiter, '"', '\n', '\r']) || field.trim() != field;
+    if needs_quotes {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+/// Split delimited text into records, honouring double-quoted fields.
+/// Blank records are skipped.
+fn parse_delimited(data: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    let mut chars = data.chars().peekable();
+
+    let finish_field = |field: &mut String, quoted: &mut bool| {
+        let value = if *quoted {
+            std::mem::take(field)
+        } else {
+            let v = field.trim().to_string();
+            field.clear();
+            v
+        };
+        *quoted = false;
+        value
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == delimiter {
+            record.push(finish_field(&mut field, &mut quoted));
+        } else if c == '\n' {
+            record.push(finish_field(&mut field, &mut quoted));
+            let record = std::mem::take(&mut record);
+            if !(record.len() == 1 && record[0].is_empty()) {
+                records.push(record);
+            }
+        } else if c == '"' && !quoted && field.trim().is_empty() {
+            field.clear();
+            in_quotes = true;
+            quoted = true;
+        } else if c == '\r' || (quoted && c.is_whitespace()) {
+            // Line-ending noise, or padding after a closing quote.
+        } else {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || quoted || !record.is_empty() {
+        record.push(finish_field(&mut field, &mut quoted));
+        if !(record.len() == 1 && record[0].is_empty()) {
+            records.push(record);
+        }
+    }
+    records
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -741,6 +1092,42 @@ mod tests {
         assert_eq!(t.rows[0], vec!["Alice", "30"]);
     }
 
+    #[test]
+    fn from_csv_quoted_fields() {
+        let csv = "Name,Note\n\"Smith, J\",\"said \"\"hi\"\"\"\n\"multi\nline\", x \n";
+        let t = Table::from_csv(csv);
+        assert_eq!(t.rows[0], vec!["Smith, J", "said \"hi\""]);
+        assert_eq!(t.rows[1], vec!["multi\nline", "x"]);
+    }
+
+    #[test]
+    fn to_csv_round_trips_tricky_fields() {
+        let t = Table::new(
+            vec!["Name".into(), "Note".into()],
+            vec![
+                vec!["Smith, J".into(), "said \"hi\"".into()],
+                vec!["multi\nline".into(), "  padded ".into()],
+                vec!["plain".into(), "".into()],
+            ],
+        );
+        let csv = t.to_csv();
+        assert!(csv.starts_with("Name,Note\n\"Smith, J\",\"said \"\"hi\"\"\"\n"));
+
+        let back = Table::from_csv(&csv);
+        assert_eq!(back.headers, t.headers);
+        assert_eq!(back.rows, t.rows);
+        assert_eq!(back.to_csv(), csv);
+    }
+
+    #[test]
+    fn to_tsv_quotes_only_when_needed() {
+        let t = Table::new(
+            vec!["A".into(), "B".into()],
+            vec![vec!["x, y".into(), "tab\there".into()]],
+        );
+        assert_eq!(t.to_tsv(), "A\tB\nx, y\t\"tab\there\"\n");
+    }
+
     // ── Per-row styling tests ──
 
     #[test]
@@ -774,6 +1161,142 @@ mod tests {
         assert_eq!(f(1, &["2".to_string()]), Style::default().fg(Color::Red));
     }
 
+    // ── Virtualized rendering tests ──
+
+    fn counting_table(row_count: usize) -> (Table, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let rows = (0..row_count).map(|i| vec![i.to_string()]).collect();
+        let mut t = Table::new(vec!["N".into()], rows).with_row_style(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Style::default()
+        });
+        t.focus();
+        (t, calls)
+    }
+
+    fn render_table(t: &Table, width: u16, height: u16) -> String {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|frame| t.view(frame, frame.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let mut out = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                out.push_str(buf[(x, y)].symbol());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn view_materializes_only_visible_rows() {
+        use std::sync::atomic::Ordering;
+
+        let (t, calls) = counting_table(100_000);
+        render_table(&t, 20, 12);
+        // 12 rows minus header and margin leaves 10 data rows.
+        assert!(calls.load(Ordering::SeqCst) <= 10);
+    }
+
+    #[test]
+    fn view_highlights_selection_deep_in_large_table() {
+        use std::sync::atomic::Ordering;
+
+        let (mut t, calls) = counting_table(100_000);
+        render_table(&t, 20, 12);
+        t.set_cursor(54_321);
+        calls.store(0, Ordering::SeqCst);
+        let out = render_table(&t, 20, 12);
+        assert!(calls.load(Ordering::SeqCst) <= 10);
+        let selected_line = out.lines().find(|l| l.contains("▸")).unwrap();
+        assert!(selected_line.contains("54321"));
+    }
+
+    #[test]
+    fn view_keeps_selection_visible_after_shrink() {
+        let (mut t, _) = counting_table(100);
+        render_table(&t, 20, 12);
+        t.set_cursor(9);
+        // Shrink the area so only 3 data rows fit; the selection must stay on screen.
+        let out = render_table(&t, 20, 5);
+        let selected_line = out.lines().find(|l| l.contains("▸")).unwrap();
+        assert!(selected_line.contains('9'));
+    }
+
+    // ── Column overflow ──
+
+    fn overflow_table(overflow: ColumnOverflow) -> Table {
+        Table::new(
+            vec!["Name".into(), "Note".into()],
+            vec![
+                vec!["a".into(), "日本語のテキスト".into()],
+                vec!["b".into(), "short".into()],
+            ],
+        )
+        .with_widths(vec![Constraint::Length(3), Constraint::Length(7)])
+        .with_overflow(vec![ColumnOverflow::Clip, overflow])
+    }
+
+    #[test]
+    fn truncated_cell_ends_with_ellipsis_on_char_boundary() {
+        let t = overflow_table(ColumnOverflow::Truncate);
+        let out = render_table(&t, 16, 5);
+        let first = out.lines().nth(2).unwrap();
+        assert!(first.contains('…'), "{out}");
+        assert!(!first.contains('の'), "{out}");
+
+        // Seven columns: three double-width characters plus the ellipsis.
+        assert_eq!(t.fit_cell("日本語のテキスト", 1, &[3, 7]), vec!["日本語…"]);
+        // Never half a wide character: six columns keep only two.
+        assert_eq!(t.fit_cell("日本語のテキスト", 1, &[3, 6]), vec!["日本…"]);
+        assert_eq!(t.fit_cell("short", 1, &[3, 7]), vec!["short"]);
+    }
+
+    #[test]
+    fn wrapped_cell_makes_its_row_taller() {
+        let t = Table::new(
+            vec!["N".into(), "Note".into()],
+            vec![
+                vec!["1".into(), "the quick brown fox".into()],
+                vec!["2".into(), "ok".into()],
+            ],
+        )
+        .with_widths(vec![Constraint::Length(1), Constraint::Length(9)])
+        .with_overflow(vec![ColumnOverflow::Clip, ColumnOverflow::Wrap]);
+        assert_eq!(t.row_height(0, &[1, 9]), 2);
+        assert_eq!(t.row_height(1, &[1, 9]), 1);
+
+        let out = render_table(&t, 14, 7);
+        let rows: Vec<&str> = out.lines().collect();
+        assert!(rows[2].contains("1 the quick"), "{out}");
+        assert!(rows[3].contains("brown fox"), "{out}");
+        assert!(rows[4].contains("2 ok"), "{out}");
+    }
+
+    #[test]
+    fn wrap_text_breaks_long_words() {
+        assert_eq!(wrap_text("abcdefgh ij", 3), vec!["abc", "def", "gh", "ij"]);
+        assert_eq!(wrap_text("日本", 1), vec!["日", "本"]);
+    }
+
+    #[test]
+    fn tall_rows_keep_selection_visible() {
+        let rows = (0..10).map(|i| vec![format!("{i} aaaa bbbb")]).collect();
+        let mut t = Table::new(vec!["N".into()], rows)
+            .with_widths(vec![Constraint::Length(6)])
+            .with_overflow(vec![ColumnOverflow::Wrap]);
+        t.set_cursor(7);
+        // Three data lines fit: only the selected row (two lines high) shows.
+        let out = render_table(&t, 10, 5);
+        let selected_line = out.lines().find(|l| l.contains("▸")).unwrap();
+        assert!(selected_line.contains("7 aaaa"), "{out}");
+    }
+
     // ── selected_column getter/setter ──
 
     #[test]
@@ -785,4 +1308,75 @@ mod tests {
         t.set_selected_column(None);
         assert_eq!(t.selected_column(), None);
     }
+
+    // ── frozen columns ──
+
+    fn wide_table() -> Table {
+        let headers = ["Name", "A", "B", "C", "D"].map(String::from).to_vec();
+        let rows = vec![["row1", "a1", "b1", "c1", "d1"].map(String::from).to_vec()];
+        let mut t = Table::new(headers, rows)
+            .with_widths(vec![Constraint::Length(6); 5])
+            .with_frozen_columns(1);
+        t.focus();
+        t
+    }
+
+    #[test]
+    fn scrolling_right_keeps_frozen_columns_pinned() {
+        let mut t = wide_table();
+        // 24 columns minus the highlight symbol fit the frozen column and
+        // two scrollable ones.
+        let before = render_table(&t, 24, 4);
+        let lines: Vec<&str> = before.lines().collect();
+        assert!(lines[0].starts_with("  Name   A      B"), "{before}");
+        assert!(!lines[0].contains('C'), "{before}");
+        assert!(lines[2].starts_with("▸ row1   a1     b1"), "{before}");
+
+        // Select Name, A, B, then C, which scrolls one column.
+        for _ in 0..4 {
+            t.update(key_event(KeyCode::Right));
+        }
+        assert_eq!(t.column_offset(), 1);
+        let after = render_table(&t, 24, 4);
+        let lines: Vec<&str> = after.lines().collect();
+        assert!(lines[0].starts_with("  Name   B      C"), "{after}");
+        assert!(lines[2].starts_with("▸ row1   b1     c1"), "{after}");
+
+        t.update(key_event(KeyCode::Right));
+        let after = render_table(&t, 24, 4);
+        assert!(
+            after
+                .lines()
+                .next()
+                .unwrap()
+                .starts_with("  Name   C      D"),
+            "{after}"
+        );
+
+        // Moving back left scrolls the other way; the frozen column stays.
+        for _ in 0..3 {
+            t.update(key_event(KeyCode::Left));
+        }
+        assert_eq!(t.selected_column(), Some(1));
+        assert_eq!(t.column_offset(), 0);
+        let after = render_table(&t, 24, 4);
+        assert!(
+            after
+                .lines()
+                .next()
+                .unwrap()
+                .starts_with("  Name   A      B"),
+            "{after}"
+        );
+    }
+
+    #[test]
+    fn flexible_widths_never_scroll() {
+        let mut t = wide_table().with_widths(vec![Constraint::Percentage(20); 5]);
+        render_table(&t, 40, 4);
+        t.set_selected_column(Some(4));
+        assert_eq!(t.column_offset(), 0);
+        let out = render_table(&t, 40, 4);
+        assert!(out.lines().next().unwrap().contains('D'), "{out}");
+    }
 }
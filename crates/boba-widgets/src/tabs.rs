@@ -1,13 +1,26 @@
 //! Tab navigation component for switching between views.
+//!
+//! Tabs can carry a dirty marker (`•` prefix) and a badge (`(3)` suffix).
+//! When the labels don't fit the available width, the row scrolls so the
+//! selected tab stays visible.
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Tabs as RatatuiTabs};
 use ratatui::Frame;
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// Prefix shown before the title of a tab with unsaved changes.
+const DIRTY_MARKER: &str = "• ";
+
+/// Padding ratatui places on each side of a tab label.
+const TAB_PADDING: usize = 2;
 
 /// Messages for the tabs component.
 #[derive(Debug, Clone)]
@@ -18,10 +31,16 @@ pub enum Message {
     Select(usize),
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// A tab navigation component that renders a horizontal row of labeled tabs
 /// and tracks the currently selected index.
 pub struct Tabs {
     titles: Vec<String>,
+    badges: Vec<Option<String>>,
+    dirty: Vec<bool>,
     selected: usize,
     focus: bool,
     style: TabsStyle,
@@ -37,6 +56,10 @@ pub struct TabsStyle {
     pub selected: Style,
     /// String used as a divider between tab labels.
     pub divider: String,
+    /// Style applied to the dirty marker.
+    pub dirty: Style,
+    /// Style applied to badge text.
+    pub badge: Style,
 }
 
 impl Default for TabsStyle {
@@ -47,6 +70,8 @@ impl Default for TabsStyle {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
             divider: " | ".to_string(),
+            dirty: Style::default().fg(Color::Yellow),
+            badge: Style::default().fg(Color::Magenta),
         }
     }
 }
@@ -54,8 +79,11 @@ impl Default for TabsStyle {
 impl Tabs {
     /// Create a new tabs component with the given tab titles.
     pub fn new(titles: Vec<String>) -> Self {
+        let count = titles.len();
         Self {
             titles,
+            badges: vec![None; count],
+            dirty: vec![false; count],
             selected: 0,
             focus: false,
             style: TabsStyle::default(),
@@ -97,6 +125,81 @@ impl Tabs {
         }
     }
 
+    /// Set or clear the badge shown after the title of the tab at `index`,
+    /// e.g. a count rendered as `(3)`. No-op if the index is out of bounds.
+    pub fn set_badge(&mut self, index: usize, badge: Option<String>) {
+        if let Some(slot) = self.badges.get_mut(index) {
+            *slot = badge;
+        }
+    }
+
+    /// Mark the tab at `index` as having unsaved changes. Dirty tabs are
+    /// prefixed with `•`. No-op if the index is out of bounds.
+    pub fn set_dirty(&mut self, index: usize, dirty: bool) {
+        if let Some(slot) = self.dirty.get_mut(index) {
+            *slot = dirty;
+        }
+    }
+
+    /// Return the badge for the tab at `index`, if any.
+    pub fn badge(&self, index: usize) -> Option<&str> {
+        self.badges.get(index).and_then(|b| b.as_deref())
+    }
+
+    /// Return whether the tab at `index` is marked dirty.
+    pub fn is_dirty(&self, index: usize) -> bool {
+        self.dirty.get(index).copied().unwrap_or(false)
+    }
+
+    /// Build the label for a tab, including dirty marker and badge.
+    fn label(&self, index: usize) -> Line<'_> {
+        let mut spans = Vec::with_capacity(3);
+        if self.is_dirty(index) {
+            spans.push(Span::styled(DIRTY_MARKER, self.style.dirty));
+        }
+        spans.push(Span::raw(self.titles[index].as_str()));
+        if let Some(badge) = self.badge(index) {
+            spans.push(Span::styled(format!(" ({badge})"), self.style.badge));
+        }
+        Line::from(spans)
+    }
+
+    /// Rendered width of a tab, including padding.
+    fn tab_width(&self, index: usize) -> usize {
+        self.label(index).width() + TAB_PADDING
+    }
+
+    /// The range of tabs that fit in `width` columns while keeping the
+    /// selected tab visible.
+    fn visible_range(&self, width: usize) -> Range<usize> {
+        if self.titles.is_empty() {
+            return 0..0;
+        }
+        let divider = self.style.divider.width();
+        let selected = self.selected.min(self.titles.len() - 1);
+
+        // Scroll the start forward until the selected tab fits.
+        let mut start = 0;
+        let mut used: usize =
+            (0..=selected).map(|i| self.tab_width(i)).sum::<usize>() + selected * divider;
+        while used > width && start < selected {
+            used -= self.tab_width(start) + divider;
+            start += 1;
+        }
+
+        // Then show as many following tabs as fit.
+        let mut end = selected + 1;
+        while end < self.titles.len() {
+            let next = used + divider + self.tab_width(end);
+            if next > width {
+                break;
+            }
+            used = next;
+            end += 1;
+        }
+        start..end
+    }
+
     /// Advance to the next tab, wrapping around to the first tab after the last.
     pub fn select_next(&mut self) {
         if !self.titles.is_empty() {
@@ -146,10 +249,16 @@ impl Component for Tabs {
     }
 
     fn view(&self, frame: &mut Frame, area: Rect) {
-        let titles: Vec<Line> = self.titles.iter().map(|t| Line::raw(t.as_str())).collect();
+        let inner_width = match self.block {
+            Some(ref block) => block.inner(area).width,
+            None => area.width,
+        };
+        let range = self.visible_range(inner_width as usize);
+        let selected = self.selected.saturating_sub(range.start);
+        let titles: Vec<Line> = range.map(|i| self.label(i)).collect();
 
         let mut tabs = RatatuiTabs::new(titles)
-            .select(self.selected)
+            .select(selected)
             .style(self.style.normal)
             .highlight_style(self.style.selected)
             .divider(&self.style.divider);
@@ -164,3 +273,74 @@ impl Component for Tabs {
         self.focus
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn render(tabs: &Tabs, width: u16) -> String {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, 1)).unwrap();
+        terminal
+            .draw(|frame| tabs.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..width).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    #[test]
+    fn badges_and_dirty_markers_render() {
+        let mut tabs = Tabs::new(titles(&["main.rs", "lib.rs"]));
+        tabs.set_dirty(0, true);
+        tabs.set_badge(1, Some("3".into()));
+
+        let row = render(&tabs, 40);
+        assert!(row.contains("• main.rs"), "row was {:?}", row);
+        assert!(row.contains("lib.rs (3)"), "row was {:?}", row);
+
+        tabs.set_dirty(0, false);
+        tabs.set_badge(1, None);
+        let row = render(&tabs, 40);
+        assert!(!row.contains('•'));
+        assert!(!row.contains("(3)"));
+    }
+
+    #[test]
+    fn out_of_bounds_decorations_are_ignored() {
+        let mut tabs = Tabs::new(titles(&["a"]));
+        tabs.set_dirty(5, true);
+        tabs.set_badge(5, Some("1".into()));
+        assert!(!tabs.is_dirty(5));
+        assert_eq!(tabs.badge(5), None);
+    }
+
+    #[test]
+    fn decorations_count_towards_tab_width() {
+        let mut tabs = Tabs::new(titles(&["one", "two"]));
+        let plain = tabs.tab_width(0);
+        tabs.set_dirty(0, true);
+        tabs.set_badge(0, Some("12".into()));
+        assert_eq!(tabs.tab_width(0), plain + "• ".width() + " (12)".width());
+    }
+
+    #[test]
+    fn badges_push_tabs_into_overflow() {
+        // " one " + " | " + " two " = 13 columns
+        let mut tabs = Tabs::new(titles(&["one", "two"]));
+        assert_eq!(tabs.visible_range(13), 0..2);
+
+        tabs.set_badge(1, Some("9".into()));
+        assert_eq!(tabs.visible_range(13), 0..1);
+
+        // Selecting the badged tab scrolls the first one out of view
+        tabs.select(1);
+        assert_eq!(tabs.visible_range(13), 1..2);
+        let row = render(&tabs, 13);
+        assert!(row.contains("two (9)"), "row was {:?}", row);
+        assert!(!row.contains("one"), "row was {:?}", row);
+    }
+}
@@ -1,23 +1,61 @@
 // ABOUTME: Unified text editor widget supporting both single-line and multi-line modes.
 // ABOUTME: Features: line numbers, selection, undo/redo, submit bindings, autocomplete, validation, echo modes.
 
-//! Multi-line text editor component with line numbers, text selection,
-//! undo/redo, word case operations, clipboard integration, and soft wrapping.
+//! Multi-line text editor component with line numbers, text and block
+//! selection, undo/redo, word case operations, clipboard integration, and
+//! soft wrapping.
 
 use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::Rect;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::widgets::{Block, Paragraph};
 use ratatui::Frame;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::clock::{Clock, SystemClock};
+use crate::key::KeyCombination;
 
 /// Validation callback type for input validation.
 type ValidateFn = Box<dyn Fn(&str) -> Result<(), String> + Send>;
 
+/// Transform applied to pasted text before it is inserted.
+type PasteFilter = Box<dyn Fn(String) -> String + Send>;
+
+/// Join the lines of `text` with single spaces, dropping a trailing line
+/// break. The default paste filter for single-line inputs.
+///
+/// ```
+/// use boba_widgets::text_area::flatten_newlines;
+/// assert_eq!(flatten_newlines("a\r\nb\nc\n".into()), "a b c");
+/// ```
+pub fn flatten_newlines(text: String) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('\r', " ")
+}
+
+/// Keep only the first line of `text`. An alternative paste filter for
+/// single-line inputs.
+///
+/// ```
+/// use boba_widgets::text_area::first_line;
+/// assert_eq!(first_line("a\nb".into()), "a");
+/// ```
+pub fn first_line(text: String) -> String {
+    text.lines().next().unwrap_or_default().to_string()
+}
+
 /// Controls which key combination triggers a submit.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SubmitBinding {
@@ -59,12 +97,75 @@ pub enum Message {
     Copy(String),
     /// Emitted with selected text on Ctrl+X.
     Cut(String),
+    /// Insert text as a rectangular block: each line goes into the next
+    /// row at the cursor column. Replaces an active block selection.
+    PasteBlock(String),
     /// Emitted when the user triggers the submit binding.
     Submit(String),
+    /// Emitted on Enter when [`with_next_field`](TextArea::with_next_field)
+    /// is enabled and Enter doesn't submit.
+    NextField,
+    /// Internal timer for [`with_change_throttle`](TextArea::with_change_throttle).
+    /// Forward it back to the editor like any other message.
+    ChangeSettled(u64),
+    /// Emitted before [`Changed`](Message::Changed) when
+    /// [`with_edit_events`](TextArea::with_edit_events) is enabled.
+    Edited {
+        /// Rows of the new content that differ from before the edit.
+        changed_rows: Range<usize>,
+        /// Line count before the edit.
+        old_line_count: usize,
+        /// Line count after the edit.
+        new_line_count: usize,
+    },
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress,
+    paste: Paste,
+});
+
 type UndoEntry = (Vec<Vec<char>>, (usize, usize));
 
+/// How consecutive typed characters are grouped into undo steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UndoGrouping {
+    /// Every keystroke is its own undo step.
+    #[default]
+    PerKeystroke,
+    /// A run of typing is undone a word at a time: a new step starts when
+    /// a non-whitespace character follows whitespace.
+    PerWord,
+    /// Characters typed within the given idle window of each other are
+    /// undone together.
+    Timed(Duration),
+}
+
+/// A completion being previewed by cycling with Tab.
+struct Completion {
+    /// The input as it was before the first Tab.
+    original: String,
+    matches: Vec<String>,
+    index: usize,
+}
+
+/// Rows an edit in progress may touch, recorded before it starts.
+struct EditSpan {
+    first: usize,
+    /// Last touched row, in the content before the edit.
+    last: usize,
+    old_line_count: usize,
+}
+
+/// The typing run the next typed character may extend.
+#[derive(Clone, Copy)]
+struct TypingRun {
+    /// Cursor position right after the last typed character.
+    cursor: (usize, usize),
+    last: char,
+    at: Instant,
+}
+
 /// A multi-line text editor with line numbers.
 ///
 /// # Example
@@ -91,29 +192,71 @@ pub struct TextArea {
     focus: bool,
     show_line_numbers: bool,
     char_limit: Option<usize>,
+    max_lines: Option<usize>,
+    max_line_length: Option<usize>,
     style: TextAreaStyle,
     selection_start: Option<(usize, usize)>,
+    /// Whether the active selection is a rectangle (Alt+Shift+arrows)
+    /// rather than a run of text.
+    block_selection: bool,
     undo_stack: VecDeque<UndoEntry>,
     redo_stack: VecDeque<UndoEntry>,
+    undo_grouping: UndoGrouping,
+    /// Set while typed characters can join the latest undo step.
+    typing: Option<TypingRun>,
+    clock: Arc<dyn Clock>,
     soft_wrap: bool,
+    /// Fixed soft-wrap column, narrower than the area when set.
+    wrap_width: Option<u16>,
+    wrap_alignment: Alignment,
     line_prompt: Option<String>,
     history: Option<boba_core::input_history::InputHistory>,
     block: Option<Block<'static>>,
     single_line: bool,
     submit_binding: SubmitBinding,
+    /// Exact submit keys; replace `submit_binding` when non-empty.
+    submit_keys: Vec<KeyCombination>,
+    next_field: bool,
     /// Horizontal scroll offset for single-line mode.
     h_offset: usize,
+    /// Mark hidden text at either edge in single-line mode.
+    scroll_indicators: bool,
+    /// Text columns at which a vertical guide is drawn.
+    rulers: Vec<u16>,
+    show_whitespace: bool,
     placeholder: String,
+    placeholder_while_focused: bool,
+    terminal_cursor: bool,
     echo_mode: EchoMode,
     suggestions: Vec<String>,
     filtered_suggestions: Vec<String>,
     show_suggestions: bool,
     suggestion_index: usize,
+    completion_cycling: bool,
+    completion: Option<Completion>,
     validate: Option<ValidateFn>,
+    paste_filter: Option<PasteFilter>,
     err: Option<String>,
     /// When set, caps the visible height used for rendering and scroll
     /// calculations. `visual_height()` also respects this limit.
     max_visible_lines: Option<u16>,
+    bracket_matching: bool,
+    auto_indent: bool,
+    smart_home: bool,
+    disabled: bool,
+    /// Input mask for single-line mode; `#` marks a digit slot.
+    mask: Option<Vec<char>>,
+    numeric: bool,
+    /// Read-only lines at the start of the content; the last one may be
+    /// partial (e.g. a REPL prompt).
+    locked_prefix: Option<Vec<Vec<char>>>,
+    /// Quiet period before a `Changed` is emitted, if throttled.
+    change_throttle: Option<Duration>,
+    /// Bumped on every throttled change; only the newest timer emits.
+    change_generation: u64,
+    edit_events: bool,
+    /// Rows of the edit being made, for [`Message::Edited`].
+    edit_span: Option<EditSpan>,
 }
 
 /// Style configuration for the text area.
@@ -133,6 +276,21 @@ pub struct TextAreaStyle {
     pub placeholder: Style,
     /// Style applied to ghost text from autocomplete suggestions.
     pub suggestion: Style,
+    /// Style patched over the text and border while validation fails.
+    pub error: Style,
+    /// Style patched over a bracket under the cursor and its match.
+    pub bracket_match: Style,
+    /// Style patched over the text while the editor is disabled.
+    pub disabled: Style,
+    /// Style patched over the `‹`/`›` markers shown when a single-line
+    /// input scrolls horizontally.
+    pub scroll_indicator: Style,
+    /// Style of the vertical guides set with
+    /// [`with_rulers`](TextArea::with_rulers).
+    pub ruler: Style,
+    /// Style patched over the markers shown by
+    /// [`with_show_whitespace`](TextArea::with_show_whitespace).
+    pub whitespace: Style,
 }
 
 impl Default for TextAreaStyle {
@@ -145,6 +303,18 @@ impl Default for TextAreaStyle {
             prompt: Style::default().fg(Color::Cyan),
             placeholder: Style::default().fg(Color::DarkGray),
             suggestion: Style::default().fg(Color::DarkGray),
+            error: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::UNDERLINED),
+            bracket_match: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            disabled: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
+            scroll_indicator: Style::default().fg(Color::DarkGray),
+            ruler: Style::default().fg(Color::DarkGray),
+            whitespace: Style::default().fg(Color::DarkGray),
         }
     }
 }
@@ -160,26 +330,55 @@ impl TextArea {
             focus: false,
             show_line_numbers: true,
             char_limit: None,
+            max_lines: None,
+            max_line_length: None,
             style: TextAreaStyle::default(),
             selection_start: None,
+            block_selection: false,
             undo_stack: VecDeque::new(),
             redo_stack: VecDeque::new(),
+            undo_grouping: UndoGrouping::PerKeystroke,
+            typing: None,
+            clock: Arc::new(SystemClock),
             soft_wrap: false,
+            wrap_width: None,
+            wrap_alignment: Alignment::Left,
             line_prompt: None,
             history: None,
             block: None,
             single_line: false,
             submit_binding: SubmitBinding::None,
+            submit_keys: Vec::new(),
+            next_field: false,
             h_offset: 0,
+            scroll_indicators: true,
+            rulers: Vec::new(),
+            show_whitespace: false,
             placeholder: String::new(),
+            placeholder_while_focused: false,
+            terminal_cursor: true,
             echo_mode: EchoMode::Normal,
             suggestions: Vec::new(),
             filtered_suggestions: Vec::new(),
             show_suggestions: true,
             suggestion_index: 0,
+            completion_cycling: false,
+            completion: None,
             validate: None,
+            paste_filter: None,
             err: None,
             max_visible_lines: None,
+            bracket_matching: false,
+            auto_indent: false,
+            smart_home: false,
+            disabled: false,
+            mask: None,
+            numeric: false,
+            locked_prefix: None,
+            change_throttle: None,
+            change_generation: 0,
+            edit_events: false,
+            edit_span: None,
         }
     }
 
@@ -202,6 +401,86 @@ impl TextArea {
         self
     }
 
+    /// Group consecutive typed characters into undo steps (default:
+    /// [`UndoGrouping::PerKeystroke`]). Any other edit, an undo, or typing
+    /// somewhere else ends the current group.
+    pub fn with_undo_grouping(mut self, grouping: UndoGrouping) -> Self {
+        self.undo_grouping = grouping;
+        self
+    }
+
+    /// Measure [`UndoGrouping::Timed`] windows with the given clock instead
+    /// of the system clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Highlight the bracket under the cursor and its matching partner
+    /// (default: false). Applies to `()`, `[]` and `{}`, searching across
+    /// lines.
+    pub fn with_bracket_matching(mut self, enabled: bool) -> Self {
+        self.bracket_matching = enabled;
+        self
+    }
+
+    /// Start new lines with the current line's leading whitespace when
+    /// Enter is pressed (default: false). The split and the inherited
+    /// indent are undone together.
+    pub fn with_auto_indent(mut self, enabled: bool) -> Self {
+        self.auto_indent = enabled;
+        self
+    }
+
+    /// Emit [`Message::Edited`] with the changed row range before every
+    /// [`Message::Changed`] (default: false).
+    ///
+    /// Lets the parent re-validate or re-highlight only the rows an edit
+    /// touched instead of the whole document. Edits are reported one by
+    /// one even when [`with_change_throttle`](Self::with_change_throttle)
+    /// delays `Changed`; undo and redo report every row.
+    pub fn with_edit_events(mut self, enabled: bool) -> Self {
+        self.edit_events = enabled;
+        self
+    }
+
+    /// Coalesce [`Message::Changed`] so it is emitted only once the content
+    /// has been left alone for `window`.
+    ///
+    /// Useful when the parent does expensive work per change, such as
+    /// filtering a large list. [`value`](Self::value) is always current;
+    /// only the notification is delayed.
+    pub fn with_change_throttle(mut self, window: Duration) -> Self {
+        self.change_throttle = Some(window);
+        self
+    }
+
+    /// Make Home jump to the first non-whitespace character of the line,
+    /// and to column 0 when the cursor is already there (default: false).
+    /// Pressing Home repeatedly toggles between the two.
+    pub fn with_smart_home(mut self, enabled: bool) -> Self {
+        self.smart_home = enabled;
+        self
+    }
+
+    /// Put `prefix` in front of the content and make it read-only.
+    ///
+    /// The cursor and selection can't move into the prefix, and edits that
+    /// would change it (such as Backspace at its end) are ignored.
+    /// [`value`](Self::value) still includes the prefix; use
+    /// [`editable_value`](Self::editable_value) for the text after it.
+    pub fn with_locked_prefix(mut self, prefix: &str) -> Self {
+        let rest = self.value();
+        self.set_value(&format!("{prefix}{rest}"));
+        self.locked_prefix = Some(prefix.split('\n').map(|l| l.chars().collect()).collect());
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.typing = None;
+        self.cursor_row = self.lines.len() - 1;
+        self.cursor_col = self.current_line_len();
+        self
+    }
+
     /// Set custom styles.
     pub fn with_style(mut self, style: TextAreaStyle) -> Self {
         self.style = style;
@@ -214,12 +493,108 @@ impl TextArea {
         self
     }
 
+    /// Set the maximum number of lines. Enter does nothing once the text
+    /// has this many lines, and pasted text stops at the last line.
+    pub fn with_max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines.max(1));
+        self
+    }
+
+    /// Set the maximum number of characters per line. Typing into a full
+    /// line is ignored, as is deleting a line break when the joined line
+    /// would be too long; other lines stay editable.
+    pub fn with_max_line_length(mut self, length: usize) -> Self {
+        self.max_line_length = Some(length);
+        self
+    }
+
+    /// Only accept digits (default: false). Other typed characters are
+    /// ignored and pasted text keeps just its digits.
+    pub fn with_numeric(mut self, numeric: bool) -> Self {
+        self.numeric = numeric;
+        self
+    }
+
+    /// Format single-line input with a mask such as `"(###) ###-####"`.
+    ///
+    /// Each `#` accepts one digit; every other character is a literal that
+    /// is inserted automatically as the slots before it fill up. Implies
+    /// [`with_numeric`](Self::with_numeric) and caps the input at the number
+    /// of `#` slots. [`value`](Self::value) returns the bare digits, while
+    /// the view and [`display_value`](Self::display_value) show the
+    /// formatted text.
+    pub fn with_mask(mut self, mask: &str) -> Self {
+        let mask: Vec<char> = mask.chars().collect();
+        self.char_limit = Some(mask.iter().filter(|&&c| c == '#').count());
+        self.numeric = true;
+        self.mask = Some(mask);
+        self
+    }
+
     /// Enable soft wrapping at the visible width.
+    ///
+    /// Lines are broken by character, and the view scrolls by visual row so
+    /// the cursor stays on screen even within a long wrapped line.
     pub fn with_soft_wrap(mut self, wrap: bool) -> Self {
         self.soft_wrap = wrap;
         self
     }
 
+    /// Soft-wrap at a fixed column instead of the visible width (default:
+    /// `None`). `Some` also enables [soft wrapping](Self::with_soft_wrap).
+    ///
+    /// The column counts text only, not line numbers or prompts. An area
+    /// narrower than the column still wraps at its own width.
+    pub fn with_wrap_width(mut self, width: Option<u16>) -> Self {
+        self.wrap_width = width.map(|w| w.max(1));
+        if width.is_some() {
+            self.soft_wrap = true;
+        }
+        self
+    }
+
+    /// Where a block narrower than the area, because of
+    /// [`with_wrap_width`](Self::with_wrap_width), sits horizontally
+    /// (default: [`Alignment::Left`]).
+    pub fn with_wrap_alignment(mut self, alignment: Alignment) -> Self {
+        self.wrap_alignment = alignment;
+        self
+    }
+
+    /// Show `‹`/`›` at the edges of a single-line input when text is
+    /// scrolled out of view to the left or right (default: true). A marker
+    /// never covers the cursor.
+    pub fn with_scroll_indicators(mut self, show: bool) -> Self {
+        self.scroll_indicators = show;
+        self
+    }
+
+    /// Draw a vertical guide after `column` display columns of text, e.g.
+    /// `Some(80)` to mark the usual line-length limit. `None` removes it.
+    ///
+    /// The guide is measured from the start of the text, past any line
+    /// numbers and prompt, and only shows where no text covers it. It is
+    /// not drawn in single-line mode.
+    pub fn with_ruler(self, column: Option<u16>) -> Self {
+        self.with_rulers(column)
+    }
+
+    /// Draw a vertical guide at each of the given text columns. See
+    /// [`with_ruler`](Self::with_ruler).
+    pub fn with_rulers(mut self, columns: impl IntoIterator<Item = u16>) -> Self {
+        self.rulers = columns.into_iter().collect();
+        self
+    }
+
+    /// Show trailing spaces as `·` and tabs as `→` (default: false).
+    ///
+    /// Only the display changes; [`value`](Self::value) keeps the real
+    /// characters. Not shown in single-line mode.
+    pub fn with_show_whitespace(mut self, show: bool) -> Self {
+        self.show_whitespace = show;
+        self
+    }
+
     /// Set a per-line prompt string rendered before each line.
     pub fn with_line_prompt(mut self, prompt: impl Into<String>) -> Self {
         self.line_prompt = Some(prompt.into());
@@ -240,6 +615,22 @@ impl TextArea {
         self
     }
 
+    /// Keep showing the placeholder as dim ghost text after the cursor while
+    /// the editor is focused and empty (default: false). Typing replaces it.
+    /// Applies to single-line mode.
+    pub fn with_placeholder_while_focused(mut self, show: bool) -> Self {
+        self.placeholder_while_focused = show;
+        self
+    }
+
+    /// Place the terminal's own cursor on the caret while focused (default:
+    /// true), so IMEs compose text and screen readers track input there.
+    /// The styled cursor cell is drawn either way.
+    pub fn with_terminal_cursor(mut self, enabled: bool) -> Self {
+        self.terminal_cursor = enabled;
+        self
+    }
+
     /// Enable input history with the given maximum number of entries.
     ///
     /// When enabled and the buffer is a single line, Up/Down keys browse
@@ -280,6 +671,32 @@ impl TextArea {
         self
     }
 
+    /// Submit on exactly these key combinations, replacing the
+    /// [submit binding](Self::with_submit). An empty list restores it.
+    ///
+    /// Modifiers must match exactly, so Ctrl+Enter as a submit key leaves
+    /// plain Enter free for newlines or
+    /// [`with_next_field`](Self::with_next_field).
+    pub fn with_submit_keys(mut self, keys: Vec<KeyCombination>) -> Self {
+        self.submit_keys = keys;
+        self
+    }
+
+    /// Emit [`Message::NextField`] on plain Enter when it doesn't submit
+    /// (default: false), e.g. to move through a form. Enter then no longer
+    /// inserts newlines.
+    pub fn with_next_field(mut self, enabled: bool) -> Self {
+        self.next_field = enabled;
+        self
+    }
+
+    /// Whether `key` is one of the [submit keys](Self::with_submit_keys).
+    fn is_submit_key(&self, key: &KeyEvent) -> bool {
+        self.submit_keys
+            .iter()
+            .any(|k| k.code == key.code && k.modifiers == key.modifiers)
+    }
+
     /// Set the echo mode for displaying input text.
     ///
     /// Only meaningful in single-line mode. In `Password` mode, each character
@@ -313,12 +730,23 @@ impl TextArea {
         self
     }
 
+    /// Make Tab cycle through every matching suggestion instead of
+    /// accepting the first one (default: false). Single-line mode only.
+    ///
+    /// Each Tab (or Shift+Tab, backwards) puts the next match in the input
+    /// as a preview. Enter or Right keeps it, Esc restores what was typed
+    /// before the first Tab, and any other key keeps it and carries on.
+    pub fn with_completion_cycling(mut self, enabled: bool) -> Self {
+        self.completion_cycling = enabled;
+        self
+    }
+
     /// Return the currently highlighted suggestion, if any.
     ///
-    /// Returns `None` when not in single-line mode or when suggestions
-    /// are hidden.
+    /// Returns `None` when not in single-line mode, when suggestions are
+    /// hidden, or while a cycled completion is being previewed.
     pub fn current_suggestion(&self) -> Option<&str> {
-        if !self.single_line || !self.show_suggestions {
+        if !self.single_line || !self.show_suggestions || self.completion.is_some() {
             return None;
         }
         self.filtered_suggestions
@@ -348,6 +776,24 @@ impl TextArea {
         self
     }
 
+    /// Transform pasted text before it is inserted, ahead of the numeric
+    /// filter and the character limit.
+    ///
+    /// Single-line inputs default to [`flatten_newlines`]; pass
+    /// [`first_line`] to cut a paste at its first line break instead. Any
+    /// line breaks a custom filter leaves in are still removed in
+    /// single-line mode.
+    ///
+    /// ```ignore
+    /// let input = TextArea::new()
+    ///     .with_single_line(true)
+    ///     .with_paste_filter(|text| text.trim().to_string());
+    /// ```
+    pub fn with_paste_filter(mut self, f: impl Fn(String) -> String + Send + 'static) -> Self {
+        self.paste_filter = Some(Box::new(f));
+        self
+    }
+
     /// Cap the visible height of the widget to at most `n` lines.
     ///
     /// When set, scroll calculations and `visual_height()` will not
@@ -381,6 +827,77 @@ impl TextArea {
         self.suggestion_index = 0;
     }
 
+    /// Report a content change: immediately, or via a timer when throttled.
+    fn changed(&mut self) -> Command<Message> {
+        let changed = match self.change_throttle {
+            None => Command::message(Message::Changed(self.value())),
+            Some(window) => {
+                self.change_generation += 1;
+                let generation = self.change_generation;
+                Command::tick(window, move |_| Message::ChangeSettled(generation))
+            }
+        };
+        match self.take_edit() {
+            Some(edited) => Command::batch([Command::message(edited), changed]),
+            None => changed,
+        }
+    }
+
+    /// Remember the rows under the cursor and selection before an edit.
+    fn begin_edit(&mut self) {
+        let mut first = self.cursor_row;
+        let mut last = self.cursor_row;
+        if let Some((row, _)) = self.selection_start {
+            first = first.min(row);
+            last = last.max(row);
+        }
+        let old_line_count = self.lines.len();
+        let span = self.edit_span.get_or_insert(EditSpan {
+            first,
+            last,
+            old_line_count,
+        });
+        span.first = span.first.min(first);
+        span.last = span.last.max(last);
+    }
+
+    /// Extend the current edit to rows `first..=last` of the new content.
+    fn touch_rows(&mut self, first: usize, last: usize) {
+        if let Some(span) = self.edit_span.as_mut() {
+            let added = self.lines.len().saturating_sub(span.old_line_count);
+            span.first = span.first.min(first);
+            span.last = span.last.max(last.saturating_sub(added));
+        }
+    }
+
+    /// The [`Message::Edited`] for the edit that just finished, if edit
+    /// events are enabled. Without a recorded span every row is reported.
+    fn take_edit(&mut self) -> Option<Message> {
+        let span = self.edit_span.take();
+        if !self.edit_events {
+            return None;
+        }
+        let new_line_count = self.lines.len();
+        let (changed_rows, old_line_count) = match span {
+            Some(span) => {
+                // Rows after the span only moved; rows inside it follow the
+                // change in line count.
+                let end = (span.last + 1 + new_line_count)
+                    .saturating_sub(span.old_line_count)
+                    .max(self.cursor_row + 1)
+                    .min(new_line_count);
+                let start = span.first.min(self.cursor_row);
+                (start..end.max(start), span.old_line_count)
+            }
+            None => (0..new_line_count, new_line_count),
+        };
+        Some(Message::Edited {
+            changed_rows,
+            old_line_count,
+            new_line_count,
+        })
+    }
+
     /// Run the validation function against the current value, updating `err`.
     fn run_validate(&mut self) {
         if let Some(ref f) = self.validate {
@@ -388,6 +905,220 @@ impl TextArea {
         }
     }
 
+    /// Style for content text, switching to the error style while
+    /// validation fails.
+    pub(crate) fn text_style(&self) -> Style {
+        if self.disabled {
+            self.style.text.patch(self.style.disabled)
+        } else if self.err.is_some() {
+            self.style.text.patch(self.style.error)
+        } else {
+            self.style.text
+        }
+    }
+
+    /// Whether the editor should show a cursor and accept input.
+    fn is_active(&self) -> bool {
+        self.focus && !self.disabled
+    }
+
+    /// The number of text columns a soft-wrapped line fills when `width`
+    /// columns are available.
+    fn wrap_columns(&self, width: usize) -> usize {
+        self.wrap_width
+            .map_or(width, |w| (w as usize).min(width))
+            .max(1)
+    }
+
+    /// Map the logical cursor to a `(row, col)` offset within its line's
+    /// wrapped visual rows, for a text width of `width` characters.
+    fn visual_cursor(&self, width: usize) -> (usize, usize) {
+        if !self.soft_wrap || width == 0 {
+            return (0, self.cursor_col);
+        }
+        (self.cursor_col / width, self.cursor_col % width)
+    }
+
+    /// Build the styled content spans for one logical line, including the
+    /// cursor and selection highlighting but not the gutter.
+    fn line_content_spans(
+        &self,
+        actual_row: usize,
+        text_style: Style,
+        has_sel: bool,
+    ) -> Vec<Span<'static>> {
+        let line_chars = &self.lines[actual_row];
+        let mut spans = Vec::new();
+        if has_sel {
+            // Render with selection highlighting
+            let line_len = line_chars.len();
+            let is_cursor_line = self.is_active() && actual_row == self.cursor_row;
+
+            // Build spans character by character, grouping consecutive
+            // characters with the same style.
+            let mut i = 0;
+            while i <= line_len {
+                if i == line_len {
+                    // At end of line: render cursor if this is the
+                    // cursor line and cursor is past all chars.
+                    if is_cursor_line && self.cursor_col == line_len {
+                        if self.is_selected(actual_row, i) {
+                            // Cursor on selected trailing position
+                            spans.push(Span::styled(" ", self.style.cursor));
+                        } else {
+                            spans.push(Span::styled(" ", self.style.cursor));
+                        }
+                    }
+                    break;
+                }
+
+                let sel = self.is_selected(actual_row, i);
+                let is_cursor = is_cursor_line && i == self.cursor_col;
+
+                if is_cursor {
+                    // Render cursor character
+                    spans.push(Span::styled(line_chars[i].to_string(), self.style.cursor));
+                    i += 1;
+                } else {
+                    // Collect a run of characters that share the same
+                    // selected state and are not the cursor.
+                    let style = if sel {
+                        self.style.selection
+                    } else {
+                        text_style
+                    };
+                    let start = i;
+                    while i < line_len
+                        && self.is_selected(actual_row, i) == sel
+                        && !(is_cursor_line && i == self.cursor_col)
+                    {
+                        i += 1;
+                    }
+                    let chunk: String = line_chars[start..i].iter().collect();
+                    if !chunk.is_empty() {
+                        spans.push(Span::styled(chunk, style));
+                    }
+                }
+            }
+        } else if self.is_active() && actual_row == self.cursor_row {
+            let line_str: String = line_chars.iter().collect();
+            let col = self.cursor_col;
+            let before: String = line_str.chars().take(col).collect();
+            let cursor_char = line_str.chars().nth(col);
+            let after: String = line_str.chars().skip(col + 1).collect();
+
+            if !before.is_empty() {
+                spans.push(Span::styled(before, text_style));
+            }
+            if let Some(c) = cursor_char {
+                spans.push(Span::styled(c.to_string(), self.style.cursor));
+            } else {
+                spans.push(Span::styled(" ", self.style.cursor));
+            }
+            if !after.is_empty() {
+                spans.push(Span::styled(after, text_style));
+            }
+        } else {
+            let line_str: String = line_chars.iter().collect();
+            spans.push(Span::styled(line_str, text_style));
+        }
+        spans
+    }
+
+    /// Return the position `(row, col)` of the bracket matching the one
+    /// under the cursor.
+    ///
+    /// Returns `None` when the cursor is not on a bracket or the bracket has
+    /// no partner. Nesting of the same bracket kind is respected; other
+    /// kinds are ignored while scanning.
+    pub fn matching_bracket(&self) -> Option<(usize, usize)> {
+        let ch = *self.lines[self.cursor_row].get(self.cursor_col)?;
+        let (open, close, forward) = match ch {
+            '(' => ('(', ')', true),
+            '[' => ('[', ']', true),
+            '{' => ('{', '}', true),
+            ')' => ('(', ')', false),
+            ']' => ('[', ']', false),
+            '}' => ('{', '}', false),
+            _ => return None,
+        };
+        let mut depth = 0usize;
+        let mut row = self.cursor_row;
+        let mut col = self.cursor_col;
+        loop {
+            let c = self.lines[row].get(col).copied();
+            if c == Some(open) {
+                if forward {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                }
+            } else if c == Some(close) {
+                if forward {
+                    depth -= 1;
+                } else {
+                    depth += 1;
+                }
+            }
+            if depth == 0 {
+                return Some((row, col));
+            }
+            // Step to the next (or previous) character, crossing lines.
+            if forward {
+                if col + 1 < self.lines[row].len() {
+                    col += 1;
+                } else {
+                    row += 1;
+                    while row < self.lines.len() && self.lines[row].is_empty() {
+                        row += 1;
+                    }
+                    if row == self.lines.len() {
+                        return None;
+                    }
+                    col = 0;
+                }
+            } else if col > 0 {
+                col -= 1;
+            } else {
+                loop {
+                    if row == 0 {
+                        return None;
+                    }
+                    row -= 1;
+                    if !self.lines[row].is_empty() {
+                        break;
+                    }
+                }
+                col = self.lines[row].len() - 1;
+            }
+        }
+    }
+
+    /// Cells to highlight as a matched bracket pair, if enabled.
+    fn bracket_highlights(&self) -> Vec<(usize, usize)> {
+        if !self.bracket_matching || !self.is_active() {
+            return Vec::new();
+        }
+        match self.matching_bracket() {
+            Some(other) => vec![(self.cursor_row, self.cursor_col), other],
+            None => Vec::new(),
+        }
+    }
+
+    /// The configured block, decorated with the error style and the error
+    /// message as a bottom title while validation fails.
+    fn decorated_block(&self) -> Option<Block<'static>> {
+        let block = self.block.clone()?;
+        match self.err {
+            Some(ref err) => Some(
+                block
+                    .border_style(self.style.error)
+                    .title_bottom(Line::styled(err.clone(), self.style.error)),
+            ),
+            None => Some(block),
+        }
+    }
+
     /// Accept the current suggestion, replacing the input content.
     ///
     /// Returns true if a suggestion was accepted.
@@ -403,6 +1134,78 @@ impl TextArea {
         }
     }
 
+    /// Handle a key for completion cycling. Returns `None` if the key
+    /// should be processed normally.
+    fn completion_key(&mut self, key: &KeyEvent) -> Option<Command<Message>> {
+        if !self.completion_cycling || !self.single_line {
+            return None;
+        }
+        match key.code {
+            KeyCode::Tab => Some(self.cycle_completion(true)),
+            KeyCode::BackTab => Some(self.cycle_completion(false)),
+            KeyCode::Enter | KeyCode::Right if self.completion.is_some() => {
+                self.completion = None;
+                self.filter_suggestions();
+                Some(Command::none())
+            }
+            KeyCode::Esc => {
+                let completion = self.completion.take()?;
+                self.set_line(&completion.original);
+                self.filter_suggestions();
+                self.run_validate();
+                Some(self.changed())
+            }
+            _ => {
+                if self.completion.take().is_some() {
+                    self.filter_suggestions();
+                }
+                None
+            }
+        }
+    }
+
+    /// Preview the next (or previous) matching suggestion in the input,
+    /// starting a completion if none is in progress.
+    fn cycle_completion(&mut self, forward: bool) -> Command<Message> {
+        let completion = match self.completion.as_mut() {
+            Some(completion) => {
+                let len = completion.matches.len();
+                completion.index = if forward {
+                    (completion.index + 1) % len
+                } else {
+                    (completion.index + len - 1) % len
+                };
+                completion
+            }
+            None => {
+                let Some(matches) = self
+                    .current_suggestion()
+                    .map(|_| self.filtered_suggestions.clone())
+                else {
+                    return Command::none();
+                };
+                self.push_undo();
+                let index = if forward { 0 } else { matches.len() - 1 };
+                self.completion.insert(Completion {
+                    original: self.value(),
+                    matches,
+                    index,
+                })
+            }
+        };
+        let text = completion.matches[completion.index].clone();
+        self.set_line(&text);
+        self.run_validate();
+        self.changed()
+    }
+
+    /// Replace the single line's content and put the cursor at its end.
+    fn set_line(&mut self, text: &str) {
+        self.lines = vec![text.chars().collect()];
+        self.cursor_row = 0;
+        self.cursor_col = self.lines[0].len();
+    }
+
     /// Push a value into the input history.
     ///
     /// Typically called after the user submits input. Empty strings
@@ -428,6 +1231,31 @@ impl TextArea {
         self.focus = false;
     }
 
+    /// Enable or disable the editor. A disabled editor ignores input and
+    /// renders dimmed with no cursor, whether or not it has focus.
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    /// Whether the editor is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// The content as displayed: hidden or masked per the echo mode,
+    /// formatted through the mask set with [`with_mask`](Self::with_mask),
+    /// or the same as [`value`](Self::value) otherwise.
+    pub fn display_value(&self) -> String {
+        match (&self.echo_mode, &self.mask) {
+            (EchoMode::Hidden, _) => String::new(),
+            (EchoMode::Password(c), _) => c.to_string().repeat(self.len()),
+            (EchoMode::Normal, Some(mask)) => {
+                apply_mask(mask, &self.lines[0], 0).0.into_iter().collect()
+            }
+            (EchoMode::Normal, None) => self.value(),
+        }
+    }
+
     /// Get the full content as a newline-separated string.
     pub fn value(&self) -> String {
         self.lines
@@ -437,8 +1265,34 @@ impl TextArea {
             .join("\n")
     }
 
+    /// The content after the [locked prefix](Self::with_locked_prefix), or
+    /// the whole content when there is none.
+    pub fn editable_value(&self) -> String {
+        let value = self.value();
+        match self.locked_end() {
+            Some((row, col)) => {
+                let skip: usize =
+                    self.lines[..row].iter().map(|l| l.len() + 1).sum::<usize>() + col;
+                value.chars().skip(skip).collect()
+            }
+            None => value,
+        }
+    }
+
     /// Programmatically set content, resetting the cursor to 0,0.
+    ///
+    /// With a [locked prefix](Self::with_locked_prefix) the cursor starts
+    /// after the prefix instead; if `content` no longer begins with the
+    /// prefix, the lock is dropped. A [numeric](Self::with_numeric) area
+    /// keeps only the digits, as when typing.
     pub fn set_value(&mut self, content: &str) {
+        let digits: String;
+        let content = if self.numeric {
+            digits = content.chars().filter(char::is_ascii_digit).collect();
+            &digits
+        } else {
+            content
+        };
         self.lines = content.lines().map(|l| l.chars().collect()).collect();
         if self.lines.is_empty() {
             self.lines.push(Vec::new());
@@ -451,6 +1305,10 @@ impl TextArea {
         self.cursor_col = 0;
         self.scroll_offset = 0;
         self.selection_start = None;
+        if !self.prefix_intact() {
+            self.locked_prefix = None;
+        }
+        self.clamp_to_editable();
     }
 
     /// Insert a string at the current cursor position, handling newlines.
@@ -463,11 +1321,14 @@ impl TextArea {
                 }
             }
             if c == '\n' {
+                if self.at_max_lines() {
+                    return;
+                }
                 let rest = self.lines[self.cursor_row].split_off(self.cursor_col);
                 self.cursor_row += 1;
                 self.cursor_col = 0;
                 self.lines.insert(self.cursor_row, rest);
-            } else {
+            } else if !self.line_full() {
                 self.lines[self.cursor_row].insert(self.cursor_col, c);
                 self.cursor_col += 1;
             }
@@ -481,6 +1342,9 @@ impl TextArea {
                 return;
             }
         }
+        if self.line_full() {
+            return;
+        }
         self.lines[self.cursor_row].insert(self.cursor_col, c);
         self.cursor_col += 1;
     }
@@ -558,9 +1422,13 @@ impl TextArea {
         self.cursor_col = self.lines[self.cursor_row].len();
     }
 
-    /// Reset content, cursor, selection, and undo/redo stacks.
+    /// Reset content, cursor, selection, and undo/redo stacks. A locked
+    /// prefix is kept, with the cursor placed after it.
     pub fn reset(&mut self) {
-        self.lines = vec![Vec::new()];
+        self.lines = self
+            .locked_prefix
+            .clone()
+            .unwrap_or_else(|| vec![Vec::new()]);
         self.cursor_row = 0;
         self.cursor_col = 0;
         self.scroll_offset = 0;
@@ -568,6 +1436,8 @@ impl TextArea {
         self.selection_start = None;
         self.undo_stack.clear();
         self.redo_stack.clear();
+        self.typing = None;
+        self.clamp_to_editable();
     }
 
     /// Return the current horizontal scroll offset (single-line mode).
@@ -583,7 +1453,7 @@ impl TextArea {
         let raw = if self.single_line {
             1
         } else if self.soft_wrap && width > 0 {
-            let w = width as usize;
+            let w = self.wrap_columns(width as usize);
             self.lines
                 .iter()
                 .map(|line| {
@@ -643,7 +1513,22 @@ impl TextArea {
     }
 
     /// Return the selected text, or None if no selection.
+    ///
+    /// A block selection yields the selected column range of each row,
+    /// joined with newlines.
     pub fn selected_text(&self) -> Option<String> {
+        if let Some((rows, (c0, c1))) = self.block_range() {
+            let text = rows
+                .map(|row| {
+                    let line = &self.lines[row];
+                    line[c0.min(line.len())..c1.min(line.len())]
+                        .iter()
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Some(text);
+        }
         let ((sr, sc), (er, ec)) = self.selection_range()?;
         if sr == er {
             // Single-line selection
@@ -671,6 +1556,7 @@ impl TextArea {
     /// Select all text in the text area.
     pub fn select_all(&mut self) {
         self.selection_start = Some((0, 0));
+        self.block_selection = false;
         let last_row = self.lines.len() - 1;
         let last_col = self.lines[last_row].len();
         self.cursor_row = last_row;
@@ -678,7 +1564,21 @@ impl TextArea {
     }
 
     /// Delete selected text. Returns true if there was a selection to delete.
+    ///
+    /// A block selection removes its column range from every selected row.
     pub fn delete_selection(&mut self) -> bool {
+        if let Some((rows, (c0, c1))) = self.block_range() {
+            let top = rows.start;
+            for row in rows {
+                let line = &mut self.lines[row];
+                let end = c1.min(line.len());
+                line.drain(c0.min(end)..end);
+            }
+            self.cursor_row = top;
+            self.cursor_col = c0.min(self.lines[top].len());
+            self.selection_start = None;
+            return true;
+        }
         let range = match self.selection_range() {
             Some(r) => r,
             None => {
@@ -706,16 +1606,149 @@ impl TextArea {
         true
     }
 
-    /// Begin or continue a selection. If no selection is active, record the
-    /// current cursor position as the selection start.
-    fn ensure_selection_started(&mut self) {
-        if self.selection_start.is_none() {
-            self.selection_start = Some((self.cursor_row, self.cursor_col));
-        }
+    /// Return whether the active selection is a rectangular block.
+    pub fn is_block_selection(&self) -> bool {
+        self.block_selection && self.has_selection()
     }
 
-    /// Clear the selection without deleting text.
-    fn clear_selection(&mut self) {
+    /// Select the rectangle spanning `anchor` and `cursor`, both given as
+    /// `(row, col)`. The cursor is placed at `cursor`, clamped to the text.
+    pub fn select_block(&mut self, anchor: (usize, usize), cursor: (usize, usize)) {
+        let clamp = |(row, col): (usize, usize)| {
+            let row = row.min(self.lines.len() - 1);
+            (row, col.min(self.lines[row].len()))
+        };
+        self.selection_start = Some(clamp(anchor));
+        (self.cursor_row, self.cursor_col) = clamp(cursor);
+        self.block_selection = true;
+    }
+
+    /// Insert `text` as a rectangular block at the cursor: line `i` of the
+    /// text goes into row `cursor_row + i` at the cursor column. Short rows
+    /// are padded with spaces and rows are appended past the end as needed.
+    /// An active selection is deleted first.
+    pub fn insert_block(&mut self, text: &str) {
+        self.delete_selection();
+        let (top, col) = (self.cursor_row, self.cursor_col);
+        for (i, chunk) in text.split('\n').enumerate() {
+            let mut chunk: Vec<char> = chunk.chars().filter(|c| *c != '\r').collect();
+            let row = top + i;
+            if row == self.lines.len() {
+                self.lines.push(Vec::new());
+            }
+            let pad = col.saturating_sub(self.lines[row].len());
+            if let Some(limit) = self.char_limit {
+                let available = limit.saturating_sub(self.total_chars());
+                if pad > available {
+                    break;
+                }
+                chunk.truncate(available - pad);
+            }
+            let line = &mut self.lines[row];
+            line.resize(line.len() + pad, ' ');
+            line.splice(col..col, chunk);
+        }
+        self.touch_rows(top, top + text.split('\n').count() - 1);
+        self.cursor_row = top;
+        self.cursor_col = col;
+    }
+
+    /// The rows and `[start, end)` columns of the active block selection.
+    fn block_range(&self) -> Option<(std::ops::Range<usize>, (usize, usize))> {
+        if !self.block_selection {
+            return None;
+        }
+        let (sr, sc) = self.selection_start?;
+        if !self.has_selection() {
+            return None;
+        }
+        let (cr, cc) = (self.cursor_row, self.cursor_col);
+        Some((sr.min(cr)..sr.max(cr) + 1, (sc.min(cc), sc.max(cc))))
+    }
+
+    /// End of the locked prefix as `(row, col)`, if there is one.
+    fn locked_end(&self) -> Option<(usize, usize)> {
+        let prefix = self.locked_prefix.as_ref()?;
+        Some((prefix.len() - 1, prefix[prefix.len() - 1].len()))
+    }
+
+    /// Where Home moves the cursor: column 0, or with smart home the
+    /// line's indent unless the cursor is already there.
+    fn home_col(&self) -> usize {
+        if !self.smart_home {
+            return 0;
+        }
+        let indent = self.lines[self.cursor_row]
+            .iter()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        if self.cursor_col == indent {
+            0
+        } else {
+            indent
+        }
+    }
+
+    /// Whether the content still begins with the locked prefix.
+    fn prefix_intact(&self) -> bool {
+        let Some(prefix) = &self.locked_prefix else {
+            return true;
+        };
+        let last = prefix.len() - 1;
+        self.lines.len() > last
+            && self.lines[..last] == prefix[..last]
+            && self.lines[last].starts_with(&prefix[last])
+    }
+
+    /// Whether `key` can only move the cursor or selection, so it cannot
+    /// touch the locked prefix. Right may accept a suggestion in single-line
+    /// mode, and Up/Down may replace the text with a history entry.
+    fn only_navigates(&self, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left | KeyCode::Home | KeyCode::End | KeyCode::PageUp | KeyCode::PageDown => {
+                true
+            }
+            KeyCode::Right => !self.single_line,
+            KeyCode::Up | KeyCode::Down => {
+                self.history.is_none() || (!self.single_line && self.lines.len() > 1)
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the cursor and selection anchor out of the locked prefix.
+    fn clamp_to_editable(&mut self) {
+        let Some(end) = self.locked_end() else {
+            return;
+        };
+        if (self.cursor_row, self.cursor_col) < end {
+            (self.cursor_row, self.cursor_col) = end;
+        }
+        if let Some(start) = self.selection_start.as_mut() {
+            *start = (*start).max(end);
+        }
+    }
+
+    /// Begin or continue a selection. If no selection is active, record the
+    /// current cursor position as the selection start.
+    fn ensure_selection_started(&mut self) {
+        if self.selection_start.is_none() {
+            self.selection_start = Some((self.cursor_row, self.cursor_col));
+        }
+        self.block_selection = false;
+    }
+
+    /// Like [`ensure_selection_started`](Self::ensure_selection_started),
+    /// but switches the selection to block mode.
+    fn ensure_block_selection_started(&mut self) {
+        if self.selection_start.is_none() {
+            self.selection_start = Some((self.cursor_row, self.cursor_col));
+        }
+        self.block_selection = true;
+    }
+
+    /// Clear the selection without deleting text.
+    fn clear_selection(&mut self) {
         self.selection_start = None;
     }
 
@@ -723,6 +1756,24 @@ impl TextArea {
         self.lines[self.cursor_row].len()
     }
 
+    /// Whether `row` and the row after it fit within the maximum line
+    /// length once joined.
+    fn can_join_next(&self, row: usize) -> bool {
+        self.max_line_length
+            .is_none_or(|max| self.lines[row].len() + self.lines[row + 1].len() <= max)
+    }
+
+    /// Whether the cursor's line has reached the maximum line length.
+    fn line_full(&self) -> bool {
+        self.max_line_length
+            .is_some_and(|max| self.current_line_len() >= max)
+    }
+
+    /// Whether the text has reached the maximum line count.
+    fn at_max_lines(&self) -> bool {
+        self.max_lines.is_some_and(|max| self.lines.len() >= max)
+    }
+
     fn clamp_cursor_col(&mut self) {
         let len = self.current_line_len();
         if self.cursor_col > len {
@@ -814,7 +1865,10 @@ impl TextArea {
         if boundary < self.cursor_col {
             self.lines[self.cursor_row].drain(boundary..self.cursor_col);
             self.cursor_col = boundary;
-        } else if self.cursor_col == 0 && self.cursor_row > 0 {
+        } else if self.cursor_col == 0
+            && self.cursor_row > 0
+            && self.can_join_next(self.cursor_row - 1)
+        {
             // At the start of a line, join with the previous line
             let current = self.lines.remove(self.cursor_row);
             self.cursor_row -= 1;
@@ -830,7 +1884,7 @@ impl TextArea {
         let line = &self.lines[self.cursor_row];
         let len = line.len();
         if self.cursor_col >= len {
-            if self.cursor_row < self.lines.len() - 1 {
+            if self.cursor_row < self.lines.len() - 1 && self.can_join_next(self.cursor_row) {
                 // At end of line, join with next line
                 let next = self.lines.remove(self.cursor_row + 1);
                 self.lines[self.cursor_row].extend(next);
@@ -851,17 +1905,57 @@ impl TextArea {
         }
     }
 
+    /// The spaces and tabs at the start of `row`, stopping at `max_col`.
+    fn leading_whitespace(&self, row: usize, max_col: usize) -> Vec<char> {
+        self.lines[row]
+            .iter()
+            .take(max_col)
+            .take_while(|c| **c == ' ' || **c == '\t')
+            .copied()
+            .collect()
+    }
+
     /// Push the current state onto the undo stack, clearing the redo stack.
     /// Caps the undo stack at 100 entries.
     fn push_undo(&mut self) {
+        self.begin_edit();
         self.undo_stack
             .push_back((self.lines.clone(), (self.cursor_row, self.cursor_col)));
         self.redo_stack.clear();
+        self.typing = None;
         if self.undo_stack.len() > 100 {
             self.undo_stack.pop_front();
         }
     }
 
+    /// Record the undo state before typing `c`, or extend the current
+    /// typing run per the undo grouping so it is undone with it.
+    fn push_undo_typed(&mut self, c: char) {
+        self.begin_edit();
+        let now = self.clock.now();
+        let extends = !self.has_selection()
+            && self.typing.as_ref().is_some_and(|run| {
+                run.cursor == (self.cursor_row, self.cursor_col)
+                    && match self.undo_grouping {
+                        UndoGrouping::PerKeystroke => false,
+                        UndoGrouping::PerWord => !run.last.is_whitespace() || c.is_whitespace(),
+                        UndoGrouping::Timed(window) => {
+                            now.saturating_duration_since(run.at) <= window
+                        }
+                    }
+            });
+        if extends {
+            self.redo_stack.clear();
+        } else {
+            self.push_undo();
+        }
+        self.typing = Some(TypingRun {
+            cursor: (self.cursor_row, self.cursor_col + 1),
+            last: c,
+            at: now,
+        });
+    }
+
     /// Uppercase the word at cursor (from cursor_col to next word boundary).
     fn uppercase_word(&mut self) {
         let end = self.word_end_boundary();
@@ -919,6 +2013,9 @@ impl TextArea {
     /// Returns true if the position is >= start and < end of the normalized
     /// selection range.
     fn is_selected(&self, row: usize, col: usize) -> bool {
+        if let Some((rows, (c0, c1))) = self.block_range() {
+            return rows.contains(&row) && (c0..c1).contains(&col);
+        }
         if let Some(((sr, sc), (er, ec))) = self.selection_range() {
             if row < sr || row > er {
                 return false;
@@ -942,7 +2039,8 @@ impl TextArea {
     /// Render the text area in single-line mode with horizontal scrolling
     /// and overflow indicators.
     fn view_single_line(&self, frame: &mut Frame, inner: Rect) {
-        let prompt_width = self.line_prompt.as_ref().map(|p| p.len()).unwrap_or(0);
+        let text_style = self.text_style();
+        let prompt_width = self.line_prompt.as_deref().map_or(0, str::width);
         let total_width = inner.width as usize;
         let available = total_width.saturating_sub(prompt_width);
 
@@ -961,30 +2059,45 @@ impl TextArea {
             return;
         }
 
+        // For Password echo mode, replace each character with the mask
+        // character, even with an input mask; otherwise an input mask shows
+        // the formatted text and maps the cursor into it.
+        let masked;
+        let (line_chars, cursor_col): (&[char], usize) = match (&self.echo_mode, &self.mask) {
+            (EchoMode::Password(c), _) => {
+                masked = (vec![*c; self.lines[0].len()], self.cursor_col);
+                (&masked.0, masked.1)
+            }
+            (_, Some(mask)) => {
+                masked = apply_mask(mask, &self.lines[0], self.cursor_col);
+                (&masked.0, masked.1)
+            }
+            _ => (&self.lines[0], self.cursor_col),
+        };
+        let line_len = line_chars.len();
+
         // Compute horizontal offset so cursor stays visible (same logic
         // as update_h_offset but without mutating self).
-        let h_off = if self.cursor_col < self.h_offset {
-            self.cursor_col
-        } else if self.cursor_col >= self.h_offset + available {
-            self.cursor_col.saturating_sub(available) + 1
+        let h_off = if cursor_col < self.h_offset {
+            cursor_col
+        } else if cursor_col >= self.h_offset + available {
+            cursor_col.saturating_sub(available) + 1
         } else {
             self.h_offset
         };
+        self.place_terminal_cursor(
+            frame,
+            inner,
+            inner.x as usize + prompt_width + chars_width(&line_chars[h_off..cursor_col]),
+            inner.y as usize,
+        );
 
-        // For Password echo mode, replace each character with the mask.
-        let masked;
-        let line_chars = match self.echo_mode {
-            EchoMode::Password(mask) => {
-                masked = vec![mask; self.lines[0].len()];
-                &masked
-            }
-            _ => &self.lines[0],
-        };
-        let line_len = line_chars.len();
-
-        // Determine if overflow indicators are needed
-        let has_left_overflow = h_off > 0;
-        let has_right_overflow = h_off + available < line_len;
+        // Mark hidden text at either edge, unless the cursor sits there.
+        let cursor_at = |col: usize| self.is_active() && col == cursor_col;
+        let has_left_overflow = self.scroll_indicators && h_off > 0 && !cursor_at(h_off);
+        let has_right_overflow = self.scroll_indicators
+            && h_off + available < line_len
+            && !cursor_at(h_off + available - 1);
 
         // Slice the visible window from line content
         let visible_end = (h_off + available).min(line_len);
@@ -996,12 +2109,12 @@ impl TextArea {
         }
 
         // Replace edge characters with overflow indicators
-        if has_left_overflow && !visible.is_empty() {
-            visible[0] = '\u{2026}'; // …
+        if has_left_overflow {
+            visible[0] = '‹';
         }
-        if has_right_overflow && !visible.is_empty() {
+        if has_right_overflow {
             let last = visible.len() - 1;
-            visible[last] = '\u{2026}'; // …
+            visible[last] = '›';
         }
 
         let mut spans = Vec::new();
@@ -1012,7 +2125,21 @@ impl TextArea {
 
         // Show placeholder text when empty and unfocused
         let is_empty = line_len == 0;
-        if is_empty && !self.focus && !self.placeholder.is_empty() {
+        if is_empty && !self.is_active() && !self.placeholder.is_empty() {
+            spans.push(Span::styled(
+                self.placeholder.clone(),
+                self.style.placeholder,
+            ));
+            let paragraph = Paragraph::new(Line::from(spans));
+            frame.render_widget(paragraph, inner);
+            return;
+        }
+        if is_empty
+            && self.is_active()
+            && self.placeholder_while_focused
+            && !self.placeholder.is_empty()
+        {
+            spans.push(Span::styled(" ", self.style.cursor));
             spans.push(Span::styled(
                 self.placeholder.clone(),
                 self.style.placeholder,
@@ -1039,8 +2166,30 @@ impl TextArea {
         };
 
         // Render the visible slice with cursor highlighting
-        if self.focus {
-            let cursor_in_visible = self.cursor_col.saturating_sub(h_off);
+        if self.is_active() && self.has_selection() && self.mask.is_none() {
+            // Group runs of cursor / selected / plain characters.
+            let mut run = String::new();
+            let mut run_style = text_style;
+            for (vi, &c) in visible.iter().enumerate() {
+                let col = h_off + vi;
+                let style = if col == cursor_col {
+                    self.style.cursor
+                } else if col < line_len && self.is_selected(0, col) {
+                    self.style.selection
+                } else {
+                    text_style
+                };
+                if style != run_style && !run.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut run), run_style));
+                }
+                run_style = style;
+                run.push(c);
+            }
+            if !run.is_empty() {
+                spans.push(Span::styled(run, run_style));
+            }
+        } else if self.is_active() {
+            let cursor_in_visible = cursor_col.saturating_sub(h_off);
             let before: String = visible[..cursor_in_visible].iter().collect();
             let cursor_char = visible.get(cursor_in_visible);
             let after_start = cursor_in_visible + 1;
@@ -1051,7 +2200,7 @@ impl TextArea {
             };
 
             if !before.is_empty() {
-                spans.push(Span::styled(before, self.style.text));
+                spans.push(Span::styled(before, text_style));
             }
             if let Some(&c) = cursor_char {
                 // If the character is a padding space at the end of content,
@@ -1061,22 +2210,63 @@ impl TextArea {
                 spans.push(Span::styled(" ", self.style.cursor));
             }
             if !after.is_empty() {
-                spans.push(Span::styled(after, self.style.text));
+                spans.push(Span::styled(after, text_style));
             }
             // Show ghost text after the cursor when cursor is at end of content.
             if let Some(ref ghost) = ghost_text {
-                if self.cursor_col >= line_len {
+                if cursor_col >= line_len {
                     spans.push(Span::styled(ghost.clone(), self.style.suggestion));
                 }
             }
         } else {
             let text: String = visible.iter().collect();
-            spans.push(Span::styled(text, self.style.text));
+            spans.push(Span::styled(text, text_style));
         }
 
+        let markers = [
+            has_left_overflow.then_some(prompt_width),
+            has_right_overflow.then_some(prompt_width + available - 1),
+        ];
+        let spans = patch_cells(
+            spans,
+            markers.into_iter().flatten(),
+            self.style.scroll_indicator,
+        );
+
         let paragraph = Paragraph::new(Line::from(spans));
         frame.render_widget(paragraph, inner);
     }
+
+    /// Put the terminal cursor at `(x, y)` if it is enabled, the editor is
+    /// active and the position lies within `inner`.
+    fn place_terminal_cursor(&self, frame: &mut Frame, inner: Rect, x: usize, y: usize) {
+        if !self.terminal_cursor || !self.is_active() {
+            return;
+        }
+        let (Ok(x), Ok(y)) = (u16::try_from(x), u16::try_from(y)) else {
+            return;
+        };
+        if inner.contains((x, y).into()) {
+            frame.set_cursor_position((x, y));
+        }
+    }
+
+    /// Draw the ruler guides into the top `height` rows of `area`. Runs
+    /// before the text is rendered, so text and the cursor paint over them.
+    fn draw_rulers(&self, buf: &mut Buffer, area: Rect, height: usize, gutter_width: usize) {
+        let height = (height as u16).min(area.height);
+        for &column in &self.rulers {
+            let x = area.x as usize + gutter_width + column as usize;
+            if x >= area.right() as usize {
+                continue;
+            }
+            for y in area.y..area.y + height {
+                buf[(x as u16, y)]
+                    .set_symbol("│")
+                    .set_style(self.style.ruler);
+            }
+        }
+    }
 }
 
 impl Default for TextArea {
@@ -1085,14 +2275,196 @@ impl Default for TextArea {
     }
 }
 
+/// Display width of `chars` in terminal columns.
+fn chars_width(chars: &[char]) -> usize {
+    chars.iter().filter_map(|c| c.width()).sum()
+}
+
+/// Format `digits` through `mask`, returning the display characters and
+/// the display column of raw cursor position `cursor`.
+///
+/// Literals are emitted up to the first `#` slot that has no digit yet, so
+/// separators appear as soon as the slots before them are full.
+fn apply_mask(mask: &[char], digits: &[char], cursor: usize) -> (Vec<char>, usize) {
+    let mut out = Vec::new();
+    let mut cursor_col = None;
+    let mut digits_iter = digits.iter().enumerate();
+    if digits.is_empty() {
+        return (out, 0);
+    }
+    for &m in mask {
+        if m == '#' {
+            match digits_iter.next() {
+                Some((i, &d)) => {
+                    if i == cursor {
+                        cursor_col = Some(out.len());
+                    }
+                    out.push(d);
+                }
+                None => break,
+            }
+        } else {
+            out.push(m);
+        }
+    }
+    let len = out.len();
+    (out, cursor_col.unwrap_or(len))
+}
+
+/// Patch `style` over the characters covering the given display columns
+/// of a line.
+fn patch_cells(
+    spans: Vec<Span<'static>>,
+    cols: impl Iterator<Item = usize>,
+    style: Style,
+) -> Vec<Span<'static>> {
+    let cols: Vec<usize> = cols.collect();
+    if cols.is_empty() {
+        return spans;
+    }
+    let mut out: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+    for span in spans {
+        for ch in span.content.chars() {
+            let width = ch.width().unwrap_or(0);
+            let cell_style = if cols.iter().any(|c| (col..col + width).contains(c)) {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            match out.last_mut() {
+                // Zero-width characters stay with the cell before them.
+                Some(last) if width == 0 || last.style == cell_style => {
+                    last.content.to_mut().push(ch)
+                }
+                _ => out.push(Span::styled(ch.to_string(), cell_style)),
+            }
+            col += width;
+        }
+    }
+    out
+}
+
+/// Replace trailing spaces of `line` with `·` and its tabs with `→` in
+/// `spans`, patching `style` over them. Cells past the end of the line,
+/// like an end-of-line cursor, are left alone.
+fn mark_whitespace(spans: Vec<Span<'static>>, line: &[char], style: Style) -> Vec<Span<'static>> {
+    let trailing_from = line
+        .iter()
+        .rposition(|c| *c != ' ')
+        .map_or(0, |last| last + 1);
+    let marker = |col: usize| match line.get(col) {
+        Some('\t') => Some('→'),
+        Some(' ') if col >= trailing_from => Some('·'),
+        _ => None,
+    };
+    if !(0..line.len()).any(|col| marker(col).is_some()) {
+        return spans;
+    }
+    let mut out: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+    for span in spans {
+        for ch in span.content.chars() {
+            let (ch, cell_style) = match marker(col) {
+                Some(m) => (m, span.style.patch(style)),
+                None => (ch, span.style),
+            };
+            match out.last_mut() {
+                Some(last) if last.style == cell_style => last.content.to_mut().push(ch),
+                _ => out.push(Span::styled(ch.to_string(), cell_style)),
+            }
+            col += 1;
+        }
+    }
+    out
+}
+
+/// Split spans into rows of at most `width` characters, preserving styles.
+fn wrap_spans(spans: Vec<Span<'static>>, width: usize) -> Vec<Vec<Span<'static>>> {
+    let mut rows = vec![Vec::new()];
+    let mut used = 0;
+    for span in spans {
+        let mut rest: &str = &span.content;
+        while !rest.is_empty() {
+            if used == width {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            let take = rest
+                .char_indices()
+                .nth(width - used)
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            let (head, tail) = rest.split_at(take);
+            used += head.chars().count();
+            rows.last_mut()
+                .unwrap()
+                .push(Span::styled(head.to_string(), span.style));
+            rest = tail;
+        }
+    }
+    rows
+}
+
 impl Component for TextArea {
     type Message = Message;
 
     fn update(&mut self, msg: Message) -> Command<Message> {
+        // An edit that ended without reporting a change, such as typing
+        // into a full line, must not widen the next one.
+        self.edit_span = None;
+        if let Some(prefix) = self.locked_prefix.take() {
+            let edits = self.is_active()
+                && match &msg {
+                    Message::KeyPress(key) => !self.only_navigates(key),
+                    Message::Paste(_) | Message::PasteBlock(_) => true,
+                    _ => false,
+                };
+            if !edits {
+                let cmd = self.update(msg);
+                self.locked_prefix = Some(prefix);
+                self.clamp_to_editable();
+                return cmd;
+            }
+            // Apply the edit unrestricted, then roll back anything that
+            // touched the locked prefix.
+            let lines = self.lines.clone();
+            let cursor = (self.cursor_row, self.cursor_col);
+            let typing = self.typing;
+            let change_generation = self.change_generation;
+            let cmd = self.update(msg);
+            self.locked_prefix = Some(prefix);
+            if !self.prefix_intact() {
+                if self
+                    .undo_stack
+                    .back()
+                    .is_some_and(|(undo, _)| *undo == lines)
+                {
+                    self.undo_stack.pop_back();
+                }
+                self.lines = lines;
+                (self.cursor_row, self.cursor_col) = cursor;
+                self.typing = typing;
+                self.edit_span = None;
+                self.change_generation = change_generation;
+                self.selection_start = None;
+                self.run_validate();
+                return Command::none();
+            }
+            self.clamp_to_editable();
+            return cmd;
+        }
         match msg {
-            Message::Paste(text) if self.focus => {
+            Message::Paste(text) if self.is_active() => {
+                let text = match &self.paste_filter {
+                    Some(filter) => filter(text),
+                    None if self.single_line => flatten_newlines(text),
+                    None => text,
+                };
                 // In single-line mode, strip newlines from pasted text.
-                let text = if self.single_line {
+                let text = if self.numeric {
+                    text.chars().filter(char::is_ascii_digit).collect()
+                } else if self.single_line {
                     text.replace(['\n', '\r'], "")
                 } else {
                     text
@@ -1111,13 +2483,27 @@ impl Component for TextArea {
                     self.filter_suggestions();
                 }
                 self.run_validate();
-                Command::message(Message::Changed(self.value()))
+                self.changed()
             }
-            Message::KeyPress(key) if self.focus => {
+            Message::PasteBlock(text) if self.is_active() && !self.single_line => {
+                self.push_undo();
+                self.insert_block(&text);
+                self.run_validate();
+                self.changed()
+            }
+            Message::KeyPress(key) if self.is_active() => {
+                if let Some(cmd) = self.completion_key(&key) {
+                    return cmd;
+                }
+                if self.is_submit_key(&key) {
+                    return Command::message(Message::Submit(self.value()));
+                }
                 let shift = key.modifiers.contains(KeyModifiers::SHIFT);
                 match (key.code, key.modifiers) {
                     // Ctrl+Z: undo
                     (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
+                        self.typing = None;
+                        self.edit_span = None;
                         if let Some((lines, (row, col))) = self.undo_stack.pop_back() {
                             self.redo_stack.push_back((
                                 self.lines.clone(),
@@ -1131,13 +2517,15 @@ impl Component for TextArea {
                                 self.filter_suggestions();
                             }
                             self.run_validate();
-                            Command::message(Message::Changed(self.value()))
+                            self.changed()
                         } else {
                             Command::none()
                         }
                     }
                     // Ctrl+Y: redo
                     (KeyCode::Char('y'), KeyModifiers::CONTROL) => {
+                        self.typing = None;
+                        self.edit_span = None;
                         if let Some((lines, (row, col))) = self.redo_stack.pop_back() {
                             self.undo_stack.push_back((
                                 self.lines.clone(),
@@ -1151,7 +2539,7 @@ impl Component for TextArea {
                                 self.filter_suggestions();
                             }
                             self.run_validate();
-                            Command::message(Message::Changed(self.value()))
+                            self.changed()
                         } else {
                             Command::none()
                         }
@@ -1173,10 +2561,7 @@ impl Component for TextArea {
                                 self.filter_suggestions();
                             }
                             self.run_validate();
-                            Command::batch([
-                                Command::message(Message::Cut(text)),
-                                Command::message(Message::Changed(self.value())),
-                            ])
+                            Command::batch([Command::message(Message::Cut(text)), self.changed()])
                         } else {
                             Command::none()
                         }
@@ -1195,7 +2580,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Ctrl+U: kill to start of line
                     (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
@@ -1206,7 +2591,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Ctrl+W / Alt+Backspace: delete word backward
                     (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
@@ -1217,7 +2602,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     (KeyCode::Backspace, KeyModifiers::ALT) => {
                         self.push_undo();
@@ -1227,7 +2612,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Alt+D: delete word forward
                     (KeyCode::Char('d'), KeyModifiers::ALT) => {
@@ -1238,7 +2623,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Alt+U: uppercase word at cursor
                     (KeyCode::Char('u'), KeyModifiers::ALT) => {
@@ -1249,7 +2634,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Alt+L: lowercase word at cursor
                     (KeyCode::Char('l'), KeyModifiers::ALT) => {
@@ -1260,7 +2645,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Alt+C: capitalize word at cursor
                     (KeyCode::Char('c'), KeyModifiers::ALT) => {
@@ -1271,7 +2656,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Ctrl+Delete: delete word forward
                     (KeyCode::Delete, KeyModifiers::CONTROL) => {
@@ -1282,7 +2667,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     // Ctrl+Left / Alt+Left: move to previous word boundary
                     (KeyCode::Left, KeyModifiers::CONTROL) | (KeyCode::Left, KeyModifiers::ALT) => {
@@ -1297,6 +2682,23 @@ impl Component for TextArea {
                         self.cursor_col = self.next_word_boundary();
                         Command::none()
                     }
+                    // Alt+Shift+arrows: extend a block selection
+                    (KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down, m)
+                        if m.contains(KeyModifiers::ALT | KeyModifiers::SHIFT)
+                            && !self.single_line =>
+                    {
+                        self.ensure_block_selection_started();
+                        match key.code {
+                            KeyCode::Left => self.cursor_col = self.cursor_col.saturating_sub(1),
+                            KeyCode::Right => {
+                                self.cursor_col = (self.cursor_col + 1).min(self.current_line_len())
+                            }
+                            KeyCode::Up => self.cursor_row = self.cursor_row.saturating_sub(1),
+                            _ => self.cursor_row = (self.cursor_row + 1).min(self.lines.len() - 1),
+                        }
+                        self.clamp_cursor_col();
+                        Command::none()
+                    }
                     // Shift+Left: extend selection left
                     (KeyCode::Left, _) if shift => {
                         self.ensure_selection_started();
@@ -1340,7 +2742,7 @@ impl Component for TextArea {
                     // Shift+Home: select to start of line
                     (KeyCode::Home, _) if shift => {
                         self.ensure_selection_started();
-                        self.cursor_col = 0;
+                        self.cursor_col = self.home_col();
                         Command::none()
                     }
                     // Shift+End: select to end of line
@@ -1354,7 +2756,7 @@ impl Component for TextArea {
                         if self.single_line {
                             if self.accept_suggestion() {
                                 self.run_validate();
-                                Command::message(Message::Changed(self.value()))
+                                self.changed()
                             } else {
                                 Command::none()
                             }
@@ -1367,53 +2769,79 @@ impl Component for TextArea {
                                         break;
                                     }
                                 }
+                                if self.line_full() {
+                                    break;
+                                }
                                 self.lines[self.cursor_row].insert(self.cursor_col, ' ');
                                 self.cursor_col += 1;
                             }
                             self.run_validate();
-                            Command::message(Message::Changed(self.value()))
+                            self.changed()
                         }
                     }
                     (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
-                        self.push_undo();
+                        if self.numeric && !c.is_ascii_digit() {
+                            return Command::none();
+                        }
+                        self.push_undo_typed(c);
                         self.delete_selection();
                         if let Some(limit) = self.char_limit {
                             if self.total_chars() >= limit {
                                 return Command::none();
                             }
                         }
+                        if self.line_full() {
+                            return Command::none();
+                        }
                         self.lines[self.cursor_row].insert(self.cursor_col, c);
                         self.cursor_col += 1;
                         if self.single_line {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     (KeyCode::Enter, m) => {
-                        let is_submit = match self.submit_binding {
-                            SubmitBinding::Enter => {
-                                !m.contains(KeyModifiers::SHIFT)
-                                    && !m.contains(KeyModifiers::CONTROL)
-                            }
-                            SubmitBinding::ShiftEnter => m.contains(KeyModifiers::SHIFT),
-                            SubmitBinding::CtrlEnter => m.contains(KeyModifiers::CONTROL),
-                            SubmitBinding::None => false,
-                        };
+                        let is_submit = self.submit_keys.is_empty()
+                            && match self.submit_binding {
+                                SubmitBinding::Enter => {
+                                    !m.contains(KeyModifiers::SHIFT)
+                                        && !m.contains(KeyModifiers::CONTROL)
+                                }
+                                SubmitBinding::ShiftEnter => m.contains(KeyModifiers::SHIFT),
+                                SubmitBinding::CtrlEnter => m.contains(KeyModifiers::CONTROL),
+                                SubmitBinding::None => false,
+                            };
                         if is_submit {
                             return Command::message(Message::Submit(self.value()));
                         }
-                        if self.single_line {
+                        if self.next_field && m == KeyModifiers::NONE {
+                            return Command::message(Message::NextField);
+                        }
+                        if self.single_line || self.at_max_lines() {
                             return Command::none();
                         }
                         self.push_undo();
                         self.delete_selection();
+                        let mut indent = if self.auto_indent {
+                            self.leading_whitespace(self.cursor_row, self.cursor_col)
+                        } else {
+                            Vec::new()
+                        };
+                        if let Some(limit) = self.char_limit {
+                            indent.truncate(limit.saturating_sub(self.total_chars()));
+                        }
+                        if let Some(max) = self.max_line_length {
+                            let rest = self.current_line_len() - self.cursor_col;
+                            indent.truncate(max.saturating_sub(rest));
+                        }
                         let rest = self.lines[self.cursor_row].split_off(self.cursor_col);
                         self.cursor_row += 1;
-                        self.cursor_col = 0;
-                        self.lines.insert(self.cursor_row, rest);
+                        self.cursor_col = indent.len();
+                        indent.extend(rest);
+                        self.lines.insert(self.cursor_row, indent);
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     (KeyCode::Backspace, _) => {
                         if self.has_selection() {
@@ -1425,7 +2853,8 @@ impl Component for TextArea {
                                 self.push_undo();
                                 self.cursor_col -= 1;
                                 self.lines[self.cursor_row].remove(self.cursor_col);
-                            } else if self.cursor_row > 0 {
+                            } else if self.cursor_row > 0 && self.can_join_next(self.cursor_row - 1)
+                            {
                                 self.push_undo();
                                 let current = self.lines.remove(self.cursor_row);
                                 self.cursor_row -= 1;
@@ -1439,7 +2868,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     (KeyCode::Delete, _) => {
                         if self.has_selection() {
@@ -1450,7 +2879,9 @@ impl Component for TextArea {
                             if self.cursor_col < self.current_line_len() {
                                 self.push_undo();
                                 self.lines[self.cursor_row].remove(self.cursor_col);
-                            } else if self.cursor_row < self.lines.len() - 1 {
+                            } else if self.cursor_row < self.lines.len() - 1
+                                && self.can_join_next(self.cursor_row)
+                            {
                                 self.push_undo();
                                 let next = self.lines.remove(self.cursor_row + 1);
                                 self.lines[self.cursor_row].extend(next);
@@ -1462,7 +2893,7 @@ impl Component for TextArea {
                             self.filter_suggestions();
                         }
                         self.run_validate();
-                        Command::message(Message::Changed(self.value()))
+                        self.changed()
                     }
                     (KeyCode::Left, _) => {
                         self.clear_selection();
@@ -1483,7 +2914,7 @@ impl Component for TextArea {
                             && self.accept_suggestion()
                         {
                             self.run_validate();
-                            return Command::message(Message::Changed(self.value()));
+                            return self.changed();
                         } else if self.cursor_row < self.lines.len() - 1 {
                             self.cursor_row += 1;
                             self.cursor_col = 0;
@@ -1507,7 +2938,7 @@ impl Component for TextArea {
                                 self.cursor_col = self.lines[0].len();
                                 self.selection_start = None;
                                 self.run_validate();
-                                return Command::message(Message::Changed(self.value()));
+                                return self.changed();
                             }
                         }
                         self.clear_selection();
@@ -1529,7 +2960,7 @@ impl Component for TextArea {
                                     self.cursor_col = self.lines[0].len();
                                     self.selection_start = None;
                                     self.run_validate();
-                                    return Command::message(Message::Changed(self.value()));
+                                    return self.changed();
                                 }
                             }
                         }
@@ -1542,7 +2973,7 @@ impl Component for TextArea {
                     }
                     (KeyCode::Home, _) => {
                         self.clear_selection();
-                        self.cursor_col = 0;
+                        self.cursor_col = self.home_col();
                         Command::none()
                     }
                     (KeyCode::End, _) => {
@@ -1553,19 +2984,23 @@ impl Component for TextArea {
                     _ => Command::none(),
                 }
             }
+            Message::ChangeSettled(generation) if generation == self.change_generation => {
+                Command::message(Message::Changed(self.value()))
+            }
             Message::Changed(_) => Command::none(),
             _ => Command::none(),
         }
     }
 
     fn view(&self, frame: &mut Frame, area: Rect) {
-        let inner = if let Some(ref block) = self.block {
+        let inner = if let Some(block) = self.decorated_block() {
             let inner = block.inner(area);
-            frame.render_widget(block.clone(), area);
+            frame.render_widget(block, area);
             inner
         } else {
             area
         };
+        let text_style = self.text_style();
 
         // Single-line mode uses a separate rendering path with horizontal
         // scrolling and no line numbers or vertical scroll.
@@ -1576,7 +3011,7 @@ impl Component for TextArea {
 
         // Show placeholder text when empty and unfocused
         let content_empty = self.lines.len() == 1 && self.lines[0].is_empty();
-        if content_empty && !self.focus && !self.placeholder.is_empty() {
+        if content_empty && !self.is_active() && !self.placeholder.is_empty() {
             let placeholder_line = Line::from(Span::styled(
                 self.placeholder.clone(),
                 self.style.placeholder,
@@ -1608,122 +3043,146 @@ impl Component for TextArea {
             0
         };
 
-        let prompt_width = self.line_prompt.as_ref().map(|p| p.len()).unwrap_or(0);
+        let prompt_width = self.line_prompt.as_deref().map_or(0, str::width);
 
         let has_sel = self.has_selection();
+        let brackets = self.bracket_highlights();
+        let content = |actual_row: usize| {
+            let spans = self.line_content_spans(actual_row, text_style, has_sel);
+            let cols = brackets
+                .iter()
+                .filter(|(row, _)| *row == actual_row)
+                .map(|&(_, col)| col);
+            let spans = patch_cells(spans, cols, self.style.bracket_match);
+            if self.show_whitespace {
+                mark_whitespace(spans, &self.lines[actual_row], self.style.whitespace)
+            } else {
+                spans
+            }
+        };
 
-        let end = self.lines.len().min(scroll + visible_height);
-        let visible_lines = &self.lines[scroll..end];
-        let display_lines: Vec<Line> = visible_lines
-            .iter()
-            .enumerate()
-            .map(|(vi, line_chars)| {
-                let actual_row = scroll + vi;
-                let mut spans = Vec::new();
-
-                if self.show_line_numbers {
-                    let num = format!("{:>width$} ", actual_row + 1, width = line_num_width - 1);
-                    spans.push(Span::styled(num, self.style.line_number));
-                }
-
-                if let Some(ref prompt) = self.line_prompt {
-                    spans.push(Span::styled(prompt.clone(), self.style.prompt));
-                }
-
-                if has_sel {
-                    // Render with selection highlighting
-                    let line_len = line_chars.len();
-                    let is_cursor_line = self.focus && actual_row == self.cursor_row;
-
-                    // Build spans character by character, grouping consecutive
-                    // characters with the same style.
-                    let mut i = 0;
-                    while i <= line_len {
-                        if i == line_len {
-                            // At end of line: render cursor if this is the
-                            // cursor line and cursor is past all chars.
-                            if is_cursor_line && self.cursor_col == line_len {
-                                if self.is_selected(actual_row, i) {
-                                    // Cursor on selected trailing position
-                                    spans.push(Span::styled(" ", self.style.cursor));
-                                } else {
-                                    spans.push(Span::styled(" ", self.style.cursor));
-                                }
-                            }
-                            break;
-                        }
+        let gutter = |actual_row: usize| {
+            let mut spans = Vec::new();
+            if self.show_line_numbers {
+                let num = format!("{:>width$} ", actual_row + 1, width = line_num_width - 1);
+                spans.push(Span::styled(num, self.style.line_number));
+            }
+            if let Some(ref prompt) = self.line_prompt {
+                spans.push(Span::styled(prompt.clone(), self.style.prompt));
+            }
+            spans
+        };
 
-                        let sel = self.is_selected(actual_row, i);
-                        let is_cursor = is_cursor_line && i == self.cursor_col;
+        if self.soft_wrap {
+            // Wrap by character ourselves so the cursor's visual row is known
+            // and can be kept on screen.
+            let gutter_width = line_num_width + prompt_width;
+            let text_width = self.wrap_columns((inner.width as usize).saturating_sub(gutter_width));
+            // Narrow the area to the wrapped block and align it.
+            let block_width = (gutter_width + text_width).min(inner.width as usize) as u16;
+            let slack = inner.width - block_width;
+            let outer = inner;
+            let inner = Rect {
+                x: inner.x
+                    + match self.wrap_alignment {
+                        Alignment::Left => 0,
+                        Alignment::Center => slack / 2,
+                        Alignment::Right => slack,
+                    },
+                width: block_width,
+                ..inner
+            };
+            let blank_gutter = " ".repeat(gutter_width);
 
-                        if is_cursor {
-                            // Render cursor character
-                            spans.push(Span::styled(line_chars[i].to_string(), self.style.cursor));
-                            i += 1;
-                        } else {
-                            // Collect a run of characters that share the same
-                            // selected state and are not the cursor.
-                            let style = if sel {
-                                self.style.selection
-                            } else {
-                                self.style.text
-                            };
-                            let start = i;
-                            while i < line_len
-                                && self.is_selected(actual_row, i) == sel
-                                && !(is_cursor_line && i == self.cursor_col)
-                            {
-                                i += 1;
-                            }
-                            let chunk: String = line_chars[start..i].iter().collect();
-                            if !chunk.is_empty() {
-                                spans.push(Span::styled(chunk, style));
-                            }
-                        }
-                    }
-                } else if self.focus && actual_row == self.cursor_row {
-                    let line_str: String = line_chars.iter().collect();
-                    let col = self.cursor_col;
-                    let before: String = line_str.chars().take(col).collect();
-                    let cursor_char = line_str.chars().nth(col);
-                    let after: String = line_str.chars().skip(col + 1).collect();
-
-                    if !before.is_empty() {
-                        spans.push(Span::styled(before, self.style.text));
-                    }
-                    if let Some(c) = cursor_char {
-                        spans.push(Span::styled(c.to_string(), self.style.cursor));
+            let mut rows: Vec<Line> = Vec::new();
+            let mut cursor_visual_row = 0;
+            for actual_row in scroll..self.lines.len() {
+                if actual_row == self.cursor_row {
+                    cursor_visual_row = rows.len() + self.visual_cursor(text_width).0;
+                }
+                for (i, chunk) in wrap_spans(content(actual_row), text_width)
+                    .into_iter()
+                    .enumerate()
+                {
+                    let mut spans = if i == 0 {
+                        gutter(actual_row)
+                    } else if gutter_width > 0 {
+                        vec![Span::raw(blank_gutter.clone())]
                     } else {
-                        spans.push(Span::styled(" ", self.style.cursor));
-                    }
-                    if !after.is_empty() {
-                        spans.push(Span::styled(after, self.style.text));
-                    }
-                } else {
-                    let line_str: String = line_chars.iter().collect();
-                    spans.push(Span::styled(line_str, self.style.text));
+                        Vec::new()
+                    };
+                    spans.extend(chunk);
+                    rows.push(Line::from(spans));
+                }
+                if actual_row >= self.cursor_row && rows.len() >= visible_height {
+                    break;
                 }
+            }
+
+            let skip = (cursor_visual_row + 1).saturating_sub(visible_height);
+            self.place_terminal_cursor(
+                frame,
+                inner,
+                inner.x as usize + gutter_width + self.visual_cursor(text_width).1,
+                inner.y as usize + cursor_visual_row - skip,
+            );
+            let display_lines: Vec<Line> =
+                rows.into_iter().skip(skip).take(visible_height).collect();
+            // Rulers may sit past the wrap column, e.g. to mark it.
+            let ruler_area = Rect {
+                width: outer.right() - inner.x,
+                ..inner
+            };
+            self.draw_rulers(frame.buffer_mut(), ruler_area, visible_height, gutter_width);
+            frame.render_widget(Paragraph::new(display_lines), inner);
+            return;
+        }
 
+        let end = self.lines.len().min(scroll + visible_height);
+        let display_lines: Vec<Line> = (scroll..end)
+            .map(|actual_row| {
+                let mut spans = gutter(actual_row);
+                spans.extend(content(actual_row));
                 Line::from(spans)
             })
             .collect();
 
-        // Account for prompt and line number widths when placing the cursor.
-        // The cursor_col in the internal model maps to a display column offset
-        // by line_num_width + prompt_width.
-        let _cursor_display_col = line_num_width + prompt_width + self.cursor_col;
+        let gutter_width = line_num_width + prompt_width;
+        self.place_terminal_cursor(
+            frame,
+            inner,
+            inner.x as usize
+                + gutter_width
+                + chars_width(&self.lines[self.cursor_row][..self.cursor_col]),
+            (inner.y as usize + self.cursor_row).saturating_sub(scroll),
+        );
+        self.draw_rulers(frame.buffer_mut(), inner, visible_height, gutter_width);
+        let paragraph = Paragraph::new(display_lines);
+        frame.render_widget(paragraph, inner);
+    }
 
-        let paragraph = if self.soft_wrap {
-            Paragraph::new(display_lines).wrap(Wrap { trim: false })
+    /// Single-line inputs (and areas capped by `max_visible_lines`) ask for
+    /// exactly their content height plus block chrome; free-growing areas
+    /// ask for at least that much.
+    fn size_hint(&self, available: Rect) -> (Constraint, Constraint) {
+        let (chrome_w, chrome_h) = crate::chrome::block_chrome(self.block.as_ref());
+        let height = self.visual_height(available.width.saturating_sub(chrome_w)) + chrome_h;
+        let height = if self.single_line || self.max_visible_lines.is_some() {
+            Constraint::Length(height)
         } else {
-            Paragraph::new(display_lines)
+            Constraint::Min(height)
         };
-        frame.render_widget(paragraph, inner);
+        (Constraint::Min(0), height)
     }
 
     fn focused(&self) -> bool {
         self.focus
     }
+
+    /// `true` while a cycled completion is previewed, so Esc can revert it.
+    fn wants_escape(&self) -> bool {
+        self.completion.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -1819,6 +3278,65 @@ mod tests {
         assert!(ta.undo_stack.len() <= 100);
     }
 
+    fn type_str(ta: &mut TextArea, text: &str) {
+        for c in text.chars() {
+            send_key(ta, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    fn undo(ta: &mut TextArea) {
+        send_key(ta, KeyCode::Char('z'), KeyModifiers::CONTROL);
+    }
+
+    #[test]
+    fn undo_grouping_per_word_undoes_whole_words() {
+        let mut ta = TextArea::new().with_undo_grouping(UndoGrouping::PerWord);
+        ta.focus();
+        type_str(&mut ta, "hello world");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "hello ");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "");
+
+        // Other edits still get their own step.
+        type_str(&mut ta, "abc");
+        send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        type_str(&mut ta, "d");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "ab");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "abc");
+    }
+
+    #[test]
+    fn undo_grouping_timed_groups_within_window() {
+        let clock = crate::clock::ManualClock::new();
+        let mut ta = TextArea::new()
+            .with_undo_grouping(UndoGrouping::Timed(Duration::from_millis(500)))
+            .with_clock(clock.clone());
+        ta.focus();
+        for c in "one two".chars() {
+            send_key(&mut ta, KeyCode::Char(c), KeyModifiers::NONE);
+            clock.advance(Duration::from_millis(100));
+        }
+        clock.advance(Duration::from_secs(1));
+        type_str(&mut ta, " three");
+
+        undo(&mut ta);
+        assert_eq!(ta.value(), "one two");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "");
+    }
+
+    #[test]
+    fn undo_grouping_per_keystroke_is_default() {
+        let mut ta = TextArea::new();
+        ta.focus();
+        type_str(&mut ta, "ab");
+        undo(&mut ta);
+        assert_eq!(ta.value(), "a");
+    }
+
     #[test]
     fn test_undo_enter() {
         let mut ta = TextArea::new().with_content("hello");
@@ -1832,8 +3350,259 @@ mod tests {
     }
 
     #[test]
-    fn test_undo_backspace_at_line_boundary() {
-        let mut ta = TextArea::new().with_content("hello\nworld");
+    fn test_auto_indent_inherits_leading_whitespace() {
+        let mut ta = TextArea::new()
+            .with_content("    foo")
+            .with_auto_indent(true);
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "    foo\n    ");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 4));
+
+        send_key(&mut ta, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(ta.value(), "    foo\n    x");
+    }
+
+    #[test]
+    fn test_auto_indent_splits_mid_line() {
+        let mut ta = TextArea::new()
+            .with_content("\tfoo bar")
+            .with_auto_indent(true);
+        ta.focus();
+        ta.cursor_col = 5;
+        send_key(&mut ta, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "\tfoo \n\tbar");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 1));
+    }
+
+    #[test]
+    fn test_auto_indent_undo_reverts_split_and_indent() {
+        let mut ta = TextArea::new()
+            .with_content("    foo")
+            .with_auto_indent(true);
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Enter, KeyModifiers::NONE);
+        send_key(&mut ta, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(ta.value(), "    foo");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (0, 7));
+    }
+
+    /// The `Edited` message at the front of an edit's command.
+    fn edited(cmd: Command<Message>) -> (Range<usize>, usize, usize) {
+        let first = cmd.into_batch().unwrap().into_iter().next().unwrap();
+        match first.into_message() {
+            Some(Message::Edited {
+                changed_rows,
+                old_line_count,
+                new_line_count,
+            }) => (changed_rows, old_line_count, new_line_count),
+            other => panic!("expected Edited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_edit_on_one_line_reports_only_that_row() {
+        let mut ta = TextArea::new()
+            .with_content("one\ntwo\nthree")
+            .with_edit_events(true);
+        ta.focus();
+        ta.cursor_row = 1;
+        ta.cursor_col = 3;
+        let cmd = send_key(&mut ta, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(edited(cmd), (1..2, 3, 3));
+
+        // Joining two lines changes the upper one.
+        ta.cursor_col = 0;
+        let cmd = send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "onetwos\nthree");
+        assert_eq!(edited(cmd), (0..1, 3, 2));
+
+        // Off by default.
+        let mut plain = TextArea::new().with_content("one");
+        plain.focus();
+        let cmd = send_key(&mut plain, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(matches!(extract_message(cmd), Some(Message::Changed(_))));
+    }
+
+    #[test]
+    fn test_rejected_edit_does_not_widen_next_report() {
+        let mut ta = TextArea::new()
+            .with_content("abc\nx\ny")
+            .with_max_line_length(3)
+            .with_edit_events(true);
+        ta.focus();
+        ta.cursor_col = 3;
+        assert!(send_key(&mut ta, KeyCode::Char('d'), KeyModifiers::NONE).is_none());
+
+        ta.cursor_row = 2;
+        ta.cursor_col = 1;
+        let cmd = send_key(&mut ta, KeyCode::Char('z'), KeyModifiers::NONE);
+        assert_eq!(edited(cmd), (2..3, 3, 3));
+    }
+
+    #[test]
+    fn test_multi_line_paste_reports_spanned_rows() {
+        let mut ta = TextArea::new()
+            .with_content("a\nb\nc")
+            .with_edit_events(true);
+        ta.focus();
+        ta.cursor_row = 1;
+        ta.cursor_col = 1;
+        let cmd = ta.update(Message::Paste("x\ny\nz".into()));
+        assert_eq!(ta.value(), "a\nbx\ny\nz\nc");
+        assert_eq!(edited(cmd), (1..4, 3, 5));
+    }
+
+    #[test]
+    fn test_terminal_cursor_follows_caret_when_focused() {
+        let mut ta = TextArea::new().with_content("hello\nworld");
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 4)).unwrap();
+        let mut draw = |ta: &TextArea| {
+            terminal.draw(|f| ta.view(f, f.area())).unwrap();
+            terminal.get_cursor_position().unwrap()
+        };
+
+        ta.focus();
+        ta.cursor_row = 1;
+        ta.cursor_col = 3;
+        // Past the "1 " line-number gutter.
+        assert_eq!(draw(&ta), (5, 1).into());
+
+        let mut wrapped = TextArea::new()
+            .with_content("abcdefghij")
+            .with_line_numbers(false)
+            .with_soft_wrap(true);
+        wrapped.focus();
+        wrapped.cursor_col = 7;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(4, 4)).unwrap();
+        terminal.draw(|f| wrapped.view(f, f.area())).unwrap();
+        assert_eq!(terminal.get_cursor_position().unwrap(), (3, 1).into());
+    }
+
+    #[test]
+    fn test_terminal_cursor_counts_wide_characters() {
+        let mut ta = TextArea::new()
+            .with_content("日本語")
+            .with_line_numbers(false);
+        ta.focus();
+        ta.cursor_col = 2;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 2)).unwrap();
+        terminal.draw(|f| ta.view(f, f.area())).unwrap();
+        assert_eq!(terminal.get_cursor_position().unwrap(), (4, 0).into());
+
+        let mut line = TextArea::new()
+            .with_single_line(true)
+            .with_content("日本語");
+        line.focus();
+        line.cursor_col = 2;
+        terminal.draw(|f| line.view(f, f.area())).unwrap();
+        assert_eq!(terminal.get_cursor_position().unwrap(), (4, 0).into());
+    }
+
+    #[test]
+    fn test_enter_is_ignored_at_max_lines() {
+        let mut ta = TextArea::new().with_content("a\nb").with_max_lines(2);
+        ta.focus();
+        send_key(&mut ta, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "a\nb");
+        assert_eq!(ta.line_count(), 2);
+
+        // Pasted text stops at the last allowed line.
+        let mut ta = TextArea::new().with_max_lines(2);
+        ta.focus();
+        ta.update(Message::Paste("one\ntwo\nthree".into()));
+        assert_eq!(ta.value(), "one\ntwo");
+    }
+
+    #[test]
+    fn test_full_line_rejects_inserts_but_others_stay_editable() {
+        let mut ta = TextArea::new()
+            .with_content("abc\nx")
+            .with_max_line_length(3);
+        ta.focus();
+        ta.cursor_col = 3;
+        send_key(&mut ta, KeyCode::Char('d'), KeyModifiers::NONE);
+        ta.insert_rune('e');
+        assert_eq!(ta.value(), "abc\nx");
+
+        send_key(&mut ta, KeyCode::Down, KeyModifiers::NONE);
+        send_key(&mut ta, KeyCode::Char('y'), KeyModifiers::NONE);
+        send_key(&mut ta, KeyCode::Char('z'), KeyModifiers::NONE);
+        send_key(&mut ta, KeyCode::Char('!'), KeyModifiers::NONE);
+        assert_eq!(ta.value(), "abc\nxyz");
+    }
+
+    #[test]
+    fn test_join_over_max_line_length_is_rejected() {
+        let mut ta = TextArea::new()
+            .with_content("ab\ncd\ne")
+            .with_max_line_length(3);
+        ta.focus();
+        ta.cursor_row = 1;
+        assert!(send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE).is_none());
+        ta.cursor_row = 0;
+        ta.cursor_col = 2;
+        assert!(send_key(&mut ta, KeyCode::Delete, KeyModifiers::NONE).is_none());
+        assert_eq!(ta.value(), "ab\ncd\ne");
+
+        // Joins that fit still go through.
+        ta.cursor_row = 2;
+        ta.cursor_col = 0;
+        send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "ab\ncde");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 2));
+    }
+
+    #[test]
+    fn test_auto_indent_disabled_by_default() {
+        let mut ta = TextArea::new().with_content("    foo");
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "    foo\n");
+    }
+
+    #[test]
+    fn test_smart_home_toggles_between_indent_and_column_zero() {
+        let mut ta = TextArea::new()
+            .with_content("    foo")
+            .with_smart_home(true);
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(ta.cursor_col(), 4);
+        send_key(&mut ta, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(ta.cursor_col(), 0);
+        send_key(&mut ta, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(ta.cursor_col(), 4);
+    }
+
+    #[test]
+    fn test_smart_home_with_shift_selects_to_indent() {
+        let mut ta = TextArea::new()
+            .with_content("    foo")
+            .with_smart_home(true);
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Home, KeyModifiers::SHIFT);
+        assert_eq!(ta.selected_text().as_deref(), Some("foo"));
+
+        // Without smart home, Home always goes to column 0.
+        let mut ta = TextArea::new().with_content("    foo");
+        ta.focus();
+        ta.cursor_col = 7;
+        send_key(&mut ta, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(ta.cursor_col(), 0);
+    }
+
+    #[test]
+    fn test_undo_backspace_at_line_boundary() {
+        let mut ta = TextArea::new().with_content("hello\nworld");
         ta.focus();
         ta.cursor_row = 1;
         ta.cursor_col = 0;
@@ -1992,6 +3761,152 @@ mod tests {
         assert_eq!(ta.value(), "hello");
     }
 
+    fn repl() -> TextArea {
+        let mut ta = TextArea::new().with_locked_prefix("log\n>>> ");
+        ta.focus();
+        ta
+    }
+
+    #[test]
+    fn locked_prefix_keeps_cursor_out() {
+        let mut ta = repl();
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 4));
+        for code in [KeyCode::Left, KeyCode::Home, KeyCode::Up] {
+            send_key(&mut ta, code, KeyModifiers::NONE);
+            assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 4), "{code:?}");
+        }
+        send_key(&mut ta, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(ta.selected_text(), None);
+    }
+
+    #[test]
+    fn only_edit_keys_count_as_locked_edits() {
+        let mut ta = repl();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(ta.only_navigates(&key(KeyCode::Left)));
+        assert!(ta.only_navigates(&key(KeyCode::Up)));
+        assert!(!ta.only_navigates(&key(KeyCode::Char('x'))));
+
+        // History browsing swaps in a whole entry, so Up may edit.
+        ta = TextArea::new()
+            .with_single_line(true)
+            .with_history(10)
+            .with_locked_prefix("> ");
+        assert!(!ta.only_navigates(&key(KeyCode::Up)));
+        assert!(!ta.only_navigates(&key(KeyCode::Right)));
+    }
+
+    #[test]
+    fn backspace_at_locked_boundary_is_noop() {
+        let mut ta = repl();
+        send_key(&mut ta, KeyCode::Char('x'), KeyModifiers::NONE);
+        let cmd = send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(matches!(extract_message(cmd), Some(Message::Changed(_))));
+
+        let cmd = send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(cmd.is_none());
+        let cmd = send_key(&mut ta, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert!(cmd.is_none());
+        assert_eq!(ta.value(), "log\n>>> ");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 4));
+
+        // The rejected edits left nothing to undo past the typed "x".
+        send_key(&mut ta, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(ta.value(), "log\n>>> x");
+    }
+
+    #[test]
+    fn rejected_edit_keeps_pending_change_timer() {
+        let mut ta = TextArea::new()
+            .with_locked_prefix(">>> ")
+            .with_change_throttle(Duration::from_millis(100));
+        ta.focus();
+        send_key(&mut ta, KeyCode::Char('x'), KeyModifiers::NONE);
+        send_key(&mut ta, KeyCode::Left, KeyModifiers::NONE);
+        assert!(send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE).is_none());
+
+        // The timer scheduled by the typed "x" still reports it.
+        let cmd = ta.update(Message::ChangeSettled(1));
+        assert!(matches!(extract_message(cmd), Some(Message::Changed(s)) if s == ">>> x"));
+    }
+
+    #[test]
+    fn value_includes_locked_prefix() {
+        let mut ta = repl();
+        for c in "1 + 1".chars() {
+            send_key(&mut ta, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(ta.value(), "log\n>>> 1 + 1");
+        assert_eq!(ta.editable_value(), "1 + 1");
+
+        ta.reset();
+        assert_eq!(ta.value(), "log\n>>> ");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (1, 4));
+    }
+
+    fn block_fixture() -> TextArea {
+        let mut ta = TextArea::new().with_content("abcde\nfghij\nklmno");
+        ta.focus();
+        // Anchor at (0, 1), then Alt+Shift+Right x3 and Alt+Shift+Down x1.
+        ta.cursor_row = 0;
+        ta.cursor_col = 1;
+        let block = KeyModifiers::ALT | KeyModifiers::SHIFT;
+        for _ in 0..3 {
+            send_key(&mut ta, KeyCode::Right, block);
+        }
+        send_key(&mut ta, KeyCode::Down, block);
+        ta
+    }
+
+    #[test]
+    fn block_selection_copies_rectangle() {
+        let mut ta = block_fixture();
+        assert!(ta.is_block_selection());
+        assert_eq!(ta.selected_text().as_deref(), Some("bcd\nghi"));
+        let cmd = send_key(&mut ta, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        match extract_message(cmd) {
+            Some(Message::Copy(text)) => assert_eq!(text, "bcd\nghi"),
+            other => panic!("expected Copy, got {other:?}"),
+        }
+        assert!(ta.is_selected(1, 1));
+        assert!(!ta.is_selected(1, 4));
+        assert!(!ta.is_selected(2, 2));
+    }
+
+    #[test]
+    fn block_selection_deletes_rectangle() {
+        let mut ta = block_fixture();
+        send_key(&mut ta, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(ta.value(), "ae\nfj\nklmno");
+        assert_eq!((ta.cursor_row(), ta.cursor_col()), (0, 1));
+        assert!(!ta.has_selection());
+
+        send_key(&mut ta, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(ta.value(), "abcde\nfghij\nklmno");
+    }
+
+    #[test]
+    fn paste_block_inserts_across_lines() {
+        let mut ta = block_fixture();
+        let block = ta.selected_text().unwrap();
+        send_key(&mut ta, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(ta.value(), "ae\nfj\nklmno");
+
+        // Paste back at the end of the rows, padding and extending as needed.
+        ta.cursor_row = 1;
+        ta.cursor_col = 2;
+        ta.update(Message::PasteBlock(format!("{block}\nXY")));
+        assert_eq!(ta.value(), "ae\nfjbcd\nklghimno\n  XY");
+    }
+
+    #[test]
+    fn shift_arrow_turns_block_into_linear_selection() {
+        let mut ta = block_fixture();
+        send_key(&mut ta, KeyCode::Right, KeyModifiers::SHIFT);
+        assert!(!ta.is_block_selection());
+        assert_eq!(ta.selected_text().as_deref(), Some("bcde\nfghij"));
+    }
+
     #[test]
     fn test_delete_key_undo() {
         let mut ta = TextArea::new().with_content("hello");
@@ -2117,13 +4032,43 @@ mod tests {
         assert_eq!(ta.line_count(), 1);
     }
 
+    #[test]
+    fn terminal_events_map_to_key_press_and_paste() {
+        use boba_core::event::{TerminalEvent, TerminalMessage};
+
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(matches!(
+            Message::from_terminal_event(TerminalEvent::Key(key)),
+            Some(Message::KeyPress(k)) if k == key
+        ));
+        assert!(matches!(
+            Message::from_terminal_event(TerminalEvent::Paste("b c".into())),
+            Some(Message::Paste(text)) if text == "b c"
+        ));
+        assert!(Message::from_terminal_event(TerminalEvent::FocusGained).is_none());
+
+        let mut ta = TextArea::new();
+        ta.focus();
+        ta.handle_terminal_event(TerminalEvent::Key(key));
+        ta.handle_terminal_event(TerminalEvent::Paste("b c".into()));
+        assert_eq!(ta.value(), "ab c");
+    }
+
     #[test]
     fn single_line_paste_strips_newlines() {
         let mut ta = TextArea::new().with_single_line(true);
         ta.focus();
         ta.update(Message::Paste("hello\nworld\nfoo".into()));
-        assert_eq!(ta.value(), "helloworldfoo");
+        assert_eq!(ta.value(), "hello world foo");
         assert_eq!(ta.line_count(), 1);
+
+        // A custom filter can leave line breaks in; they are still dropped.
+        let mut ta = TextArea::new()
+            .with_single_line(true)
+            .with_paste_filter(|text| text);
+        ta.focus();
+        ta.update(Message::Paste("hello\nworld".into()));
+        assert_eq!(ta.value(), "helloworld");
     }
 
     #[test]
@@ -2347,6 +4292,25 @@ mod tests {
         assert_eq!(ta.err(), Some("Too long"));
     }
 
+    #[test]
+    fn error_style_applies_while_invalid() {
+        let mut ta = TextArea::new().with_single_line(true).with_validate(|v| {
+            if v == "x" {
+                Err("no x".into())
+            } else {
+                Ok(())
+            }
+        });
+        ta.focus();
+        assert_eq!(ta.text_style(), ta.style.text);
+
+        send_key(&mut ta, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(ta.text_style(), ta.style.text.patch(ta.style.error));
+
+        send_key(&mut ta, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(ta.text_style(), ta.style.text);
+    }
+
     #[test]
     fn validation_clears_on_valid() {
         let mut ta = TextArea::new().with_single_line(true).with_validate(|s| {
@@ -2449,4 +4413,258 @@ mod tests {
         assert_eq!(ta.cursor_row(), 0);
         assert_eq!(ta.cursor_col(), 0);
     }
+
+    fn render_rows(ta: &TextArea, width: u16, height: u16) -> ratatui::buffer::Buffer {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| ta.view(frame, frame.area())).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row_text(buf: &ratatui::buffer::Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    fn is_cursor_cell(buf: &ratatui::buffer::Buffer, x: u16, y: u16) -> bool {
+        buf[(x, y)].modifier.contains(Modifier::REVERSED)
+    }
+
+    #[test]
+    fn soft_wrap_cursor_past_width_renders_on_next_row() {
+        let mut ta = TextArea::new()
+            .with_soft_wrap(true)
+            .with_line_numbers(false)
+            .with_content("abcdefghij");
+        ta.focus();
+        ta.set_cursor(7); // "h"
+
+        let buf = render_rows(&ta, 5, 3);
+        assert_eq!(row_text(&buf, 0), "abcde");
+        assert_eq!(row_text(&buf, 1), "fghij");
+        assert!(is_cursor_cell(&buf, 2, 1));
+        assert!(!is_cursor_cell(&buf, 2, 0));
+    }
+
+    #[test]
+    fn soft_wrap_cursor_at_end_of_full_row_wraps() {
+        let mut ta = TextArea::new()
+            .with_soft_wrap(true)
+            .with_line_numbers(false)
+            .with_content("abcde");
+        ta.focus();
+        ta.set_cursor(5);
+
+        let buf = render_rows(&ta, 5, 2);
+        assert_eq!(row_text(&buf, 0), "abcde");
+        assert!(is_cursor_cell(&buf, 0, 1));
+    }
+
+    #[test]
+    fn soft_wrap_scrolls_to_keep_cursor_row_visible() {
+        let mut ta = TextArea::new()
+            .with_soft_wrap(true)
+            .with_line_numbers(false)
+            .with_content("aaaaabbbbbccccc");
+        ta.focus();
+        ta.set_cursor(12); // third visual row
+
+        let buf = render_rows(&ta, 5, 2);
+        assert_eq!(row_text(&buf, 0), "bbbbb");
+        assert_eq!(row_text(&buf, 1), "ccccc");
+        assert!(is_cursor_cell(&buf, 2, 1));
+    }
+
+    #[test]
+    fn soft_wrap_continuation_rows_keep_gutter() {
+        let mut ta = TextArea::new()
+            .with_soft_wrap(true)
+            .with_line_numbers(true)
+            .with_content("abcdefgh");
+        ta.focus();
+        ta.set_cursor(6);
+
+        // Gutter is "1 " (2 columns), leaving 4 for text
+        let buf = render_rows(&ta, 6, 2);
+        assert_eq!(row_text(&buf, 0), "1 abcd");
+        assert_eq!(row_text(&buf, 1), "  efgh");
+        assert!(is_cursor_cell(&buf, 4, 1));
+    }
+
+    #[test]
+    fn wrap_width_wraps_at_fixed_column() {
+        let mut ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_wrap_width(Some(4))
+            .with_content("abcdefghij");
+        ta.focus();
+        ta.set_cursor(5); // "f"
+        assert_eq!(ta.visual_height(10), 3);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(10, 3)).unwrap();
+        terminal.draw(|f| ta.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        assert_eq!(row_text(buf, 0), "abcd      ");
+        assert_eq!(row_text(buf, 1), "efgh      ");
+        assert_eq!(row_text(buf, 2), "ij        ");
+        assert!(is_cursor_cell(buf, 1, 1));
+        assert_eq!(terminal.get_cursor_position().unwrap(), (1, 1).into());
+    }
+
+    #[test]
+    fn wrap_width_block_can_be_centered() {
+        let mut ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_wrap_width(Some(4))
+            .with_wrap_alignment(Alignment::Center)
+            .with_content("abcdef");
+        ta.focus();
+        ta.set_cursor(5);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(10, 2)).unwrap();
+        terminal.draw(|f| ta.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        assert_eq!(row_text(buf, 0), "   abcd   ");
+        assert_eq!(row_text(buf, 1), "   ef     ");
+        assert!(is_cursor_cell(buf, 4, 1));
+        assert_eq!(terminal.get_cursor_position().unwrap(), (4, 1).into());
+
+        // A narrower area wraps at its own width.
+        assert_eq!(ta.visual_height(3), 2);
+    }
+
+    #[test]
+    fn show_whitespace_marks_trailing_spaces_and_tabs() {
+        let ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_show_whitespace(true)
+            .with_content("a b  \n\tc");
+
+        let buf = render_rows(&ta, 6, 2);
+        assert_eq!(row_text(&buf, 0), "a b·· ");
+        assert_eq!(row_text(&buf, 1), "→c    ");
+        assert_eq!(buf[(3, 0)].fg, Color::DarkGray);
+        // Inner spaces are left as they are.
+        assert_eq!(buf[(1, 0)].fg, Color::Reset);
+        assert_eq!(ta.value(), "a b  \n\tc");
+    }
+
+    #[test]
+    fn scroll_indicators_follow_wide_prompts() {
+        let mut ta = TextArea::new()
+            .with_single_line(true)
+            .with_line_prompt("中 ")
+            .with_content("abcdefghijklmnop");
+        ta.set_cursor(0);
+
+        // The prompt takes three columns, leaving seven for text.
+        let buf = render_rows(&ta, 10, 1);
+        assert_eq!(buf[(3, 0)].symbol(), "a");
+        assert_eq!(buf[(8, 0)].symbol(), "f");
+        assert_eq!(buf[(9, 0)].symbol(), "›");
+        assert_eq!(buf[(9, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(8, 0)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn ruler_marks_the_column_past_the_limit() {
+        let ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_ruler(Some(10))
+            .with_content("short\nexactly10c\nthis one is too long");
+
+        let buf = render_rows(&ta, 24, 4);
+        assert_eq!(row_text(&buf, 0), "short     │             ");
+        assert_eq!(row_text(&buf, 1), "exactly10c│             ");
+        // Text crossing the guide covers it.
+        assert_eq!(row_text(&buf, 2), "this one is too long    ");
+        // The guide runs past the last line.
+        assert_eq!(buf[(10, 3)].symbol(), "│");
+        assert_eq!(buf[(10, 0)].fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn ruler_shifts_with_the_gutter() {
+        let content = (1..=10)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ta = TextArea::new()
+            .with_line_numbers(true)
+            .with_line_prompt("> ")
+            .with_ruler(Some(10))
+            .with_content(&content);
+
+        // "10" needs a three-column number gutter, plus the two-column prompt.
+        let buf = render_rows(&ta, 20, 2);
+        assert_eq!(row_text(&buf, 0), " 1 > 1         │    ");
+        assert_eq!(buf[(15, 1)].symbol(), "│");
+
+        let ta = ta.with_line_numbers(false);
+        let buf = render_rows(&ta, 20, 1);
+        assert_eq!(row_text(&buf, 0), "> 1         │       ");
+    }
+
+    #[test]
+    fn matching_bracket_same_line() {
+        let mut ta = TextArea::new().with_content("f(a[1], b)");
+        ta.set_cursor(1); // "("
+        assert_eq!(ta.matching_bracket(), Some((0, 9)));
+        ta.set_cursor(9); // ")"
+        assert_eq!(ta.matching_bracket(), Some((0, 1)));
+        ta.set_cursor(3); // "["
+        assert_eq!(ta.matching_bracket(), Some((0, 5)));
+    }
+
+    #[test]
+    fn matching_bracket_across_lines() {
+        let mut ta = TextArea::new().with_content("fn x() {\n    if y {\n\n    }\n}");
+        ta.set_cursor(7); // outer "{"
+        assert_eq!(ta.matching_bracket(), Some((4, 0)));
+        ta.set_cursor(ta.value().len()); // end of text, past the last "}"
+        assert_eq!(ta.matching_bracket(), None);
+        ta.set_cursor(ta.value().len() - 1); // final "}"
+        assert_eq!(ta.matching_bracket(), Some((0, 7)));
+    }
+
+    #[test]
+    fn unmatched_bracket_highlights_nothing() {
+        let mut ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_bracket_matching(true)
+            .with_content("(a b");
+        ta.focus();
+        ta.set_cursor(0);
+        assert_eq!(ta.matching_bracket(), None);
+        let buf = render_rows(&ta, 10, 1);
+        for x in 0..4 {
+            assert_ne!(buf[(x, 0)].fg, Color::Yellow, "column {x}");
+        }
+    }
+
+    #[test]
+    fn bracket_pair_is_highlighted_when_enabled() {
+        let mut ta = TextArea::new()
+            .with_line_numbers(false)
+            .with_bracket_matching(true)
+            .with_content("(ab)\n[x]");
+        ta.focus();
+        ta.set_cursor(3); // ")"
+        let buf = render_rows(&ta, 10, 2);
+        assert_eq!(buf[(0, 0)].fg, Color::Yellow);
+        assert_eq!(buf[(3, 0)].fg, Color::Yellow);
+        assert!(is_cursor_cell(&buf, 3, 0));
+        assert_ne!(buf[(1, 0)].fg, Color::Yellow);
+        assert_ne!(buf[(0, 1)].fg, Color::Yellow);
+
+        // Disabled by default.
+        let mut plain = TextArea::new()
+            .with_line_numbers(false)
+            .with_content("(ab)");
+        plain.focus();
+        plain.set_cursor(3);
+        let buf = render_rows(&plain, 10, 1);
+        assert_ne!(buf[(0, 0)].fg, Color::Yellow);
+    }
 }
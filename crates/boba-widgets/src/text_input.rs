@@ -9,12 +9,14 @@
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Block;
 use ratatui::Frame;
 
+use crate::key::KeyCombination;
 use crate::text_area::{self, TextArea, TextAreaStyle};
 
 /// Controls how input text is displayed.
@@ -42,6 +44,14 @@ pub struct TextInputStyle {
     pub cursor: Style,
     /// Style applied to autocomplete suggestion ghost text.
     pub suggestion: Style,
+    /// Style applied to the text and border while validation fails.
+    pub error: Style,
+    /// Style applied to selected text.
+    pub selection: Style,
+    /// Style patched over the text while the input is disabled.
+    pub disabled: Style,
+    /// Style patched over the `‹`/`›` scroll indicators.
+    pub scroll_indicator: Style,
 }
 
 impl Default for TextInputStyle {
@@ -52,6 +62,14 @@ impl Default for TextInputStyle {
             placeholder: Style::default().fg(Color::DarkGray),
             cursor: Style::default().add_modifier(Modifier::REVERSED),
             suggestion: Style::default().fg(Color::DarkGray),
+            error: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::UNDERLINED),
+            selection: Style::default().bg(Color::DarkGray),
+            disabled: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
+            scroll_indicator: Style::default().fg(Color::DarkGray),
         }
     }
 }
@@ -65,10 +83,30 @@ pub enum Message {
     Paste(String),
     /// Emitted when the input value changes.
     Changed(String),
-    /// Emitted when Enter is pressed.
+    /// Emitted when Enter, or a key set with
+    /// [`with_submit_keys`](TextInput::with_submit_keys), is pressed.
     Submit(String),
+    /// Emitted on Enter when
+    /// [`with_next_field`](TextInput::with_next_field) is enabled and
+    /// Enter doesn't submit.
+    NextField,
+    /// Emitted on Ctrl+C with the selected text.
+    Copy(String),
+    /// Emitted on Ctrl+X with the text removed from the selection.
+    Cut(String),
+    /// Internal timer for [`with_change_throttle`](TextInput::with_change_throttle).
+    /// Forward it back to the input like any other message.
+    ChangeSettled(u64),
+    /// Emitted before [`Changed`](Message::Changed) when
+    /// [`with_edit_events`](TextInput::with_edit_events) is enabled.
+    Edited,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress,
+    paste: Paste,
+});
+
 /// A single-line text input component.
 ///
 /// This is a thin wrapper around [`TextArea`] configured in single-line mode.
@@ -131,6 +169,34 @@ impl TextInput {
         self.inner.history()
     }
 
+    /// Keep showing the placeholder as dim text after the cursor while the
+    /// input is focused and empty (default: false).
+    pub fn with_placeholder_while_focused(mut self, show: bool) -> Self {
+        self.inner = self.inner.with_placeholder_while_focused(show);
+        self
+    }
+
+    /// Place the terminal's own cursor on the caret while focused (default:
+    /// true), for IME composition and screen readers.
+    pub fn with_terminal_cursor(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.with_terminal_cursor(enabled);
+        self
+    }
+
+    /// Submit on exactly these key combinations instead of Enter, e.g.
+    /// Ctrl+Enter in a form. An empty list restores Enter.
+    pub fn with_submit_keys(mut self, keys: Vec<KeyCombination>) -> Self {
+        self.inner = self.inner.with_submit_keys(keys);
+        self
+    }
+
+    /// Emit [`Message::NextField`] on plain Enter when it doesn't submit
+    /// (default: false).
+    pub fn with_next_field(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.with_next_field(enabled);
+        self
+    }
+
     /// Set a prompt string displayed before the input (e.g., `> `).
     pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
         self.inner = self.inner.with_prompt(prompt);
@@ -143,6 +209,60 @@ impl TextInput {
         self
     }
 
+    /// Only accept digits (default: false).
+    pub fn with_numeric(mut self, numeric: bool) -> Self {
+        self.inner = self.inner.with_numeric(numeric);
+        self
+    }
+
+    /// Format the input with a mask such as `"(###) ###-####"`, where `#`
+    /// accepts a digit and other characters are separators inserted
+    /// automatically. [`value`](Self::value) returns only the digits; see
+    /// [`TextArea::with_mask`].
+    pub fn with_mask(mut self, mask: &str) -> Self {
+        self.inner = self.inner.with_mask(mask);
+        self
+    }
+
+    /// Coalesce [`Message::Changed`] so only the latest value is emitted,
+    /// once typing has paused for `window`. [`value`](Self::value) is always
+    /// current. See [`TextArea::with_change_throttle`].
+    pub fn with_change_throttle(mut self, window: std::time::Duration) -> Self {
+        self.inner = self.inner.with_change_throttle(window);
+        self
+    }
+
+    /// Emit [`Message::Edited`] before every [`Message::Changed`]
+    /// (default: false). Edits are reported one by one even when
+    /// [`with_change_throttle`](Self::with_change_throttle) delays
+    /// `Changed`. See [`TextArea::with_edit_events`].
+    pub fn with_edit_events(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.with_edit_events(enabled);
+        self
+    }
+
+    /// Show `‹`/`›` at the edges when text is scrolled out of view
+    /// (default: true).
+    pub fn with_scroll_indicators(mut self, show: bool) -> Self {
+        self.inner = self.inner.with_scroll_indicators(show);
+        self
+    }
+
+    /// Make repeated Tab presses cycle through every matching suggestion.
+    /// See [`TextArea::with_completion_cycling`].
+    pub fn with_completion_cycling(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.with_completion_cycling(enabled);
+        self
+    }
+
+    /// Transform pasted text before it is inserted. Defaults to
+    /// [`flatten_newlines`](text_area::flatten_newlines). See
+    /// [`TextArea::with_paste_filter`].
+    pub fn with_paste_filter(mut self, f: impl Fn(String) -> String + Send + 'static) -> Self {
+        self.inner = self.inner.with_paste_filter(f);
+        self
+    }
+
     /// Set the maximum number of characters allowed.
     pub fn with_char_limit(mut self, limit: usize) -> Self {
         self.inner = self.inner.with_char_limit(limit);
@@ -165,6 +285,10 @@ impl TextInput {
     }
 
     /// Set a validation function called after every change. Returns `Ok(())` or `Err(message)`.
+    ///
+    /// While validation fails the text (and border, if a block is set) is
+    /// drawn with [`TextInputStyle::error`], and the message is shown in the
+    /// bottom border of the block.
     pub fn with_validate(
         mut self,
         f: impl Fn(&str) -> Result<(), String> + Send + 'static,
@@ -209,11 +333,33 @@ impl TextInput {
         self.inner.blur();
     }
 
+    /// Enable or disable the input. A disabled input ignores keystrokes and
+    /// pastes and renders with [`TextInputStyle::disabled`].
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.inner.set_disabled(disabled);
+    }
+
+    /// Whether the input is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.inner.is_disabled()
+    }
+
     /// Get the current input value as a String.
     pub fn value(&self) -> String {
         self.inner.value()
     }
 
+    /// The value as displayed: masked per the echo mode, or formatted
+    /// through the input mask if one is set.
+    pub fn display_value(&self) -> String {
+        self.inner.display_value()
+    }
+
+    /// Return the currently selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        self.inner.selected_text()
+    }
+
     /// Programmatically set the input value and move cursor to end.
     pub fn set_value(&mut self, value: &str) {
         self.inner.set_value(value);
@@ -263,16 +409,6 @@ impl TextInput {
         self.inner.len()
     }
 
-    /// Return the display value based on the current echo mode.
-    #[cfg(test)]
-    fn display_value(&self) -> String {
-        match self.inner.echo_mode() {
-            text_area::EchoMode::Normal => self.inner.value(),
-            text_area::EchoMode::Password(c) => c.to_string().repeat(self.inner.len()),
-            text_area::EchoMode::Hidden => String::new(),
-        }
-    }
-
     /// Translate a text_input key event, remapping readline bindings that
     /// TextArea handles differently (Ctrl+A, Ctrl+E, Alt+B, Alt+F).
     fn translate_key(key: KeyEvent) -> KeyEvent {
@@ -315,8 +451,14 @@ impl Component for TextInput {
                 text_area::Message::KeyPress(translated)
             }
             Message::Paste(s) => text_area::Message::Paste(s),
-            // Changed and Submit are output-only messages; no-op if received.
-            Message::Changed(_) | Message::Submit(_) => return Command::none(),
+            Message::ChangeSettled(generation) => text_area::Message::ChangeSettled(generation),
+            // Output-only messages; no-op if received.
+            Message::Changed(_)
+            | Message::Submit(_)
+            | Message::NextField
+            | Message::Copy(_)
+            | Message::Cut(_)
+            | Message::Edited => return Command::none(),
         };
 
         let cmd = self.inner.update(inner_msg);
@@ -324,12 +466,14 @@ impl Component for TextInput {
         cmd.map(|ta_msg| match ta_msg {
             text_area::Message::Changed(s) => Message::Changed(s),
             text_area::Message::Submit(s) => Message::Submit(s),
+            text_area::Message::NextField => Message::NextField,
             text_area::Message::KeyPress(k) => Message::KeyPress(k),
             text_area::Message::Paste(s) => Message::Paste(s),
-            // TextInput never had Copy/Cut; drop them.
-            text_area::Message::Copy(_) | text_area::Message::Cut(_) => {
-                Message::Changed(String::new())
-            }
+            text_area::Message::Copy(s) => Message::Copy(s),
+            text_area::Message::Cut(s) => Message::Cut(s),
+            text_area::Message::PasteBlock(s) => Message::Paste(s),
+            text_area::Message::ChangeSettled(generation) => Message::ChangeSettled(generation),
+            text_area::Message::Edited { .. } => Message::Edited,
         })
     }
 
@@ -337,9 +481,17 @@ impl Component for TextInput {
         self.inner.view(frame, area);
     }
 
+    fn size_hint(&self, available: Rect) -> (Constraint, Constraint) {
+        self.inner.size_hint(available)
+    }
+
     fn focused(&self) -> bool {
         self.inner.focused()
     }
+
+    fn wants_escape(&self) -> bool {
+        self.inner.wants_escape()
+    }
 }
 
 /// Convert a text_input EchoMode to a text_area EchoMode.
@@ -357,10 +509,14 @@ fn convert_style(style: TextInputStyle) -> TextAreaStyle {
         text: style.text,
         cursor: style.cursor,
         line_number: Style::default().fg(Color::DarkGray),
-        selection: Style::default(),
+        selection: style.selection,
         prompt: style.prompt,
         placeholder: style.placeholder,
         suggestion: style.suggestion,
+        error: style.error,
+        disabled: style.disabled,
+        scroll_indicator: style.scroll_indicator,
+        ..TextAreaStyle::default()
     }
 }
 
@@ -389,6 +545,15 @@ mod tests {
         }
     }
 
+    fn key_shift(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
     fn key_alt(code: KeyCode) -> KeyEvent {
         KeyEvent {
             code,
@@ -398,6 +563,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_submit_key_replaces_enter() {
+        let mut input =
+            TextInput::new("").with_submit_keys(vec![KeyCombination::ctrl(KeyCode::Char('s'))]);
+        input.focus();
+        input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(cmd.into_message().is_none());
+
+        let cmd = input.update(Message::KeyPress(key_ctrl(KeyCode::Char('s'))));
+        assert!(matches!(cmd.into_message(), Some(Message::Submit(s)) if s == "a"));
+        assert_eq!(input.value(), "a");
+    }
+
+    #[test]
+    fn reassigned_enter_moves_to_next_field() {
+        let mut input = TextInput::new("")
+            .with_submit_keys(vec![KeyCombination::ctrl(KeyCode::Enter)])
+            .with_next_field(true);
+        input.focus();
+
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(matches!(cmd.into_message(), Some(Message::NextField)));
+
+        let cmd = input.update(Message::KeyPress(key_ctrl(KeyCode::Enter)));
+        assert!(matches!(cmd.into_message(), Some(Message::Submit(_))));
+    }
+
     #[test]
     fn new_text_input_is_empty() {
         let input = TextInput::new("placeholder");
@@ -657,6 +851,30 @@ mod tests {
         assert_eq!(input.len(), 5);
     }
 
+    #[test]
+    fn multi_line_paste_is_flattened_by_default() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.update(Message::Paste("first\r\nsecond\nthird\n".into()));
+        assert_eq!(input.value(), "first second third");
+
+        let mut input = TextInput::new("").with_paste_filter(text_area::first_line);
+        input.focus();
+        input.update(Message::Paste("first\nsecond".into()));
+        assert_eq!(input.value(), "first");
+    }
+
+    #[test]
+    fn paste_filter_runs_before_char_limit() {
+        let mut input = TextInput::new("")
+            .with_char_limit(5)
+            .with_paste_filter(|text| text.trim().to_string());
+        input.focus();
+        // Untrimmed, the leading spaces would use up the limit.
+        input.update(Message::Paste("   abcdefg  ".into()));
+        assert_eq!(input.value(), "abcde");
+    }
+
     #[test]
     fn paste_when_unfocused_is_ignored() {
         let mut input = TextInput::new("");
@@ -707,6 +925,318 @@ mod tests {
         assert!(input.err().is_none());
     }
 
+    #[test]
+    fn error_style_selected_when_validation_fails() {
+        let mut input = TextInput::new("")
+            .with_style(TextInputStyle {
+                error: Style::default().fg(Color::Magenta),
+                ..TextInputStyle::default()
+            })
+            .with_validate(|v| {
+                if v.len() < 2 {
+                    Err("too short".into())
+                } else {
+                    Ok(())
+                }
+            });
+        input.focus();
+        input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert_eq!(input.inner.text_style().fg, Some(Color::Magenta));
+
+        input.update(Message::KeyPress(key(KeyCode::Char('b'))));
+        assert_eq!(input.inner.text_style(), Style::default());
+    }
+
+    #[test]
+    fn error_message_rendered_in_block() {
+        use ratatui::widgets::Borders;
+
+        let mut input = TextInput::new("")
+            .with_block(Block::default().borders(Borders::ALL))
+            .with_validate(|v| {
+                if v.is_empty() {
+                    Ok(())
+                } else {
+                    Err("bad".into())
+                }
+            });
+        input.focus();
+        input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+
+        let backend = ratatui::backend::TestBackend::new(20, 3);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| input.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let bottom: String = (0..20).map(|x| buf[(x, 2)].symbol()).collect();
+        assert!(bottom.contains("bad"));
+        assert_eq!(buf[(0, 0)].fg, Color::Red);
+    }
+
+    fn render_row(input: &TextInput) -> String {
+        let backend = ratatui::backend::TestBackend::new(20, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| input.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..20).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    fn cycling_input() -> TextInput {
+        let mut input = TextInput::new("")
+            .with_suggestions(vec![
+                "apple".into(),
+                "apricot".into(),
+                "banana".into(),
+                "april".into(),
+            ])
+            .with_completion_cycling(true);
+        input.focus();
+        input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        input.update(Message::KeyPress(key(KeyCode::Char('p'))));
+        input
+    }
+
+    #[test]
+    fn repeated_tab_cycles_through_matches() {
+        let mut input = cycling_input();
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        assert_eq!(input.value(), "apple");
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        assert_eq!(input.value(), "apricot");
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        assert_eq!(input.value(), "april");
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        assert_eq!(input.value(), "apple");
+        input.update(Message::KeyPress(key_shift(KeyCode::BackTab)));
+        assert_eq!(input.value(), "april");
+
+        // Enter keeps the preview instead of submitting.
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Enter)));
+        assert!(cmd.is_none());
+        assert_eq!(input.value(), "april");
+        input.update(Message::KeyPress(key(KeyCode::Esc)));
+        assert_eq!(input.value(), "april");
+    }
+
+    #[test]
+    fn esc_restores_text_before_completion() {
+        let mut input = cycling_input();
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        assert_eq!(input.value(), "apricot");
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Esc)));
+        assert_eq!(input.value(), "ap");
+        assert!(matches!(cmd.into_message(), Some(Message::Changed(v)) if v == "ap"));
+
+        // Typing after a preview keeps it and carries on.
+        input.update(Message::KeyPress(key(KeyCode::Tab)));
+        input.update(Message::KeyPress(key(KeyCode::Char('s'))));
+        assert_eq!(input.value(), "apples");
+    }
+
+    #[test]
+    fn scroll_indicators_mark_hidden_text() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("short");
+        let row = render_row(&input);
+        assert!(!row.contains('‹') && !row.contains('›'), "{row:?}");
+
+        // The cursor sits at the end, so the start is scrolled out of view.
+        input.set_value("abcdefghijklmnopqrstuvwxyz0123");
+        let row = render_row(&input);
+        assert!(row.starts_with('‹'), "{row:?}");
+        assert!(!row.contains('›'), "{row:?}");
+
+        // Back at the start, only the tail is hidden.
+        input.update(Message::KeyPress(key(KeyCode::Home)));
+        let row = render_row(&input);
+        assert!(row.starts_with('a'), "{row:?}");
+        assert!(row.ends_with('›'), "{row:?}");
+        assert!(!row.contains('‹'), "{row:?}");
+    }
+
+    #[test]
+    fn scroll_indicators_use_style_and_can_be_disabled() {
+        let style = TextInputStyle {
+            scroll_indicator: Style::default().fg(Color::Magenta),
+            ..TextInputStyle::default()
+        };
+        let mut input = TextInput::new("").with_style(style);
+        input.set_value("abcdefghijklmnopqrstuvwxyz0123");
+        let backend = ratatui::backend::TestBackend::new(20, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| input.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        assert_eq!(buf[(0, 0)].symbol(), "‹");
+        assert_eq!(buf[(0, 0)].fg, Color::Magenta);
+
+        let mut input = TextInput::new("").with_scroll_indicators(false);
+        input.set_value("abcdefghijklmnopqrstuvwxyz0123");
+        let row = render_row(&input);
+        assert!(!row.contains('‹') && !row.contains('›'), "{row:?}");
+    }
+
+    #[test]
+    fn placeholder_hidden_while_focused_by_default() {
+        let mut input = TextInput::new("Search");
+        assert!(render_row(&input).starts_with("Search"));
+        input.focus();
+        assert!(!render_row(&input).contains("Search"));
+    }
+
+    #[test]
+    fn placeholder_shown_while_focused_when_enabled() {
+        let mut input = TextInput::new("Search").with_placeholder_while_focused(true);
+        input.focus();
+        assert!(render_row(&input).starts_with(" Search"));
+
+        input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert!(!render_row(&input).contains("Search"));
+    }
+
+    #[test]
+    fn terminal_cursor_tracks_caret_through_scrolling() {
+        let mut input = TextInput::new("").with_prompt("> ");
+        input.focus();
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(10, 1)).unwrap();
+        let mut cursor_after = |input: &TextInput| {
+            terminal.draw(|f| input.view(f, f.area())).unwrap();
+            terminal.get_cursor_position().unwrap()
+        };
+
+        type_str(&mut input, "abc");
+        assert_eq!(cursor_after(&input), (5, 0).into());
+
+        // Past the right edge the text scrolls and the cursor stays on the
+        // last column.
+        type_str(&mut input, "defghijk");
+        assert_eq!(cursor_after(&input), (9, 0).into());
+
+        input.update(Message::KeyPress(key(KeyCode::Home)));
+        assert_eq!(cursor_after(&input), (2, 0).into());
+    }
+
+    fn type_str(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.update(Message::KeyPress(key(KeyCode::Char(c))));
+        }
+    }
+
+    #[test]
+    fn mask_fills_slots_and_inserts_literals() {
+        let mut input = TextInput::new("").with_mask("(###) ###-####");
+        input.focus();
+        type_str(&mut input, "555");
+        assert_eq!(input.display_value(), "(555) ");
+        type_str(&mut input, "1234567");
+        assert_eq!(input.value(), "5551234567");
+        assert_eq!(input.display_value(), "(555) 123-4567");
+
+        // The mask is full.
+        type_str(&mut input, "8");
+        assert_eq!(input.value(), "5551234567");
+
+        assert!(render_row(&input).starts_with("(555) 123-4567"));
+    }
+
+    #[test]
+    fn mask_and_numeric_reject_non_digits() {
+        let mut input = TextInput::new("").with_mask("##/##");
+        input.focus();
+        type_str(&mut input, "1a2-");
+        assert_eq!(input.value(), "12");
+        assert_eq!(input.display_value(), "12/");
+
+        let mut input = TextInput::new("").with_numeric(true);
+        input.focus();
+        type_str(&mut input, "4x2 ");
+        input.update(Message::Paste("(7) 1".into()));
+        assert_eq!(input.value(), "4271");
+    }
+
+    #[test]
+    fn password_hides_masked_input() {
+        let mut input = TextInput::new("")
+            .with_mask("(###) ###")
+            .with_echo_mode(EchoMode::Password('*'));
+        input.focus();
+        type_str(&mut input, "1234");
+        assert_eq!(input.display_value(), "****");
+        assert!(render_row(&input).starts_with("****"));
+        assert!(!render_row(&input).contains(['1', '(']));
+    }
+
+    #[test]
+    fn set_value_filters_like_typing() {
+        let mut input = TextInput::new("").with_mask("(###) ###");
+        input.set_value("(555) 12a");
+        assert_eq!(input.value(), "55512");
+        assert_eq!(input.display_value(), "(555) 12");
+    }
+
+    #[test]
+    fn mask_cursor_maps_to_display_column() {
+        let mut input = TextInput::new("").with_mask("(###) ###");
+        input.focus();
+        type_str(&mut input, "1234");
+        input.update(Message::KeyPress(key(KeyCode::Left)));
+        let backend = ratatui::backend::TestBackend::new(12, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| input.view(frame, frame.area()))
+            .unwrap();
+        // Raw cursor 3 sits on the '4' at display column 6.
+        let buf = terminal.backend().buffer();
+        assert_eq!(buf[(6, 0)].symbol(), "4");
+        assert!(buf[(6, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn disabled_input_ignores_keystrokes() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("ab");
+        input.set_disabled(true);
+        assert!(input.is_disabled());
+
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Char('c'))));
+        assert!(cmd.is_none());
+        input.update(Message::KeyPress(key(KeyCode::Backspace)));
+        input.update(Message::Paste("zz".into()));
+        assert_eq!(input.value(), "ab");
+
+        input.set_disabled(false);
+        input.update(Message::KeyPress(key(KeyCode::Char('c'))));
+        assert_eq!(input.value(), "abc");
+    }
+
+    #[test]
+    fn disabled_input_renders_dimmed_without_cursor() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("ab");
+        input.set_disabled(true);
+
+        let backend = ratatui::backend::TestBackend::new(20, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| input.view(frame, frame.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let disabled = TextInputStyle::default().disabled;
+        assert_eq!(buf[(0, 0)].fg, disabled.fg.unwrap());
+        assert!(buf[(0, 0)].modifier.contains(Modifier::DIM));
+        // No reversed cursor cell after the text.
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
     #[test]
     fn validation_runs_on_paste() {
         let mut input = TextInput::new("").with_validate(|v| {
@@ -904,4 +1434,113 @@ mod tests {
         assert_eq!(input.value(), "long entry");
         assert_eq!(input.cursor_position(), 10); // cursor at end
     }
+
+    #[test]
+    fn size_hint_requests_one_line() {
+        let input = TextInput::new("");
+        let (_, height) = input.size_hint(Rect::new(0, 0, 40, 20));
+        assert_eq!(height, Constraint::Length(1));
+
+        let bordered = TextInput::new("").with_block(Block::bordered());
+        let (_, height) = bordered.size_hint(Rect::new(0, 0, 40, 20));
+        assert_eq!(height, Constraint::Length(3));
+    }
+
+    #[test]
+    fn shift_arrows_extend_selection() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("hello world");
+        input.update(Message::KeyPress(key_shift(KeyCode::Left)));
+        input.update(Message::KeyPress(key_shift(KeyCode::Left)));
+        assert_eq!(input.selected_text().as_deref(), Some("ld"));
+
+        input.update(Message::KeyPress(key_shift(KeyCode::Home)));
+        assert_eq!(input.selected_text().as_deref(), Some("hello world"));
+
+        // A plain arrow collapses the selection.
+        input.update(Message::KeyPress(key(KeyCode::Right)));
+        assert_eq!(input.selected_text(), None);
+    }
+
+    #[test]
+    fn typing_replaces_selection() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("hello world");
+        for _ in 0..5 {
+            input.update(Message::KeyPress(key_shift(KeyCode::Left)));
+        }
+        input.update(Message::KeyPress(key(KeyCode::Char('X'))));
+        assert_eq!(input.value(), "hello X");
+
+        input.update(Message::KeyPress(key_shift(KeyCode::Home)));
+        input.update(Message::KeyPress(key(KeyCode::Backspace)));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn ctrl_c_emits_selected_substring() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("hello world");
+        for _ in 0..5 {
+            input.update(Message::KeyPress(key_shift(KeyCode::Left)));
+        }
+        let cmd = input.update(Message::KeyPress(key_ctrl(KeyCode::Char('c'))));
+        assert!(matches!(cmd.into_message(), Some(Message::Copy(s)) if s == "world"));
+        assert_eq!(input.value(), "hello world");
+    }
+
+    #[test]
+    fn ctrl_x_removes_selection() {
+        let mut input = TextInput::new("");
+        input.focus();
+        input.set_value("hello world");
+        input.update(Message::KeyPress(key_shift(KeyCode::Home)));
+        let cmd = input.update(Message::KeyPress(key_ctrl(KeyCode::Char('x'))));
+        let first = cmd.into_batch().unwrap().into_iter().next().unwrap();
+        assert!(matches!(first.into_message(), Some(Message::Cut(s)) if s == "hello world"));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn throttled_typing_coalesces_changed() {
+        let mut input =
+            TextInput::new("").with_change_throttle(std::time::Duration::from_millis(100));
+        input.focus();
+        for c in "abc".chars() {
+            let cmd = input.update(Message::KeyPress(key(KeyCode::Char(c))));
+            // A timer is scheduled instead of an immediate Changed.
+            assert!(!cmd.is_none());
+            assert!(cmd.into_message().is_none());
+        }
+        assert_eq!(input.value(), "abc");
+
+        // Timers from the first two keystrokes are stale.
+        assert!(input.update(Message::ChangeSettled(1)).is_none());
+        assert!(input.update(Message::ChangeSettled(2)).is_none());
+        let cmd = input.update(Message::ChangeSettled(3));
+        assert!(matches!(cmd.into_message(), Some(Message::Changed(s)) if s == "abc"));
+    }
+
+    #[test]
+    fn edit_events_precede_throttled_changes() {
+        let mut input = TextInput::new("")
+            .with_edit_events(true)
+            .with_change_throttle(std::time::Duration::from_millis(100));
+        input.focus();
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        let first = cmd.into_batch().unwrap().into_iter().next().unwrap();
+        assert!(matches!(first.into_message(), Some(Message::Edited)));
+        assert!(input.update(Message::Edited).is_none());
+    }
+
+    #[test]
+    fn unthrottled_typing_emits_changed_immediately() {
+        let mut input = TextInput::new("");
+        input.focus();
+        let cmd = input.update(Message::KeyPress(key(KeyCode::Char('a'))));
+        assert!(matches!(cmd.into_message(), Some(Message::Changed(s)) if s == "a"));
+    }
 }
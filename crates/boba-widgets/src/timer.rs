@@ -1,5 +1,9 @@
 //! Countdown timer component that counts down from a specified duration.
+//!
+//! Remaining time is measured with a [`Clock`], so it stays accurate even if
+//! ticks are delayed or dropped. Ticks drive redraws and timeout detection.
 
+use crate::clock::{Clock, SystemClock};
 use boba_core::command::Command;
 use boba_core::component::Component;
 use boba_core::subscription::Subscription;
@@ -9,12 +13,14 @@ use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Messages for the timer component.
 #[derive(Debug, Clone)]
 pub enum Message {
-    /// Advance the countdown by one interval tick.
+    /// Periodic tick while running; refreshes the display and checks for
+    /// timeout.
     Tick,
     /// Start the timer.
     Start,
@@ -34,12 +40,15 @@ pub enum Message {
 /// When the timer reaches zero, it emits `Message::Timeout` and stops.
 pub struct Timer {
     timeout: Duration,
-    remaining: Duration,
-    running: bool,
+    /// Time consumed over previous runs.
+    consumed: Duration,
+    /// When the current run started, if running.
+    started_at: Option<Instant>,
     interval: Duration,
     timed_out: bool,
     style: Style,
     id: &'static str,
+    clock: Arc<dyn Clock>,
 }
 
 impl Timer {
@@ -48,15 +57,22 @@ impl Timer {
     pub fn new(id: &'static str, timeout: Duration) -> Self {
         Self {
             timeout,
-            remaining: timeout,
-            running: false,
+            consumed: Duration::ZERO,
+            started_at: None,
             interval: Duration::from_millis(100),
             timed_out: false,
             style: Style::default(),
             id,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Measure time with the given clock instead of the system clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Set the tick interval for time updates.
     pub fn with_interval(mut self, d: Duration) -> Self {
         self.interval = d;
@@ -71,12 +87,20 @@ impl Timer {
 
     /// Get the remaining time.
     pub fn remaining(&self) -> Duration {
-        self.remaining
+        self.timeout.saturating_sub(self.consumed())
+    }
+
+    /// Time consumed so far, including the current run.
+    fn consumed(&self) -> Duration {
+        match self.started_at {
+            Some(start) => self.consumed + self.clock.now().saturating_duration_since(start),
+            None => self.consumed,
+        }
     }
 
     /// Whether the timer is currently running.
     pub fn running(&self) -> bool {
-        self.running
+        self.started_at.is_some()
     }
 
     /// Whether the timer has reached zero.
@@ -86,26 +110,27 @@ impl Timer {
 
     /// Start the timer.
     pub fn start(&mut self) {
-        if !self.timed_out {
-            self.running = true;
+        if !self.timed_out && self.started_at.is_none() {
+            self.started_at = Some(self.clock.now());
         }
     }
 
     /// Stop (pause) the timer.
     pub fn stop(&mut self) {
-        self.running = false;
+        self.consumed = self.consumed();
+        self.started_at = None;
     }
 
     /// Reset the timer to its original timeout duration and stop.
     pub fn reset(&mut self) {
-        self.remaining = self.timeout;
-        self.running = false;
+        self.consumed = Duration::ZERO;
+        self.started_at = None;
         self.timed_out = false;
     }
 
     /// Toggle between running and stopped.
     pub fn toggle(&mut self) {
-        if self.running {
+        if self.running() {
             self.stop();
         } else {
             self.start();
@@ -128,15 +153,10 @@ impl Component for Timer {
     fn update(&mut self, msg: Message) -> Command<Message> {
         match msg {
             Message::Tick => {
-                if self.running && !self.timed_out {
-                    if self.remaining <= self.interval {
-                        self.remaining = Duration::ZERO;
-                        self.running = false;
-                        self.timed_out = true;
-                        return Command::message(Message::Timeout);
-                    } else {
-                        self.remaining -= self.interval;
-                    }
+                if self.running() && !self.timed_out && self.remaining().is_zero() {
+                    self.stop();
+                    self.timed_out = true;
+                    return Command::message(Message::Timeout);
                 }
                 Command::none()
             }
@@ -169,14 +189,14 @@ impl Component for Timer {
             return;
         }
 
-        let text = format_duration(self.remaining);
+        let text = format_duration(self.remaining());
         let span = Span::styled(text, self.style);
         let paragraph = Paragraph::new(span);
         frame.render_widget(paragraph, area);
     }
 
     fn subscriptions(&self) -> Vec<Subscription<Message>> {
-        if self.running && !self.timed_out {
+        if self.running() && !self.timed_out {
             vec![
                 boba_core::subscription::subscribe(Every::new(self.interval, self.id))
                     .map(|_: std::time::Instant| Message::Tick),
@@ -186,3 +206,41 @@ impl Component for Timer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    #[test]
+    fn remaining_follows_manual_clock() {
+        let clock = ManualClock::new();
+        let mut timer = Timer::new("t", Duration::from_secs(10)).with_clock(clock.clone());
+        timer.start();
+        clock.advance(Duration::from_millis(2500));
+        let cmd = timer.update(Message::Tick);
+        assert!(cmd.is_none());
+        assert_eq!(timer.remaining(), Duration::from_millis(7500));
+
+        timer.stop();
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(timer.remaining(), Duration::from_millis(7500));
+    }
+
+    #[test]
+    fn times_out_when_clock_passes_deadline() {
+        let clock = ManualClock::new();
+        let mut timer = Timer::new("t", Duration::from_secs(1)).with_clock(clock.clone());
+        timer.start();
+        clock.advance(Duration::from_secs(3));
+
+        let cmd = timer.update(Message::Tick);
+        assert!(matches!(cmd.into_message(), Some(Message::Timeout)));
+        assert!(timer.timed_out());
+        assert!(!timer.running());
+        assert_eq!(timer.remaining(), Duration::ZERO);
+
+        timer.reset();
+        assert_eq!(timer.remaining(), Duration::from_secs(1));
+    }
+}
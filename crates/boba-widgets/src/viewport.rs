@@ -1,5 +1,5 @@
 // ABOUTME: Scrollable content viewer with plain text, styled lines, and ANSI support.
-// ABOUTME: Features horizontal/vertical scroll, word wrap, mouse wheel, padding, and keyboard navigation.
+// ABOUTME: Features horizontal/vertical scroll, word wrap, line numbers, mouse wheel, padding, and keyboard navigation.
 
 //! Scrollable content area with support for plain text, styled lines,
 //! ANSI escape sequences, mouse wheel scrolling, and horizontal scroll.
@@ -8,9 +8,10 @@ use std::cell::Cell;
 
 use boba_core::command::Command;
 use boba_core::component::Component;
+use boba_core::impl_terminal_message;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{
     Block, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
@@ -54,6 +55,10 @@ pub enum Message {
     HalfViewDown,
 }
 
+impl_terminal_message!(Message {
+    key_press: KeyPress
+});
+
 /// Configurable key bindings for the viewport component.
 pub struct ViewportKeyBindings {
     /// Scroll up one line. Default: Up, k
@@ -177,13 +182,26 @@ pub struct Viewport {
     padding: Padding,
     key_seq: boba_core::key_sequence::KeySequenceTracker,
     key_bindings: ViewportKeyBindings,
+    /// When true, a right-aligned line-number gutter is drawn on the left.
+    line_numbers: bool,
 }
 
 /// Style configuration for the viewport.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ViewportStyle {
     /// Style applied to the vertical scrollbar.
     pub scrollbar: Style,
+    /// Style applied to the line-number gutter.
+    pub line_number: Style,
+}
+
+impl Default for ViewportStyle {
+    fn default() -> Self {
+        Self {
+            scrollbar: Style::default(),
+            line_number: Style::default().fg(Color::DarkGray),
+        }
+    }
 }
 
 impl Viewport {
@@ -207,6 +225,7 @@ impl Viewport {
             visible_width: Cell::new(80),
             key_seq: boba_core::key_sequence::KeySequenceTracker::new(),
             key_bindings: ViewportKeyBindings::default(),
+            line_numbers: false,
         }
     }
 
@@ -312,6 +331,24 @@ impl Viewport {
         self
     }
 
+    /// Show a right-aligned line-number gutter sized to the line count
+    /// (default: false). With word wrap, only the first row of each line
+    /// is numbered.
+    pub fn with_line_numbers(mut self, enabled: bool) -> Self {
+        self.line_numbers = enabled;
+        self
+    }
+
+    /// Width of the line-number gutter in columns, including its trailing
+    /// space, or 0 when line numbers are off.
+    pub fn gutter_width(&self) -> u16 {
+        if !self.line_numbers {
+            return 0;
+        }
+        let digits = self.line_widths().len().max(1).ilog10() + 1;
+        digits as u16 + 1
+    }
+
     /// Enable follow mode: automatically scroll to the bottom whenever
     /// content is updated, unless the user has scrolled away from the bottom.
     ///
@@ -431,37 +468,53 @@ impl Viewport {
         }
     }
 
+    /// Display width of every source line.
+    fn line_widths(&self) -> Vec<usize> {
+        if let Some(ref lines) = self.styled_content {
+            lines.iter().map(Line::width).collect()
+        } else {
+            self.content.lines().map(UnicodeWidthStr::width).collect()
+        }
+    }
+
+    /// Visual rows each source line takes up: one, or more when wrapped.
+    fn rows_per_line(&self) -> Vec<usize> {
+        let width = self.visible_width.get() as usize;
+        self.line_widths()
+            .into_iter()
+            .map(|w| {
+                if !self.word_wrap || w == 0 || width == 0 {
+                    1
+                } else {
+                    w.div_ceil(width)
+                }
+            })
+            .collect()
+    }
+
     /// Count total visual lines after word wrapping.
     fn wrapped_line_count(&self) -> usize {
-        let width = self.visible_width.get() as usize;
-        if width == 0 {
+        if self.visible_width.get() == 0 {
             return 0;
         }
-        if let Some(ref lines) = self.styled_content {
-            lines
-                .iter()
-                .map(|line| {
-                    let w = line.width();
-                    if w == 0 {
-                        1
-                    } else {
-                        w.div_ceil(width)
-                    }
-                })
-                .sum()
-        } else {
-            self.content
-                .lines()
-                .map(|line| {
-                    let w = UnicodeWidthStr::width(line);
-                    if w == 0 {
-                        1
-                    } else {
-                        w.div_ceil(width)
-                    }
-                })
-                .sum()
-        }
+        self.rows_per_line().into_iter().sum()
+    }
+
+    /// Gutter rows for the visible window: the line number on the first
+    /// row of each line, blank on wrapped continuation rows.
+    fn gutter_lines(&self, offset: u16, height: u16) -> Vec<Line<'static>> {
+        let digits = self.gutter_width().saturating_sub(1) as usize;
+        self.rows_per_line()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, rows)| {
+                std::iter::once(format!("{:>digits$} ", i + 1))
+                    .chain(std::iter::repeat_n(" ".repeat(digits + 1), rows - 1))
+            })
+            .skip(offset as usize)
+            .take(height as usize)
+            .map(|label| Line::styled(label, self.style.line_number))
+            .collect()
     }
 
     fn max_offset(&self, visible_height: u16) -> u16 {
@@ -600,13 +653,30 @@ impl Component for Viewport {
             }
         };
 
+        // Carve the gutter off the left; scrolling only moves the content.
+        let gutter = self.gutter_width().min(inner.width);
+        let content = Rect {
+            x: inner.x + gutter,
+            width: inner.width - gutter,
+            ..inner
+        };
+
         // Update visible dimensions via interior mutability.
-        self.visible_height.set(inner.height);
-        self.visible_width.set(inner.width);
+        self.visible_height.set(content.height);
+        self.visible_width.set(content.width);
 
-        let max = self.max_offset(inner.height);
+        let max = self.max_offset(content.height);
         let offset = self.offset.min(max);
 
+        if gutter > 0 && content.width > 0 {
+            let gutter_area = Rect {
+                width: gutter,
+                ..inner
+            };
+            let labels = self.gutter_lines(offset, inner.height);
+            frame.render_widget(Paragraph::new(labels), gutter_area);
+        }
+
         let text = if let Some(ref lines) = self.styled_content {
             Text::from(lines.clone())
         } else {
@@ -618,10 +688,10 @@ impl Component for Viewport {
             paragraph = paragraph.wrap(ratatui::widgets::Wrap { trim: false });
         }
 
-        frame.render_widget(paragraph, inner);
+        frame.render_widget(paragraph, content);
 
         // Render scrollbar if content exceeds visible area
-        if self.total_lines() > inner.height {
+        if self.total_lines() > content.height {
             let mut scrollbar_state = ScrollbarState::new(max as usize).position(offset as usize);
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
             frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
@@ -653,6 +723,65 @@ mod tests {
         assert_eq!(vp.padding().left, 1);
     }
 
+    fn render_rows(vp: &Viewport, width: u16, height: u16) -> Vec<String> {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| vp.view(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn gutter_width_scales_with_line_count() {
+        let lines = |n: usize| {
+            (1..=n)
+                .map(|i| format!("l{i}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_eq!(Viewport::new(lines(9)).gutter_width(), 0);
+        assert_eq!(
+            Viewport::new(lines(9))
+                .with_line_numbers(true)
+                .gutter_width(),
+            2
+        );
+        assert_eq!(
+            Viewport::new(lines(10))
+                .with_line_numbers(true)
+                .gutter_width(),
+            3
+        );
+        assert_eq!(
+            Viewport::new(lines(1000))
+                .with_line_numbers(true)
+                .gutter_width(),
+            5
+        );
+    }
+
+    #[test]
+    fn content_starts_after_gutter() {
+        let content = (1..=12)
+            .map(|i| format!("line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut vp = Viewport::new(content).with_line_numbers(true);
+        let rows = render_rows(&vp, 12, 3);
+        assert!(rows[0].starts_with(" 1 line1 "), "{rows:?}");
+        assert!(rows[2].starts_with(" 3 line3 "), "{rows:?}");
+
+        // Numbers scroll with the content; horizontal scroll leaves the
+        // gutter in place.
+        vp.update(Message::ScrollDown(9));
+        vp.update(Message::ScrollRight(4));
+        let rows = render_rows(&vp, 12, 3);
+        assert!(rows[0].starts_with("10 10 "), "{rows:?}");
+        assert!(rows[2].starts_with("12 12 "), "{rows:?}");
+    }
+
     #[test]
     fn follow_mode_scrolls_to_bottom_on_set_content() {
         let mut vp = Viewport::new("line1").with_follow(true);